@@ -0,0 +1,322 @@
+use crate::helpers::airgap::{verify_signatures, Handoff, HandoffStage, QrMatrix, SignatureCheck};
+use crate::helpers::genesis::GenesisSource;
+use crate::helpers::ledgers::IndyLedger;
+use crate::helpers::signer::Signer;
+use crate::helpers::wallet::IndyWallet;
+use crate::publish_tool::{write_review_ui, WriteReview, WriteTarget};
+use egui::{Color32, TextEdit, Ui};
+use futures_executor::block_on;
+use rfd::FileDialog;
+use std::collections::HashMap;
+
+#[derive(Default)]
+pub struct AirgapState {
+    /// Unsigned request to hand to the offline instance
+    pub request_text: String,
+    pub import_text: String,
+    pub imported: Option<Handoff>,
+    /// Hand-off produced by signing the imported request
+    pub signed: Option<Handoff>,
+    pub checks: Option<Vec<SignatureCheck>>,
+    pub qr: Option<Result<QrMatrix, String>>,
+    pub result: Option<Result<String, String>>,
+    pub review: WriteReview,
+}
+
+pub fn airgap_tool_ui(
+    ui: &mut Ui,
+    wallet: &Option<IndyWallet>,
+    ledgers: &Option<IndyLedger>,
+    target: WriteTarget<'_>,
+    publish_output: &str,
+    state: &mut AirgapState,
+) {
+    ui.label("Move requests between an online instance and an offline signing instance");
+    let genesis_source = target.genesis_source;
+
+    if let (Some(ledger), Some(pending)) = (
+        ledgers,
+        write_review_ui(ui.ctx(), &mut state.review, target),
+    ) {
+        state.result =
+            Some(block_on(ledger.submit_signed(&pending.request)).map_err(|e| format!("{:#}", e)));
+    }
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::CollapsingHeader::new("1. Export an unsigned request (online)")
+            .default_open(true)
+            .show(ui, |ui| {
+                export_ui(ui, genesis_source, publish_output, state)
+            });
+
+        egui::CollapsingHeader::new("2. Import a hand-off")
+            .default_open(true)
+            .show(ui, |ui| import_ui(ui, state));
+
+        if let Some(handoff) = state.imported.clone() {
+            ui.separator();
+            egui::CollapsingHeader::new("Sign (offline)")
+                .default_open(handoff.stage == HandoffStage::Unsigned)
+                .show(ui, |ui| sign_ui(ui, wallet, &handoff, state));
+            if handoff.stage == HandoffStage::Signed {
+                egui::CollapsingHeader::new("Verify and submit (online)")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        submit_ui(ui, ledgers, genesis_source, &handoff, state)
+                    });
+            }
+        }
+
+        if let Some(result) = &state.result {
+            ui.separator();
+            match result {
+                Ok(message) => ui.colored_label(Color32::GREEN, message),
+                Err(error) => ui.colored_label(Color32::RED, error),
+            };
+        }
+
+        if let Some(qr) = &state.qr {
+            ui.separator();
+            match qr {
+                Ok(matrix) => {
+                    if ui.button("Hide QR code").clicked() {
+                        state.qr = None;
+                    } else {
+                        qr_ui(ui, matrix);
+                    }
+                }
+                Err(error) => {
+                    ui.colored_label(Color32::RED, error);
+                }
+            }
+        }
+    });
+}
+
+fn export_ui(
+    ui: &mut Ui,
+    genesis_source: Option<&GenesisSource>,
+    publish_output: &str,
+    state: &mut AirgapState,
+) {
+    ui.label(
+        "Prepare the request in the Publish Tool with Sign and Send unchecked, then export it",
+    );
+    if ui.button("Use Publish Tool output").clicked() {
+        state.request_text = publish_output.to_string();
+    }
+    ui.add(
+        TextEdit::multiline(&mut state.request_text)
+            .code_editor()
+            .hint_text("Unsigned request JSON")
+            .desired_rows(6)
+            .desired_width(f32::INFINITY),
+    );
+
+    let network = genesis_source.map(GenesisSource::location);
+    match Handoff::unsigned(&state.request_text, network) {
+        Ok(handoff) => handoff_export_ui(ui, &handoff, state),
+        Err(e) if !state.request_text.trim().is_empty() => {
+            ui.colored_label(Color32::LIGHT_RED, format!("{:#}", e));
+        }
+        Err(_) => {}
+    }
+}
+
+fn import_ui(ui: &mut Ui, state: &mut AirgapState) {
+    ui.label("Load the file, or paste the text read from the QR code");
+    ui.add(
+        TextEdit::multiline(&mut state.import_text)
+            .code_editor()
+            .hint_text("Hand-off JSON")
+            .desired_rows(3)
+            .desired_width(f32::INFINITY),
+    );
+    ui.horizontal(|ui| {
+        if ui.button("📂 Load file").clicked() {
+            if let Some(path) = FileDialog::new().add_filter("JSON", &["json"]).pick_file() {
+                match std::fs::read_to_string(&path) {
+                    Ok(content) => state.import_text = content,
+                    Err(e) => state.result = Some(Err(format!("Failed to read file: {}", e))),
+                }
+            }
+        }
+        if ui.button("Import").clicked() {
+            state.signed = None;
+            state.checks = None;
+            state.result = None;
+            match Handoff::parse(&state.import_text) {
+                Ok(handoff) => state.imported = Some(handoff),
+                Err(e) => {
+                    state.imported = None;
+                    state.result = Some(Err(format!("{:#}", e)));
+                }
+            }
+        }
+    });
+
+    let Some(handoff) = &state.imported else {
+        return;
+    };
+    ui.separator();
+    egui::Grid::new("airgap_summary")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Stage");
+            ui.label(format!("{:?}", handoff.stage));
+            ui.end_row();
+            for (field, value) in handoff.summary() {
+                ui.label(field);
+                ui.colored_label(Color32::KHAKI, value);
+                ui.end_row();
+            }
+        });
+    egui::CollapsingHeader::new("Decoded transaction").show(ui, |ui| {
+        let mut json = serde_json::to_string_pretty(&handoff.request).unwrap_or_default();
+        ui.add(
+            TextEdit::multiline(&mut json)
+                .code_editor()
+                .interactive(false)
+                .desired_width(f32::INFINITY),
+        );
+    });
+}
+
+fn sign_ui(ui: &mut Ui, wallet: &Option<IndyWallet>, handoff: &Handoff, state: &mut AirgapState) {
+    let Some(wallet) = wallet else {
+        ui.colored_label(Color32::LIGHT_RED, "Please create a wallet first");
+        return;
+    };
+    if handoff.signers().iter().any(|did| did == wallet.did()) {
+        ui.colored_label(Color32::KHAKI, "Already signed by the wallet DID");
+    } else if ui.button(format!("✍ Sign with {}", wallet.did())).clicked() {
+        match block_on(handoff.sign(wallet)) {
+            Ok(signed) => {
+                state.signed = Some(signed);
+                state.result = None;
+            }
+            Err(e) => state.result = Some(Err(format!("Signing failed: {:#}", e))),
+        }
+    }
+
+    if let Some(signed) = state.signed.clone() {
+        ui.label("Hand the signed request back to the online instance");
+        handoff_export_ui(ui, &signed, state);
+    }
+}
+
+fn submit_ui(
+    ui: &mut Ui,
+    ledgers: &Option<IndyLedger>,
+    genesis_source: Option<&GenesisSource>,
+    handoff: &Handoff,
+    state: &mut AirgapState,
+) {
+    let Some(ledger) = ledgers else {
+        ui.colored_label(
+            Color32::LIGHT_RED,
+            "Please connect to a ledger in the Publish Tool first",
+        );
+        return;
+    };
+    let current = genesis_source.map(GenesisSource::location);
+    if handoff.network.is_some() && handoff.network.as_deref() != current {
+        ui.colored_label(
+            Color32::YELLOW,
+            format!(
+                "⚠ Prepared for {}, connected to {}",
+                handoff.network.as_deref().unwrap_or_default(),
+                current.unwrap_or("no network")
+            ),
+        );
+    }
+
+    if ui.button("🔍 Verify signatures").clicked() {
+        let mut verkeys = HashMap::new();
+        for did in handoff.signers() {
+            match block_on(ledger.get_verkey(&did)) {
+                Ok(Some(verkey)) => {
+                    verkeys.insert(did, verkey);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    state.result = Some(Err(format!("GET_NYM for {} failed: {}", did, e)));
+                }
+            }
+        }
+        match verify_signatures(&handoff.request, &verkeys) {
+            Ok(checks) => state.checks = Some(checks),
+            Err(e) => state.result = Some(Err(format!("{:#}", e))),
+        }
+    }
+
+    let Some(checks) = &state.checks else {
+        return;
+    };
+    if checks.is_empty() {
+        ui.colored_label(Color32::LIGHT_RED, "The request has no signature");
+    }
+    for check in checks {
+        match &check.result {
+            Ok(()) => ui.colored_label(Color32::GREEN, format!("✔ {}", check.did)),
+            Err(e) => ui.colored_label(Color32::RED, format!("✖ {}: {}", check.did, e)),
+        };
+    }
+
+    let verified = !checks.is_empty() && checks.iter().all(|check| check.result.is_ok());
+    if ui
+        .add_enabled(verified, egui::Button::new("📤 Submit to ledger"))
+        .clicked()
+    {
+        // Sent once confirmed in the write review
+        if let Err(e) = state.review.open(&handoff.request.to_string(), "Airgap") {
+            state.result = Some(Err(format!("Failed to review the request: {:#}", e)));
+        }
+    }
+}
+
+/// Save, copy and QR buttons for a hand-off
+fn handoff_export_ui(ui: &mut Ui, handoff: &Handoff, state: &mut AirgapState) {
+    ui.horizontal(|ui| {
+        if ui.button("💾 Save file").clicked() {
+            if let Some(path) = FileDialog::new()
+                .set_file_name(handoff.file_name())
+                .add_filter("JSON", &["json"])
+                .save_file()
+            {
+                state.result = Some(
+                    std::fs::write(&path, handoff.to_json())
+                        .map(|_| format!("Saved {}", path.display()))
+                        .map_err(|e| format!("Failed to save file: {}", e)),
+                );
+            }
+        }
+        if ui.button("📋 Copy").clicked() {
+            ui.output_mut(|o| o.copied_text = handoff.to_json());
+        }
+        if ui.button("Show QR code").clicked() {
+            state.qr = Some(QrMatrix::encode(&handoff.to_json()).map_err(|e| e.to_string()));
+        }
+    });
+}
+
+fn qr_ui(ui: &mut Ui, matrix: &QrMatrix) {
+    // Four modules of quiet zone on each side, as scanners expect
+    let modules = matrix.width + 8;
+    let side = ui.available_width().clamp(200.0, 480.0);
+    let module = side / modules as f32;
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(side, side), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, Color32::WHITE);
+    for (index, dark) in matrix.modules.iter().enumerate() {
+        if *dark {
+            let x = (index % matrix.width + 4) as f32 * module;
+            let y = (index / matrix.width + 4) as f32 * module;
+            painter.rect_filled(
+                egui::Rect::from_min_size(rect.min + egui::vec2(x, y), egui::vec2(module, module)),
+                0.0,
+                Color32::BLACK,
+            );
+        }
+    }
+}
@@ -1,15 +1,37 @@
+use crate::airgap_tool::{airgap_tool_ui, AirgapState};
+use crate::auth_rules_tool::{auth_rules_tool_ui, AuthRulesState};
+use crate::explorer_tool::{explorer_tool_ui, ExplorerState};
+use crate::external_signer_tool::{external_signer_tool_ui, ExternalSignerState};
 use crate::helpers::genesis::GenesisSource;
 use crate::helpers::ledgers::IndyLedger;
-use crate::helpers::wallet::IndyWallet;
+use crate::helpers::mirror::LedgerMirror;
+use crate::helpers::pool_admin::{self, GenesisNode, ScheduleEntry};
+use crate::helpers::request_json::is_signed;
+use crate::helpers::revocation;
+use crate::helpers::seed::SeedInput;
+use crate::helpers::session::SessionLock;
+use crate::helpers::signer::Signer;
+use crate::helpers::wallet::{IndyWallet, LockedWallet};
 use crate::helpers::workflow_guide::WorkflowGuide;
-use crate::indorser::endorser_tool;
-use crate::publish_tool::publish_tool_ui;
-use crate::wallet_tool::{create_wallet_ui, RecentUrls};
+use crate::indorser::{endorser_tool, EndorserState};
+use crate::key_rotation_tool::{key_rotation_tool_ui, KeyRotationState};
+use crate::ledger_diff_tool::{ledger_diff_tool_ui, LedgerDiffState};
+use crate::manage_did_tool::{manage_did_tool_ui, ManageDidState};
+use crate::publish_tool::{
+    load_custom_transaction, prepared_auth_rule_key, publish_tool_ui, FeeState, WriteReview,
+    WriteTarget,
+};
+use crate::resolver_tool::{resolver_tool_ui, ResolverState};
+use crate::schema_lookup_tool::{schema_lookup_tool_ui, SchemaLookupState};
+use crate::wallet_tool::{
+    create_wallet_ui, NetworkNamespaces, NetworkPoolSettings, ProductionNetworks, RecentUrls,
+};
 use egui::TextBuffer;
 use futures_executor::block_on;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::time::timeout;
+use zeroize::Zeroize;
 
 #[derive(PartialEq, Eq, Deserialize, Serialize, Debug)]
 pub enum MyRoles {
@@ -24,6 +46,19 @@ pub enum MyRoles {
 pub struct TransactionOptions {
     pub sign: bool,
     pub send: bool,
+    /// Give pasted requests without signatures a new reqId before they are signed
+    pub refresh_req_id: bool,
+}
+
+impl TransactionOptions {
+    /// Same signing options, without submitting: sent requests are reviewed first
+    pub fn without_send(&self) -> Self {
+        Self {
+            sign: self.sign,
+            send: false,
+            refresh_req_id: self.refresh_req_id,
+        }
+    }
 }
 
 impl Default for TransactionOptions {
@@ -31,6 +66,7 @@ impl Default for TransactionOptions {
         Self {
             sign: true,
             send: true,
+            refresh_req_id: true,
         }
     }
 }
@@ -43,11 +79,130 @@ pub struct SchemaInfo {
     pub schema_done_clicked: bool,
 }
 
+pub struct RevocRegDefInfo {
+    pub cred_def_id: String,
+    pub tag: String,
+    pub issuance_type: String,
+    pub max_cred_num: String,
+    pub tails_hash: String,
+    pub tails_location: String,
+    pub accum_key: String,
+}
+
+impl Default for RevocRegDefInfo {
+    fn default() -> Self {
+        Self {
+            cred_def_id: "".to_owned(),
+            tag: "".to_owned(),
+            issuance_type: revocation::ISSUANCE_TYPES[0].to_owned(),
+            max_cred_num: "".to_owned(),
+            tails_hash: "".to_owned(),
+            tails_location: "".to_owned(),
+            accum_key: "".to_owned(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct RevocRegEntryInfo {
+    pub revoc_reg_def_id: String,
+    pub prev_accum: String,
+    pub accum: String,
+    /// Max credential number of the registry, bounds the indices
+    pub max_cred_num: String,
+    pub issued: String,
+    pub revoked: String,
+}
+
+pub struct PoolAdminInfo {
+    pub upgrade_name: String,
+    pub upgrade_version: String,
+    pub upgrade_action: String,
+    pub upgrade_sha256: String,
+    pub upgrade_package: String,
+    pub upgrade_timeout: String,
+    pub upgrade_justification: String,
+    pub upgrade_reinstall: bool,
+    pub upgrade_force: bool,
+    pub schedule_start: String,
+    pub schedule_interval: String,
+    pub schedule: Vec<ScheduleEntry>,
+    pub config_writes: bool,
+    pub config_force: bool,
+    pub restart_action: String,
+    pub restart_datetime: String,
+}
+
+impl Default for PoolAdminInfo {
+    fn default() -> Self {
+        Self {
+            upgrade_name: "".to_owned(),
+            upgrade_version: "".to_owned(),
+            upgrade_action: pool_admin::POOL_ACTIONS[0].to_owned(),
+            upgrade_sha256: "".to_owned(),
+            upgrade_package: "indy-node".to_owned(),
+            upgrade_timeout: "10".to_owned(),
+            upgrade_justification: "".to_owned(),
+            upgrade_reinstall: false,
+            upgrade_force: false,
+            schedule_start: "".to_owned(),
+            schedule_interval: "5".to_owned(),
+            schedule: Vec::new(),
+            config_writes: true,
+            config_force: false,
+            restart_action: pool_admin::POOL_ACTIONS[0].to_owned(),
+            restart_datetime: "".to_owned(),
+        }
+    }
+}
+
+pub struct NodeInfo {
+    pub dest: String,
+    pub alias: String,
+    pub node_ip: String,
+    pub node_port: String,
+    pub client_ip: String,
+    pub client_port: String,
+    pub validator: bool,
+    pub blskey: String,
+    pub blskey_pop: String,
+    pub existing: Option<GenesisNode>,
+    /// Nodes read from the pool transactions of the connected ledger, parsed once
+    pub genesis_nodes: Option<Result<Vec<GenesisNode>, String>>,
+}
+
+impl Default for NodeInfo {
+    fn default() -> Self {
+        Self {
+            dest: "".to_owned(),
+            alias: "".to_owned(),
+            node_ip: "".to_owned(),
+            node_port: "".to_owned(),
+            client_ip: "".to_owned(),
+            client_port: "".to_owned(),
+            validator: true,
+            blskey: "".to_owned(),
+            blskey_pop: "".to_owned(),
+            existing: None,
+            genesis_nodes: None,
+        }
+    }
+}
+
 pub struct ToolVisibility {
     show_endorser: bool,
     show_publish_tool: bool,
     show_wallet_tool: bool,
     show_workflow_guide: bool,
+    show_resolver: bool,
+    show_schema_lookup: bool,
+    show_ledger_diff: bool,
+    show_key_rotation: bool,
+    show_manage_did: bool,
+    show_auth_rules: bool,
+    show_explorer: bool,
+    show_external_signer: bool,
+    show_airgap: bool,
 }
 
 #[derive(Debug)]
@@ -55,6 +210,8 @@ pub struct NymInfo {
     pub(crate) did: String,
     pub(crate) verkey: String,
     pub(crate) alias: Option<String>,
+    pub(crate) version: Option<i32>,
+    pub(crate) diddoc_content: String,
 }
 #[derive(PartialEq, Eq, Deserialize, Serialize, Debug)]
 pub enum DIDVersion {
@@ -81,20 +238,25 @@ impl Default for MyRoles {
 pub struct TemplateApp {
     // Example stuff:
     // #[serde(skip)] // This how you opt-out of serialization of a field
-    trustee_seed: String,
-    endorser_seed: String,
+    trustee_seed: SeedInput,
+    endorser_state: EndorserState,
     txn: String,
-    signed_txn_result: Option<String>,
     tool_visibility: ToolVisibility,
     genesis_source: Option<GenesisSource>,
     nym_role: MyRoles,
     did_version: DIDVersion,
     wallet: Option<IndyWallet>,
+    locked_wallet: Option<LockedWallet>,
+    session_lock: SessionLock,
     publish_option: String,
     nym_info: NymInfo,
     ledgers: Option<IndyLedger>,
     txn_result: String,
     schema_info: SchemaInfo,
+    revoc_reg_def_info: RevocRegDefInfo,
+    revoc_reg_entry_info: RevocRegEntryInfo,
+    pool_admin_info: PoolAdminInfo,
+    node_info: NodeInfo,
     genesis_url_input: String,
     ledger_connecting: bool,
     ledger_error: Option<String>,
@@ -104,31 +266,59 @@ pub struct TemplateApp {
     connection_start_time: Option<std::time::Instant>,
     transaction_options: TransactionOptions,
     recent_urls: RecentUrls,
+    network_namespaces: NetworkNamespaces,
+    network_pool_settings: NetworkPoolSettings,
+    production_networks: ProductionNetworks,
+    resolver_state: ResolverState,
+    schema_lookup_state: SchemaLookupState,
+    ledger_diff_state: LedgerDiffState,
+    key_rotation_state: KeyRotationState,
+    manage_did_state: ManageDidState,
+    auth_rules_state: AuthRulesState,
+    explorer_state: ExplorerState,
+    external_signer_state: ExternalSignerState,
+    airgap_state: AirgapState,
+    write_review: WriteReview,
+    fee_state: FeeState,
+    mirror: Option<LedgerMirror>,
+    strict_proofs: bool,
 }
 
 impl Default for TemplateApp {
     fn default() -> Self {
         Self {
             // Example stuff:
-            trustee_seed: "".to_owned(),
-            endorser_seed: "".to_owned(),
+            trustee_seed: SeedInput::default(),
+            endorser_state: EndorserState::default(),
             txn: "".to_owned(),
-            signed_txn_result: None,
             tool_visibility: ToolVisibility {
                 show_endorser: true,
                 show_publish_tool: true,
                 show_wallet_tool: true,
                 show_workflow_guide: true,
+                show_resolver: false,
+                show_schema_lookup: false,
+                show_ledger_diff: false,
+                show_key_rotation: false,
+                show_manage_did: false,
+                show_auth_rules: false,
+                show_explorer: false,
+                show_external_signer: false,
+                show_airgap: false,
             },
             genesis_source: Default::default(),
             nym_role: Default::default(),
             did_version: DIDVersion::Indy,
             wallet: None,
+            locked_wallet: None,
+            session_lock: SessionLock::default(),
             publish_option: "".to_owned(),
             nym_info: NymInfo {
                 did: "".to_owned(),
                 verkey: "".to_owned(),
                 alias: None,
+                version: None,
+                diddoc_content: "".to_owned(),
             },
             ledgers: None,
             txn_result: "".to_owned(),
@@ -139,6 +329,10 @@ impl Default for TemplateApp {
                 new_attribute: "".to_owned(),
                 schema_done_clicked: false,
             },
+            revoc_reg_def_info: RevocRegDefInfo::default(),
+            revoc_reg_entry_info: RevocRegEntryInfo::default(),
+            pool_admin_info: PoolAdminInfo::default(),
+            node_info: NodeInfo::default(),
             genesis_url_input: String::new(),
             ledger_connecting: false,
             ledger_error: None,
@@ -148,6 +342,22 @@ impl Default for TemplateApp {
             connection_start_time: None,
             transaction_options: TransactionOptions::default(),
             recent_urls: RecentUrls::new(10),
+            network_namespaces: NetworkNamespaces::default(),
+            network_pool_settings: NetworkPoolSettings::default(),
+            production_networks: ProductionNetworks::default(),
+            resolver_state: ResolverState::default(),
+            schema_lookup_state: SchemaLookupState::default(),
+            ledger_diff_state: LedgerDiffState::default(),
+            key_rotation_state: KeyRotationState::default(),
+            manage_did_state: ManageDidState::default(),
+            auth_rules_state: AuthRulesState::default(),
+            explorer_state: ExplorerState::default(),
+            external_signer_state: ExternalSignerState::default(),
+            airgap_state: AirgapState::default(),
+            write_review: WriteReview::default(),
+            fee_state: FeeState::default(),
+            mirror: None,
+            strict_proofs: false,
         }
     }
 }
@@ -160,18 +370,94 @@ impl TemplateApp {
 
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
+        let mut app = Self::default();
         if let Some(storage) = cc.storage {
             if let Some(urls_json) = storage.get_string("recent_urls") {
                 if let Ok(recent_urls) = serde_json::from_str(&urls_json) {
-                    return Self {
-                        recent_urls,
-                        ..Default::default()
-                    };
+                    app.recent_urls = recent_urls;
+                }
+            }
+            if let Some(namespaces_json) = storage.get_string("network_namespaces") {
+                if let Ok(network_namespaces) = serde_json::from_str(&namespaces_json) {
+                    app.network_namespaces = network_namespaces;
+                }
+            }
+            if let Some(settings_json) = storage.get_string("network_pool_settings") {
+                if let Ok(network_pool_settings) = serde_json::from_str(&settings_json) {
+                    app.network_pool_settings = network_pool_settings;
+                }
+            }
+            if let Some(production_json) = storage.get_string("production_networks") {
+                if let Ok(production_networks) = serde_json::from_str(&production_json) {
+                    app.production_networks = production_networks;
+                }
+            }
+            if let Some(lock_json) = storage.get_string("session_lock") {
+                if let Ok(session_lock) = serde_json::from_str(&lock_json) {
+                    app.session_lock = session_lock;
                 }
             }
         }
 
-        Default::default()
+        app
+    }
+
+    /// Whether anything would be dropped by locking the session
+    fn holds_secrets(&self) -> bool {
+        self.wallet.is_some()
+            || self.endorser_state.key.is_some()
+            || self.endorser_state.signed_txn_result.is_some()
+            || !self.trustee_seed.is_empty()
+            || !self.endorser_state.seed.is_empty()
+            || !self.key_rotation_state.new_seed.is_empty()
+            || self.key_rotation_state.new_key.is_some()
+            || self.key_rotation_state.review.pending.is_some()
+            || !self.txn_result.is_empty()
+            || self.holds_signed_txn()
+            || self.airgap_state.imported.is_some()
+            || self.airgap_state.signed.is_some()
+            || !self.airgap_state.import_text.is_empty()
+            || self.airgap_state.review.pending.is_some()
+            || self.write_review.pending.is_some()
+            || self.manage_did_state.result.is_some()
+            || self.manage_did_state.review.pending.is_some()
+            || self.auth_rules_state.result.is_some()
+            || self.auth_rules_state.review.pending.is_some()
+    }
+
+    /// Whether the custom transaction is signed, an unsigned one is kept on lock
+    fn holds_signed_txn(&self) -> bool {
+        serde_json::from_str::<serde_json::Value>(&self.txn)
+            .is_ok_and(|request| is_signed(&request))
+    }
+
+    /// Drops the keys, seeds and signed outputs held in memory. The wallet DID is
+    /// kept so that its seed can be asked again before the next signature.
+    fn lock_session(&mut self) {
+        if let Some(wallet) = self.wallet.take() {
+            self.locked_wallet = Some(wallet.lock());
+        }
+        self.trustee_seed.clear();
+        self.endorser_state.seed.clear();
+        self.endorser_state.unload_key();
+        self.key_rotation_state.new_seed.clear();
+        self.key_rotation_state = KeyRotationState::default();
+        self.txn_result.zeroize();
+        if self.holds_signed_txn() {
+            self.txn.zeroize();
+        }
+        self.airgap_state.import_text.zeroize();
+        self.airgap_state.imported = None;
+        self.airgap_state.signed = None;
+        self.airgap_state.checks = None;
+        self.airgap_state.qr = None;
+        self.airgap_state.review = WriteReview::default();
+        self.write_review = WriteReview::default();
+        self.manage_did_state.result = None;
+        self.manage_did_state.review = WriteReview::default();
+        self.auth_rules_state.result = None;
+        self.auth_rules_state.review = WriteReview::default();
+        log::info!("Session locked");
     }
 }
 
@@ -181,6 +467,18 @@ impl eframe::App for TemplateApp {
         // Put your widgets into a `SidePanel`, `TopBottomPanel`, `CentralPanel`, `Window` or `Area`.
         // For inspiration and more examples, go to https://emilk.github.io/egui
 
+        let now = Instant::now();
+        if ctx.input(|i| !i.events.is_empty()) {
+            self.session_lock.touch(now);
+        }
+        if self.session_lock.expired(now) && self.holds_secrets() {
+            self.lock_session();
+        }
+        // Wake up to lock an idle session, nothing to wait for once it holds no secret
+        if let (true, Some(remaining)) = (self.holds_secrets(), self.session_lock.remaining(now)) {
+            ctx.request_repaint_after(remaining);
+        }
+
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             // The top panel is often a good place for a menu bar:
 
@@ -197,6 +495,21 @@ impl eframe::App for TemplateApp {
                 }
 
                 egui::widgets::global_dark_light_mode_buttons(ui);
+                ui.separator();
+                if ui
+                    .add_enabled(self.holds_secrets(), egui::Button::new("🔒 Lock"))
+                    .on_hover_text("Drop keys, seeds and signed outputs from memory")
+                    .clicked()
+                {
+                    self.lock_session();
+                }
+                ui.label("Auto-lock after");
+                ui.add(
+                    egui::DragValue::new(&mut self.session_lock.timeout_minutes)
+                        .clamp_range(0..=240)
+                        .suffix(" min"),
+                )
+                .on_hover_text("0 disables the auto-lock");
             });
         });
         egui::SidePanel::right("side_panel")
@@ -209,12 +522,33 @@ impl eframe::App for TemplateApp {
                 ui.checkbox(&mut self.tool_visibility.show_endorser, "Endorser Tool");
                 ui.checkbox(&mut self.tool_visibility.show_publish_tool, "Publish Tool");
                 ui.checkbox(&mut self.tool_visibility.show_wallet_tool, "Wallet Tool");
+                ui.checkbox(&mut self.tool_visibility.show_resolver, "DID Resolver");
+                ui.checkbox(
+                    &mut self.tool_visibility.show_schema_lookup,
+                    "Schema Lookup",
+                );
+                ui.checkbox(&mut self.tool_visibility.show_ledger_diff, "Ledger Diff");
+                ui.checkbox(&mut self.tool_visibility.show_key_rotation, "Key Rotation");
+                ui.checkbox(&mut self.tool_visibility.show_manage_did, "Manage DID");
+                ui.checkbox(&mut self.tool_visibility.show_auth_rules, "Auth Rules");
+                ui.checkbox(&mut self.tool_visibility.show_explorer, "Ledger Explorer");
+                ui.checkbox(
+                    &mut self.tool_visibility.show_external_signer,
+                    "External Signer",
+                );
+                ui.checkbox(&mut self.tool_visibility.show_airgap, "Air-Gapped Signing");
                 ui.checkbox(&mut self.tool_visibility.show_workflow_guide, "Guide");
                 ui.separator();
                 if ui.button("Organize windows").clicked() {
                     ui.ctx().memory_mut(|mem| mem.reset_areas());
                 }
             });
+        // A mirror only belongs to the network it was opened for
+        if self.mirror.as_ref().is_some_and(|mirror| {
+            self.genesis_source.as_ref().map(GenesisSource::location) != Some(mirror.location())
+        }) {
+            self.mirror = None;
+        }
         egui::CentralPanel::default().show(ctx, |ui| {
             // The central panel the region left after adding TopPanel's and SidePanel's
             // Show the guide as a window if enabled
@@ -233,10 +567,38 @@ impl eframe::App for TemplateApp {
                     .show(ui.ctx(), |ui| {
                         endorser_tool(
                             ui,
-                            &mut self.endorser_seed,
+                            &mut self.endorser_state,
                             &mut self.txn,
-                            &mut self.signed_txn_result,
                             &mut self.did_version,
+                            &mut self.transaction_options.refresh_req_id,
+                            self.ledgers.as_ref(),
+                            self.mirror.as_ref(),
+                            self.external_signer_state.endorser_signer(),
+                        );
+                    });
+            }
+            if self.tool_visibility.show_external_signer {
+                egui::Window::new("External Signer")
+                    .default_size([500.0, 250.0])
+                    .show(ui.ctx(), |ui| {
+                        external_signer_tool_ui(ui, &mut self.external_signer_state);
+                    });
+            }
+            if self.tool_visibility.show_airgap {
+                egui::Window::new("Air-Gapped Signing")
+                    .default_size([600.0, 500.0])
+                    .show(ui.ctx(), |ui| {
+                        airgap_tool_ui(
+                            ui,
+                            &self.wallet,
+                            &self.ledgers,
+                            write_target(
+                                &self.genesis_source,
+                                &self.network_namespaces,
+                                &self.production_networks,
+                            ),
+                            &self.txn_result,
+                            &mut self.airgap_state,
                         );
                     });
             }
@@ -254,13 +616,143 @@ impl eframe::App for TemplateApp {
                     create_wallet_ui(ui,
                                      &mut self.trustee_seed,
                                      &mut self.wallet,
+                                     &mut self.locked_wallet,
                                      &mut self.genesis_source,
                                      &mut self.did_version,
                                      &mut self.genesis_url_input,
-                                     &mut self.recent_urls,)
+                                     &mut self.recent_urls,
+                                     &mut self.network_namespaces,
+                                     &mut self.network_pool_settings,
+                                     &mut self.production_networks,)
                         .expect("Something went wrong with the wallet creation");
                 });
             }
+            if self.tool_visibility.show_resolver {
+                egui::Window::new("DID Resolver")
+                    .default_size([600.0, 400.0])
+                    .show(ui.ctx(), |ui| {
+                        let namespace = self
+                            .genesis_source
+                            .as_ref()
+                            .and_then(|source| self.network_namespaces.get(source));
+                        resolver_tool_ui(
+                            ui,
+                            &self.ledgers,
+                            &self.mirror,
+                            namespace,
+                            &mut self.resolver_state,
+                        );
+                    });
+            }
+            if self.tool_visibility.show_schema_lookup {
+                egui::Window::new("Schema Lookup")
+                    .default_size([600.0, 400.0])
+                    .show(ui.ctx(), |ui| {
+                        let namespace = self
+                            .genesis_source
+                            .as_ref()
+                            .and_then(|source| self.network_namespaces.get(source));
+                        schema_lookup_tool_ui(
+                            ui,
+                            &self.wallet,
+                            &self.ledgers,
+                            &self.mirror,
+                            namespace,
+                            &mut self.schema_lookup_state,
+                        );
+                    });
+            }
+            if self.tool_visibility.show_ledger_diff {
+                egui::Window::new("Ledger Diff")
+                    .default_size([600.0, 400.0])
+                    .show(ui.ctx(), |ui| {
+                        ledger_diff_tool_ui(
+                            ui,
+                            &self.wallet,
+                            &self.ledgers,
+                            &self.genesis_source,
+                            &self.recent_urls,
+                            &self.network_pool_settings,
+                            &mut self.ledger_diff_state,
+                        );
+                    });
+                if let Some(request) = self.ledger_diff_state.open_in_publish.take() {
+                    load_custom_transaction(&mut self.publish_option, &mut self.txn, &request);
+                    self.tool_visibility.show_publish_tool = true;
+                }
+            }
+            if self.tool_visibility.show_key_rotation {
+                egui::Window::new("Key Rotation")
+                    .default_size([500.0, 300.0])
+                    .show(ui.ctx(), |ui| {
+                        key_rotation_tool_ui(
+                            ui,
+                            &mut self.wallet,
+                            &self.ledgers,
+                            write_target(
+                                &self.genesis_source,
+                                &self.network_namespaces,
+                                &self.production_networks,
+                            ),
+                            &mut self.key_rotation_state,
+                        );
+                    });
+            }
+            if self.tool_visibility.show_manage_did {
+                egui::Window::new("Manage existing DID")
+                    .default_size([600.0, 400.0])
+                    .show(ui.ctx(), |ui| {
+                        manage_did_tool_ui(
+                            ui,
+                            &self.wallet,
+                            &self.ledgers,
+                            write_target(
+                                &self.genesis_source,
+                                &self.network_namespaces,
+                                &self.production_networks,
+                            ),
+                            &mut self.transaction_options,
+                            &mut self.manage_did_state,
+                        );
+                    });
+            }
+            if self.tool_visibility.show_auth_rules {
+                egui::Window::new("Auth Rules")
+                    .default_size([800.0, 500.0])
+                    .show(ui.ctx(), |ui| {
+                        let prepared_key = prepared_auth_rule_key(
+                            &self.publish_option,
+                            &self.nym_role,
+                            &self.txn,
+                        );
+                        auth_rules_tool_ui(
+                            ui,
+                            &self.wallet,
+                            &self.ledgers,
+                            prepared_key,
+                            write_target(
+                                &self.genesis_source,
+                                &self.network_namespaces,
+                                &self.production_networks,
+                            ),
+                            &mut self.transaction_options,
+                            &mut self.auth_rules_state,
+                        );
+                    });
+            }
+            if self.tool_visibility.show_explorer {
+                egui::Window::new("Ledger Explorer")
+                    .default_size([800.0, 500.0])
+                    .show(ui.ctx(), |ui| {
+                        explorer_tool_ui(
+                            ui,
+                            &self.ledgers,
+                            &self.genesis_source,
+                            &mut self.mirror,
+                            &mut self.explorer_state,
+                        );
+                    });
+            }
             if self.tool_visibility.show_publish_tool {
                 egui::Window::new("Publish Tool")
                     .default_size([600.0, 300.0])
@@ -269,7 +761,8 @@ impl eframe::App for TemplateApp {
                         ui.separator();
 
                         // Check wallet and genesis separately
-                        let has_wallet = self.wallet.is_some();
+                        let has_wallet = self.wallet.is_some()
+                            || self.external_signer_state.publish_signer().is_some();
                         let has_genesis = self.genesis_source.is_some();
 
                         // Check if genesis source has changed
@@ -286,10 +779,17 @@ impl eframe::App for TemplateApp {
                                 self.ledger_error = None;
                                 self.genesis_content = None;
                                 self.ledger_connecting = false;
+                                self.fee_state.reset_detection();
+                                self.node_info.genesis_nodes = None;
                             }
                         }
 
-                        if !has_wallet {
+                        if !has_wallet && self.locked_wallet.is_some() {
+                            ui.colored_label(
+                                egui::Color32::YELLOW,
+                                "🔒 Wallet locked, enter its seed in the Wallet Tool to sign again"
+                            );
+                        } else if !has_wallet {
                             ui.colored_label(
                                 egui::Color32::LIGHT_RED,
                                 "Please create a wallet first"
@@ -348,6 +848,11 @@ impl eframe::App for TemplateApp {
 
                         // Only proceed if we have both
                         if has_wallet && has_genesis {
+                            let pool_settings = self
+                                .genesis_source
+                                .as_ref()
+                                .map(|source| self.network_pool_settings.get(source))
+                                .unwrap_or_default();
                             // Check if we're already connected and not in an error state
                             if self.ledgers.is_none() && !self.ledger_connecting && self.ledger_error.is_none() {
                                 // Set connecting flag to true
@@ -359,9 +864,9 @@ impl eframe::App for TemplateApp {
                                     ui.label("Connecting to ledger...");
                                     if let Some(start_time) = self.connection_start_time {
                                         let elapsed = start_time.elapsed().as_secs();
-                                        if elapsed >= 20 {  // Overall timeout of 20 seconds
+                                        if elapsed >= pool_settings.connect_timeout {  // Overall timeout from the pool settings
                                             self.ledger_connecting = false;
-                                            self.ledger_error = Some("Connection attempt timed out after 20 seconds".to_string());
+                                            self.ledger_error = Some(format!("Connection attempt timed out after {} seconds", pool_settings.connect_timeout));
                                             self.connection_start_time = None;
                                         } else {
                                             ui.label(format!("({} seconds)", elapsed));
@@ -373,8 +878,8 @@ impl eframe::App for TemplateApp {
                                     // Move connection attempt into a block_on block to ensure completion
                                     let connection_result = block_on(async {
                                         // Wrap the entire connection process in a timeout
-                                        timeout(Duration::from_secs(20), async {
-                                            match IndyLedger::new(self.genesis_source.clone().unwrap()).await {
+                                        timeout(Duration::from_secs(pool_settings.connect_timeout), async {
+                                            match IndyLedger::new(self.genesis_source.clone().unwrap(), &pool_settings).await {
                                                 Ok(ledger) => {
                                                     match ledger.check_connection().await {
                                                         Ok(true) => {
@@ -398,9 +903,11 @@ impl eframe::App for TemplateApp {
 
                                     // Handle connection result
                                     match connection_result {
-                                        Ok(ledger) => {
+                                        Ok(mut ledger) => {
+                                            ledger.strict_proofs = self.strict_proofs;
                                             self.ledgers = Some(ledger);
                                             self.ledger_error = None;
+                                            self.node_info.genesis_nodes = None;
                                         }
                                         Err(e) => {
                                             log::error!("{}", e);
@@ -468,19 +975,51 @@ impl eframe::App for TemplateApp {
                             }
 
                             // Continue with UI if connected successfully
-                            if self.ledgers.is_some() {
+                            if let Some(ledger) = &mut self.ledgers {
+                                if ui
+                                    .checkbox(
+                                        &mut self.strict_proofs,
+                                        "Strict mode: reject NYM/verkey replies without a verified state proof",
+                                    )
+                                    .changed()
+                                {
+                                    ledger.strict_proofs = self.strict_proofs;
+                                }
+                            }
+                            let signer: Option<&dyn Signer> = self
+                                .external_signer_state
+                                .publish_signer()
+                                .or(self.wallet.as_ref().map(|wallet| wallet as &dyn Signer));
+                            if let (Some(signer), true) = (signer, self.ledgers.is_some()) {
+                                let namespace = self
+                                    .genesis_source
+                                    .as_ref()
+                                    .and_then(|source| self.network_namespaces.get(source));
+                                let production = self
+                                    .genesis_source
+                                    .as_ref()
+                                    .is_some_and(|source| self.production_networks.contains(source));
                                 publish_tool_ui(
                                     ui,
-                                    &mut self.wallet,
+                                    signer,
                                     &mut self.publish_option,
                                     &mut self.nym_role,
                                     &mut self.nym_info,
                                     &mut self.genesis_source,
+                                    namespace,
                                     &mut self.ledgers,
                                     &mut self.txn_result,
                                     &mut self.schema_info,
+                                    &mut self.revoc_reg_def_info,
+                                    &mut self.revoc_reg_entry_info,
+                                    &mut self.pool_admin_info,
+                                    &mut self.node_info,
                                     &mut self.txn,
                                     &mut self.transaction_options,
+                                    self.mirror.as_ref(),
+                                    production,
+                                    &mut self.write_review,
+                                    &mut self.fee_state,
                                 ).expect("Failed to render publish tool UI");
                             }
                         }
@@ -500,10 +1039,36 @@ impl eframe::App for TemplateApp {
             if let Ok(urls_json) = serde_json::to_string(&self.recent_urls) {
                 storage.set_string("recent_urls", urls_json);
             }
+            if let Ok(namespaces_json) = serde_json::to_string(&self.network_namespaces) {
+                storage.set_string("network_namespaces", namespaces_json);
+            }
+            if let Ok(settings_json) = serde_json::to_string(&self.network_pool_settings) {
+                storage.set_string("network_pool_settings", settings_json);
+            }
+            if let Ok(production_json) = serde_json::to_string(&self.production_networks) {
+                storage.set_string("production_networks", production_json);
+            }
+            if let Ok(lock_json) = serde_json::to_string(&self.session_lock) {
+                storage.set_string("session_lock", lock_json);
+            }
         }
     }
 }
 
+/// Current network, as shown when reviewing a write
+fn write_target<'a>(
+    genesis_source: &'a Option<GenesisSource>,
+    network_namespaces: &'a NetworkNamespaces,
+    production_networks: &ProductionNetworks,
+) -> WriteTarget<'a> {
+    let genesis_source = genesis_source.as_ref();
+    WriteTarget {
+        genesis_source,
+        namespace: genesis_source.and_then(|source| network_namespaces.get(source)),
+        production: genesis_source.is_some_and(|source| production_networks.contains(source)),
+    }
+}
+
 // Add this helper function at the bottom of the file
 fn show_genesis_content(ui: &mut egui::Ui, content: &str) {
     egui::ScrollArea::vertical()
@@ -521,3 +1086,73 @@ fn show_genesis_content(ui: &mut egui::Ui, content: &str) {
             }
         });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zeroize::Zeroizing;
+
+    const SIGNED_REQUEST: &str = r#"{"identifier":"V4SGRU86Z58d6TV7PBUe6f","operation":{"type":"1","dest":"Th7MpTaRZVRYnPiabds81Y"},"reqId":1,"signature":"sig"}"#;
+
+    #[test]
+    fn test_lock_session_leaves_no_secret() {
+        let secrets: Vec<fn(&mut TemplateApp)> = vec![
+            |app| app.trustee_seed.text = Zeroizing::new("seed".to_string()),
+            |app| app.endorser_state.seed.text = Zeroizing::new("seed".to_string()),
+            |app| app.endorser_state.signed_txn_result = Some(SIGNED_REQUEST.to_string()),
+            |app| app.key_rotation_state.new_seed.text = Zeroizing::new("seed".to_string()),
+            |app| app.key_rotation_state.new_key = Some(IndyWallet::create_key(None).unwrap()),
+            |app| {
+                app.key_rotation_state
+                    .review
+                    .open(SIGNED_REQUEST, "KeyRotation")
+                    .unwrap()
+            },
+            |app| app.txn_result = SIGNED_REQUEST.to_string(),
+            |app| app.txn = SIGNED_REQUEST.to_string(),
+            |app| app.airgap_state.import_text = SIGNED_REQUEST.to_string(),
+            |app| {
+                app.airgap_state
+                    .review
+                    .open(SIGNED_REQUEST, "Airgap")
+                    .unwrap()
+            },
+            |app| app.write_review.open(SIGNED_REQUEST, "Custom").unwrap(),
+            |app| app.manage_did_state.result = Some(Ok(SIGNED_REQUEST.to_string())),
+            |app| {
+                app.manage_did_state
+                    .review
+                    .open(SIGNED_REQUEST, "ManageDid")
+                    .unwrap()
+            },
+            |app| app.auth_rules_state.result = Some(Ok(SIGNED_REQUEST.to_string())),
+            |app| {
+                app.auth_rules_state
+                    .review
+                    .open(SIGNED_REQUEST, "AuthRules")
+                    .unwrap()
+            },
+        ];
+        for (index, hold_secret) in secrets.into_iter().enumerate() {
+            let mut app = TemplateApp::default();
+            assert!(!app.holds_secrets());
+            hold_secret(&mut app);
+            assert!(app.holds_secrets(), "secret {} is not detected", index);
+            app.lock_session();
+            assert!(
+                !app.holds_secrets(),
+                "secret {} is left after a lock",
+                index
+            );
+        }
+
+        // An unsigned custom transaction is not a secret and is kept
+        let mut app = TemplateApp {
+            txn: r#"{"operation":{"type":"1"},"reqId":1}"#.to_string(),
+            ..TemplateApp::default()
+        };
+        assert!(!app.holds_secrets());
+        app.lock_session();
+        assert!(!app.txn.is_empty());
+    }
+}
@@ -0,0 +1,477 @@
+use crate::app::TransactionOptions;
+use crate::helpers::auth_rules::{
+    build_auth_rule, check_constraint, describe_constraint, role_name, AuthRuleKey, ConstraintCheck,
+};
+use crate::helpers::ledgers::{IndyLedger, ReadReply};
+use crate::helpers::state_proof::ProofStatus;
+use crate::helpers::txn_types::txn_type_label;
+use crate::helpers::wallet::IndyWallet;
+use crate::publish_tool::{held_write_result, write_review_ui, WriteReview, WriteTarget};
+use egui::Ui;
+use futures_executor::block_on;
+use indy_vdr::ledger::requests::auth_rule::AuthRuleData;
+use serde_json::Value;
+
+const DEFAULT_CONSTRAINT: &str = r#"{
+  "constraint_id": "ROLE",
+  "role": "0",
+  "sig_count": 1,
+  "need_to_be_owner": false,
+  "metadata": {}
+}"#;
+
+pub struct AuthRuleEditor {
+    pub auth_type: String,
+    pub auth_action: String,
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub constraint: String,
+}
+
+impl Default for AuthRuleEditor {
+    fn default() -> Self {
+        Self {
+            auth_type: "1".to_string(),
+            auth_action: "ADD".to_string(),
+            field: "role".to_string(),
+            old_value: String::new(),
+            new_value: "101".to_string(),
+            constraint: DEFAULT_CONSTRAINT.to_string(),
+        }
+    }
+}
+
+impl AuthRuleEditor {
+    fn key(&self) -> AuthRuleKey {
+        AuthRuleKey {
+            auth_type: self.auth_type.trim().to_string(),
+            auth_action: self.auth_action.clone(),
+            field: self.field.trim().to_string(),
+            old_value: (self.auth_action == "EDIT").then(|| self.old_value.trim().to_string()),
+            new_value: self.new_value.trim().to_string(),
+        }
+    }
+
+    fn load(&mut self, rule: &Value) {
+        let key = AuthRuleKey::from_rule(rule);
+        self.auth_type = key.auth_type;
+        self.auth_action = key.auth_action;
+        self.field = key.field;
+        self.old_value = key.old_value.unwrap_or_default();
+        self.new_value = key.new_value;
+        self.constraint = serde_json::to_string_pretty(&rule["constraint"]).unwrap_or_default();
+    }
+}
+
+#[derive(Default)]
+pub struct AuthRulesState {
+    pub rules: Option<Result<ReadReply<Vec<Value>>, String>>,
+    pub type_filter: String,
+    pub field_filter: String,
+    pub editor: AuthRuleEditor,
+    pub batch: Vec<AuthRuleData>,
+    pub result: Option<Result<String, String>>,
+    pub review: WriteReview,
+    pub checked_key: Option<AuthRuleKey>,
+    pub check: Option<Result<ConstraintCheck, String>>,
+}
+
+pub fn auth_rules_tool_ui(
+    ui: &mut Ui,
+    wallet: &Option<IndyWallet>,
+    ledgers: &Option<IndyLedger>,
+    prepared_key: Option<AuthRuleKey>,
+    target: WriteTarget<'_>,
+    transaction_options: &mut TransactionOptions,
+    state: &mut AuthRulesState,
+) {
+    ui.label("Browse, check and edit the auth rules of the ledger");
+
+    let Some(ledger) = ledgers else {
+        ui.colored_label(
+            egui::Color32::LIGHT_RED,
+            "Please connect to a ledger in the Publish Tool first",
+        );
+        return;
+    };
+
+    if let Some(pending) = write_review_ui(ui.ctx(), &mut state.review, target) {
+        state.result =
+            Some(block_on(ledger.submit_signed(&pending.request)).map_err(|e| e.to_string()));
+    }
+
+    egui::CollapsingHeader::new("Ledger auth rules")
+        .default_open(true)
+        .show(ui, |ui| rules_table_ui(ui, ledger, state));
+
+    egui::CollapsingHeader::new("Checker")
+        .default_open(true)
+        .show(ui, |ui| checker_ui(ui, ledger, wallet, prepared_key, state));
+
+    egui::CollapsingHeader::new("Constraint editor").show(ui, |ui| {
+        editor_ui(ui, ledger, wallet, transaction_options, state)
+    });
+}
+
+fn rules_table_ui(ui: &mut Ui, ledger: &IndyLedger, state: &mut AuthRulesState) {
+    ui.horizontal(|ui| {
+        if ui.button("Fetch auth rules").clicked() {
+            state.rules = Some(block_on(ledger.get_auth_rules(None)).map_err(|e| e.to_string()));
+        }
+        ui.label("Txn type:");
+        ui.add(egui::TextEdit::singleline(&mut state.type_filter).desired_width(80.0));
+        ui.label("Field:");
+        ui.add(egui::TextEdit::singleline(&mut state.field_filter).desired_width(80.0));
+    });
+
+    let rules = match &state.rules {
+        Some(Ok(reply)) => {
+            reply.proof.show(ui);
+            &reply.data
+        }
+        Some(Err(e)) => {
+            ui.colored_label(
+                egui::Color32::LIGHT_RED,
+                format!("Failed to fetch auth rules: {}", e),
+            );
+            return;
+        }
+        None => return,
+    };
+
+    let type_filter = state.type_filter.trim().to_lowercase();
+    let field_filter = state.field_filter.trim().to_lowercase();
+    let visible: Vec<&Value> = rules
+        .iter()
+        .filter(|rule| {
+            let txn_type = txn_type_label(rule["auth_type"].as_str().unwrap_or_default());
+            txn_type.to_lowercase().contains(&type_filter)
+                && rule["field"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_lowercase()
+                    .contains(&field_filter)
+        })
+        .collect();
+    ui.label(format!("{} of {} rules", visible.len(), rules.len()));
+
+    let mut selected = None;
+    egui::ScrollArea::vertical()
+        .max_height(300.0)
+        .show(ui, |ui| {
+            egui::Grid::new("auth_rules_table")
+                .num_columns(7)
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in [
+                        "Txn type",
+                        "Action",
+                        "Field",
+                        "Old",
+                        "New",
+                        "Constraint",
+                        "",
+                    ] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+
+                    for (index, rule) in visible.iter().enumerate() {
+                        ui.label(txn_type_label(
+                            rule["auth_type"].as_str().unwrap_or_default(),
+                        ));
+                        ui.label(rule["auth_action"].as_str().unwrap_or_default());
+                        ui.monospace(rule["field"].as_str().unwrap_or_default());
+                        ui.monospace(rule["old_value"].as_str().unwrap_or("-"));
+                        ui.monospace(rule["new_value"].as_str().unwrap_or("-"));
+                        ui.vertical(|ui| {
+                            constraint_tree_ui(
+                                ui,
+                                &rule["constraint"],
+                                egui::Id::new("auth_rule").with(index),
+                            )
+                        });
+                        if ui.small_button("✏ Edit").clicked() {
+                            selected = Some(*rule);
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some(rule) = selected {
+        state.editor.load(rule);
+    }
+}
+
+/// Shows AND/OR constraints as collapsible nodes and ROLE constraints as leaves
+fn constraint_tree_ui(ui: &mut Ui, constraint: &Value, id: egui::Id) {
+    match constraint["auth_constraints"].as_array() {
+        Some(constraints) => {
+            egui::CollapsingHeader::new(describe_constraint(constraint))
+                .id_source(id)
+                .show(ui, |ui| {
+                    for (index, inner) in constraints.iter().enumerate() {
+                        constraint_tree_ui(ui, inner, id.with(index));
+                    }
+                });
+        }
+        None => {
+            ui.label(describe_constraint(constraint));
+        }
+    }
+}
+
+fn checker_ui(
+    ui: &mut Ui,
+    ledger: &IndyLedger,
+    wallet: &Option<IndyWallet>,
+    prepared_key: Option<AuthRuleKey>,
+    state: &mut AuthRulesState,
+) {
+    let Some(wallet) = wallet else {
+        ui.label("Create a wallet to check its permissions");
+        return;
+    };
+    let Some(key) = prepared_key else {
+        ui.label("Select a transaction in the Publish Tool to check its auth rule");
+        return;
+    };
+
+    ui.label(format!(
+        "Transaction being prepared: {} {}, field {} → {}",
+        key.auth_action,
+        txn_type_label(&key.auth_type),
+        key.field,
+        key.new_value
+    ));
+
+    if ui.button("Check wallet DID").clicked() {
+        state.check = Some(check_prepared(ledger, wallet, &key));
+        state.checked_key = Some(key.clone());
+    }
+
+    if state.checked_key.as_ref() != Some(&key) {
+        return;
+    }
+    match &state.check {
+        Some(Ok(check)) => {
+            if check.satisfied {
+                ui.colored_label(
+                    egui::Color32::GREEN,
+                    "✔ The wallet DID can write this transaction alone",
+                );
+            } else {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    "✘ The wallet DID alone does not satisfy the auth rule, the ledger will reject it without an endorser or more signatures",
+                );
+            }
+            for line in &check.explanation {
+                ui.monospace(line);
+            }
+        }
+        Some(Err(e)) => {
+            ui.colored_label(egui::Color32::LIGHT_RED, e);
+        }
+        None => {}
+    }
+}
+
+fn check_prepared(
+    ledger: &IndyLedger,
+    wallet: &IndyWallet,
+    key: &AuthRuleKey,
+) -> Result<ConstraintCheck, String> {
+    let submitter_reply = block_on(ledger.get_nym(&wallet.did))
+        .map_err(|e| format!("Failed to fetch the wallet NYM: {}", e))?;
+    let submitter_nym = submitter_reply
+        .data
+        .ok_or_else(|| "The wallet DID is not on this ledger".to_string())?;
+    let rules = block_on(ledger.get_auth_rules(Some(key)))
+        .map_err(|e| format!("Failed to fetch the auth rule: {}", e))?;
+    let rule = rules
+        .data
+        .first()
+        .ok_or_else(|| "The ledger has no auth rule for this transaction".to_string())?;
+
+    let role = submitter_nym["role"].as_str();
+    // New objects are owned by their submitter
+    let mut check = check_constraint(&rule["constraint"], role, true);
+    check
+        .explanation
+        .insert(0, format!("Wallet DID role: {}", role_name(role)));
+    if let Some(proof) = ProofStatus::combine([submitter_reply.proof, rules.proof]) {
+        check
+            .explanation
+            .push(format!("Ledger replies: {}", proof.label()));
+    }
+    Ok(check)
+}
+
+fn editor_ui(
+    ui: &mut Ui,
+    ledger: &IndyLedger,
+    wallet: &Option<IndyWallet>,
+    transaction_options: &mut TransactionOptions,
+    state: &mut AuthRulesState,
+) {
+    let editor = &mut state.editor;
+    egui::Grid::new("auth_rule_editor")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Txn type");
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut editor.auth_type).desired_width(80.0));
+                ui.label(txn_type_label(editor.auth_type.trim()));
+            });
+            ui.end_row();
+
+            ui.label("Action");
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut editor.auth_action, "ADD".to_string(), "ADD");
+                ui.radio_value(&mut editor.auth_action, "EDIT".to_string(), "EDIT");
+            });
+            ui.end_row();
+
+            ui.label("Field");
+            ui.text_edit_singleline(&mut editor.field);
+            ui.end_row();
+
+            if editor.auth_action == "EDIT" {
+                ui.label("Old value");
+                ui.text_edit_singleline(&mut editor.old_value);
+                ui.end_row();
+            }
+
+            ui.label("New value");
+            ui.text_edit_singleline(&mut editor.new_value);
+            ui.end_row();
+        });
+
+    ui.label("Constraint:");
+    ui.add(
+        egui::TextEdit::multiline(&mut editor.constraint)
+            .code_editor()
+            .desired_rows(8)
+            .desired_width(f32::INFINITY),
+    );
+
+    let rule = build_auth_rule(&editor.key(), &editor.constraint);
+    match &rule {
+        Ok(_) => {
+            if let Ok(constraint) = serde_json::from_str::<Value>(&editor.constraint) {
+                constraint_tree_ui(ui, &constraint, egui::Id::new("auth_rule_editor_tree"));
+            }
+        }
+        Err(e) => {
+            ui.colored_label(egui::Color32::LIGHT_RED, format!("{:#}", e));
+        }
+    }
+
+    let Some(wallet) = wallet else {
+        ui.colored_label(egui::Color32::LIGHT_RED, "Please create a wallet first");
+        return;
+    };
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut transaction_options.sign, "Sign Transaction");
+        ui.checkbox(&mut transaction_options.send, "Send to Ledger");
+    });
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(rule.is_ok(), egui::Button::new("Build AUTH_RULE"))
+            .clicked()
+        {
+            if let Ok(rule) = &rule {
+                let request = block_on(ledger.publish_auth_rule(
+                    wallet,
+                    rule,
+                    &transaction_options.without_send(),
+                ));
+                state.result = held_write_result(
+                    request.map_err(|e| e.to_string()),
+                    transaction_options.send,
+                    "AuthRule",
+                    &mut state.review,
+                );
+            }
+        }
+        if ui
+            .add_enabled(rule.is_ok(), egui::Button::new("➕ Add to batch"))
+            .clicked()
+        {
+            if let Ok(rule) = rule {
+                state.batch.push(rule);
+            }
+        }
+    });
+
+    if !state.batch.is_empty() {
+        ui.label(format!("Batch: {} rules", state.batch.len()));
+        let mut removed = None;
+        for (index, rule) in state.batch.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let key = serde_json::to_value(rule)
+                    .map(|rule| AuthRuleKey::from_rule(&rule))
+                    .unwrap_or_else(|_| AuthRuleKey::add("", "", ""));
+                ui.monospace(format!(
+                    "{} {} {} {} → {}",
+                    key.auth_action,
+                    txn_type_label(&key.auth_type),
+                    key.field,
+                    key.old_value.as_deref().unwrap_or("-"),
+                    key.new_value
+                ));
+                if ui.small_button("🗑").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            state.batch.remove(index);
+        }
+        ui.horizontal(|ui| {
+            if ui.button("Build AUTH_RULES").clicked() {
+                let request = block_on(ledger.publish_auth_rules(
+                    wallet,
+                    state.batch.clone(),
+                    &transaction_options.without_send(),
+                ));
+                state.result = held_write_result(
+                    request.map_err(|e| e.to_string()),
+                    transaction_options.send,
+                    "AuthRules",
+                    &mut state.review,
+                );
+            }
+            if ui.button("Clear batch").clicked() {
+                state.batch.clear();
+            }
+        });
+    }
+
+    match &state.result {
+        Some(Ok(reply)) => {
+            ui.separator();
+            ui.colored_label(egui::Color32::GREEN, "Transaction result:");
+            egui::ScrollArea::vertical()
+                .id_source("auth_rule_result")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    ui.monospace(reply);
+                });
+            if ui.button("📋 Copy Transaction").clicked() {
+                ui.output_mut(|o| o.copied_text = reply.clone());
+            }
+        }
+        Some(Err(e)) => {
+            ui.separator();
+            ui.colored_label(egui::Color32::RED, format!("Transaction failed: {}", e));
+        }
+        None => {}
+    }
+}
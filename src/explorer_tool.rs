@@ -0,0 +1,335 @@
+use crate::helpers::explorer::{summarize_txn, TxnSummary, LEDGER_TYPES};
+use crate::helpers::genesis::GenesisSource;
+use crate::helpers::ledgers::IndyLedger;
+use crate::helpers::mirror::{LedgerMirror, MirrorQuery};
+use crate::helpers::state_proof::ProofStatus;
+use crate::helpers::txn_types::txn_type_label;
+use egui::Ui;
+use futures_executor::block_on;
+use serde_json::Value;
+
+pub struct ExplorerState {
+    pub ledger_type: i32,
+    pub from: String,
+    pub page_size: i32,
+    pub txns: Vec<(TxnSummary, Value)>,
+    pub type_filter: String,
+    pub submitter_filter: String,
+    pub selected: Option<usize>,
+    pub error: Option<String>,
+    /// State proof status of the loaded page, `None` for mirror results
+    pub proof: Option<ProofStatus>,
+    pub offline: bool,
+    pub sync_count: i32,
+    pub mirror_query: MirrorQuery,
+    pub mirror_value: String,
+    pub mirror_status: Option<String>,
+}
+
+impl Default for ExplorerState {
+    fn default() -> Self {
+        Self {
+            ledger_type: LEDGER_TYPES[0].1,
+            from: "1".to_string(),
+            page_size: 20,
+            txns: Vec::new(),
+            type_filter: String::new(),
+            submitter_filter: String::new(),
+            selected: None,
+            error: None,
+            proof: None,
+            offline: false,
+            sync_count: 100,
+            mirror_query: MirrorQuery::Did,
+            mirror_value: String::new(),
+            mirror_status: None,
+        }
+    }
+}
+
+impl ExplorerState {
+    fn show_txns(&mut self, txns: Vec<Value>) {
+        self.txns = txns
+            .into_iter()
+            .map(|txn| (summarize_txn(&txn), txn))
+            .collect();
+    }
+
+    fn load_page(&mut self, ledger: Option<&IndyLedger>, mirror: Option<&LedgerMirror>) {
+        self.txns.clear();
+        self.selected = None;
+        self.error = None;
+        self.proof = None;
+        let from = match self.from.trim().parse::<i32>() {
+            Ok(from) if from > 0 => from,
+            _ => {
+                self.error = Some("The first seq_no must be a positive number".to_string());
+                return;
+            }
+        };
+        let txns = match (self.offline, mirror, ledger) {
+            (true, Some(mirror), _) if self.ledger_type == LEDGER_TYPES[0].1 => {
+                Ok(mirror.range(from as u64, self.page_size as usize))
+            }
+            (true, _, _) => Err("Only a synced DOMAIN ledger can be browsed offline".to_string()),
+            (false, _, Some(ledger)) => {
+                block_on(ledger.get_txn_range(self.ledger_type, from, self.page_size))
+                    .map(|reply| {
+                        self.proof = Some(reply.proof);
+                        reply.data
+                    })
+                    .map_err(|e| format!("Failed to fetch transactions: {}", e))
+            }
+            (false, _, None) => {
+                Err("Please connect to a ledger in the Publish Tool first".to_string())
+            }
+        };
+        match txns {
+            Ok(txns) => {
+                if txns.is_empty() {
+                    self.error = Some(format!("No transaction from seq_no {}", from));
+                }
+                self.show_txns(txns);
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    fn move_page(
+        &mut self,
+        ledger: Option<&IndyLedger>,
+        mirror: Option<&LedgerMirror>,
+        forward: bool,
+    ) {
+        let from = self.from.trim().parse::<i32>().unwrap_or(1);
+        let from = if forward {
+            from.saturating_add(self.page_size)
+        } else {
+            (from - self.page_size).max(1)
+        };
+        self.from = from.to_string();
+        self.load_page(ledger, mirror);
+    }
+}
+
+pub fn explorer_tool_ui(
+    ui: &mut Ui,
+    ledgers: &Option<IndyLedger>,
+    genesis_source: &Option<GenesisSource>,
+    mirror: &mut Option<LedgerMirror>,
+    state: &mut ExplorerState,
+) {
+    ui.label("Browse the transactions of the connected ledgers");
+
+    if ledgers.is_none() && mirror.is_none() {
+        ui.colored_label(
+            egui::Color32::LIGHT_RED,
+            "Please connect to a ledger in the Publish Tool first",
+        );
+        return;
+    }
+
+    egui::CollapsingHeader::new("Local mirror").show(ui, |ui| {
+        mirror_ui(ui, ledgers, genesis_source, mirror, state)
+    });
+    let ledger = ledgers.as_ref();
+    let mirror = mirror.as_ref();
+
+    ui.horizontal(|ui| {
+        for (name, ledger_type) in LEDGER_TYPES {
+            if ui
+                .radio_value(&mut state.ledger_type, ledger_type, name)
+                .changed()
+            {
+                state.from = "1".to_string();
+                state.txns.clear();
+                state.selected = None;
+            }
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("From seq_no:");
+        ui.add(egui::TextEdit::singleline(&mut state.from).desired_width(80.0));
+        ui.label("Page size:");
+        ui.add(egui::DragValue::new(&mut state.page_size).clamp_range(1..=100));
+        if ui.button("Load").clicked() {
+            state.load_page(ledger, mirror);
+        }
+        if ui.button("◀ Previous").clicked() {
+            state.move_page(ledger, mirror, false);
+        }
+        if ui.button("Next ▶").clicked() {
+            state.move_page(ledger, mirror, true);
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("Type:");
+        ui.add(egui::TextEdit::singleline(&mut state.type_filter).desired_width(100.0));
+        ui.label("Submitter DID:");
+        ui.add(egui::TextEdit::singleline(&mut state.submitter_filter).desired_width(200.0));
+    });
+
+    if let Some(error) = &state.error {
+        ui.colored_label(egui::Color32::LIGHT_RED, error);
+    }
+    if let Some(proof) = &state.proof {
+        proof.show(ui);
+    }
+
+    txn_table_ui(
+        ui,
+        &state.txns,
+        &state.type_filter,
+        &state.submitter_filter,
+        &mut state.selected,
+    );
+}
+
+fn mirror_ui(
+    ui: &mut Ui,
+    ledgers: &Option<IndyLedger>,
+    genesis_source: &Option<GenesisSource>,
+    mirror: &mut Option<LedgerMirror>,
+    state: &mut ExplorerState,
+) {
+    ui.label("Keep a local copy of the DOMAIN ledger to search it offline");
+
+    let Some(current) = mirror else {
+        match genesis_source {
+            Some(genesis_source) => {
+                if ui.button("Open mirror for this network").clicked() {
+                    match LedgerMirror::for_network(genesis_source) {
+                        Ok(opened) => *mirror = Some(opened),
+                        Err(e) => state.mirror_status = Some(format!("Error: {:#}", e)),
+                    }
+                }
+            }
+            None => {
+                ui.label("Select a genesis file to open its mirror");
+            }
+        }
+        if let Some(status) = &state.mirror_status {
+            ui.label(status);
+        }
+        return;
+    };
+
+    if current.is_empty() {
+        ui.label("The mirror is empty, sync it to fetch the first transactions");
+    } else {
+        ui.label(format!(
+            "{} DOMAIN transactions mirrored (last seq_no {})",
+            current.len(),
+            current.last_seq_no()
+        ));
+    }
+    ui.horizontal(|ui| {
+        ui.label("Transactions per sync:");
+        ui.add(egui::DragValue::new(&mut state.sync_count).clamp_range(1..=1000));
+        if let Some(ledger) = ledgers {
+            if ui.button("🔄 Sync").clicked() {
+                state.mirror_status =
+                    Some(match block_on(current.sync(ledger, state.sync_count)) {
+                        Ok(0) => "The mirror is up to date".to_string(),
+                        Ok(added) => format!("Added {} transactions", added),
+                        Err(e) => format!("Error: {:#}", e),
+                    });
+            }
+        }
+        ui.checkbox(&mut state.offline, "Browse the mirror (offline)");
+    });
+    if let Some(status) = &state.mirror_status {
+        ui.label(status);
+    }
+
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source("mirror_query")
+            .selected_text(state.mirror_query.label())
+            .show_ui(ui, |ui| {
+                for query in MirrorQuery::ALL {
+                    ui.selectable_value(&mut state.mirror_query, query, query.label());
+                }
+            });
+        ui.add(egui::TextEdit::singleline(&mut state.mirror_value).desired_width(250.0));
+        if ui.button("🔍 Search").clicked() {
+            let results: Vec<Value> = current
+                .query(state.mirror_query, &state.mirror_value)
+                .into_iter()
+                .cloned()
+                .collect();
+            state.error = results
+                .is_empty()
+                .then(|| "No transaction found in the mirror".to_string());
+            state.selected = None;
+            state.proof = None;
+            state.show_txns(results);
+        }
+    });
+}
+
+/// Summary table with a full JSON view of the selected transaction
+fn txn_table_ui(
+    ui: &mut Ui,
+    txns: &[(TxnSummary, Value)],
+    type_filter: &str,
+    submitter_filter: &str,
+    selected: &mut Option<usize>,
+) {
+    if txns.is_empty() {
+        return;
+    }
+
+    let visible: Vec<usize> = (0..txns.len())
+        .filter(|index| txns[*index].0.matches(type_filter, submitter_filter))
+        .collect();
+    ui.label(format!("{} of {} transactions", visible.len(), txns.len()));
+
+    egui::ScrollArea::vertical()
+        .id_source("explorer_table")
+        .max_height(300.0)
+        .show(ui, |ui| {
+            egui::Grid::new("explorer_txns")
+                .num_columns(5)
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in ["Seq No", "Type", "Submitter", "Time", "Summary"] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+
+                    for index in visible {
+                        let summary = &txns[index].0;
+                        if ui
+                            .selectable_label(*selected == Some(index), summary.seq_no.to_string())
+                            .clicked()
+                        {
+                            *selected = Some(index);
+                        }
+                        ui.label(txn_type_label(&summary.txn_type));
+                        ui.monospace(&summary.submitter);
+                        ui.label(&summary.time);
+                        ui.label(&summary.summary);
+                        ui.end_row();
+                    }
+                });
+        });
+
+    let Some((summary, txn)) = selected.and_then(|index| txns.get(index)) else {
+        return;
+    };
+    ui.separator();
+    ui.colored_label(
+        egui::Color32::KHAKI,
+        format!("Transaction #{}", summary.seq_no),
+    );
+    let json = serde_json::to_string_pretty(txn).unwrap_or_default();
+    egui::ScrollArea::vertical()
+        .id_source("explorer_json")
+        .max_height(300.0)
+        .show(ui, |ui| {
+            ui.monospace(&json);
+        });
+    if ui.button("📋 Copy JSON").clicked() {
+        ui.output_mut(|o| o.copied_text = json);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::helpers::external_signer::{ExternalSigner, SignerEndpoint};
+use crate::helpers::signer::Signer;
+use egui::{TextEdit, Ui};
+use futures_executor::block_on;
+
+pub struct ExternalSignerState {
+    pub endpoint: String,
+    pub signer: Option<ExternalSigner>,
+    /// Sign Publish Tool writes with the external key instead of the wallet DID
+    pub use_for_publish: bool,
+    /// Endorse pasted transactions with the external key instead of a seed
+    pub use_for_endorser: bool,
+    pub error: Option<String>,
+}
+
+impl Default for ExternalSignerState {
+    fn default() -> Self {
+        Self {
+            endpoint: "http://127.0.0.1:8710/".to_owned(),
+            signer: None,
+            use_for_publish: false,
+            use_for_endorser: false,
+            error: None,
+        }
+    }
+}
+
+impl ExternalSignerState {
+    pub fn publish_signer(&self) -> Option<&dyn Signer> {
+        self.signer
+            .as_ref()
+            .filter(|_| self.use_for_publish)
+            .map(|signer| signer as &dyn Signer)
+    }
+
+    pub fn endorser_signer(&self) -> Option<&dyn Signer> {
+        self.signer
+            .as_ref()
+            .filter(|_| self.use_for_endorser)
+            .map(|signer| signer as &dyn Signer)
+    }
+}
+
+pub fn external_signer_tool_ui(ui: &mut Ui, state: &mut ExternalSignerState) {
+    ui.label(
+        "Sign with a key held by a local signing service (HSM, KMS or hardware wallet bridge)",
+    );
+    ui.label("Endpoint: http://127.0.0.1:<port>/ or unix:<socket path>");
+
+    ui.horizontal(|ui| {
+        if ui
+            .add(TextEdit::singleline(&mut state.endpoint).desired_width(300.0))
+            .changed()
+        {
+            state.signer = None;
+            state.error = None;
+        }
+        if ui.button("Connect").clicked() {
+            state.signer = None;
+            let connected = SignerEndpoint::parse(&state.endpoint)
+                .and_then(|endpoint| block_on(ExternalSigner::connect(endpoint)));
+            match connected {
+                Ok(signer) => {
+                    state.signer = Some(signer);
+                    state.error = None;
+                }
+                Err(e) => state.error = Some(format!("{:#}", e)),
+            }
+        }
+        if state.signer.is_some() && ui.button("Disconnect").clicked() {
+            state.signer = None;
+        }
+    });
+
+    if let Some(error) = &state.error {
+        ui.colored_label(egui::Color32::RED, error);
+    }
+
+    let Some(signer) = &state.signer else {
+        ui.colored_label(egui::Color32::GRAY, "Not connected");
+        return;
+    };
+    ui.label(format!("Connected to {:?}", signer.endpoint()));
+    ui.colored_label(egui::Color32::KHAKI, format!("DID: {}", signer.did()));
+    ui.colored_label(egui::Color32::KHAKI, format!("Verkey: {}", signer.verkey()));
+    ui.separator();
+
+    ui.checkbox(
+        &mut state.use_for_publish,
+        "Use for Publish Tool writes instead of the wallet DID",
+    );
+    ui.checkbox(
+        &mut state.use_for_endorser,
+        "Use as the Endorser Tool key instead of a seed",
+    );
+}
@@ -1,11 +1,13 @@
+use crate::helpers::signer::{sign_request_bytes, Signer};
+use async_trait::async_trait;
 use indy_data_types::did::{generate_did, DidValue};
 use indy_data_types::keys::PrivateKey;
-use indy_vdr::common::error::{VdrError, VdrErrorKind, VdrResult};
+use indy_vdr::common::error::VdrResult;
 use indy_vdr::pool::PreparedRequest;
 use serde_json::Value;
 
-pub fn create_did(seed: String, version: usize) -> anyhow::Result<DidInfo> {
-    let (did, prv, vk) = generate_did(Some(seed.as_bytes()), Some(version))?;
+pub fn create_did(seed: &[u8], version: usize) -> anyhow::Result<DidInfo> {
+    let (did, prv, vk) = generate_did(Some(seed), Some(version))?;
     let endorser_did = DidInfo {
         did: DidValue::from(did.to_string()),
         privatekey: prv,
@@ -15,17 +17,11 @@ pub fn create_did(seed: String, version: usize) -> anyhow::Result<DidInfo> {
     anyhow::Ok(endorser_did)
 }
 
-pub fn sign_transaction(data: DidInfo, txn: String) -> VdrResult<Value> {
+pub async fn sign_transaction(signer: &dyn Signer, txn: String) -> VdrResult<Value> {
     let mut req = PreparedRequest::from_request_json(txn)?;
     let sigin = req.get_signature_input()?;
-    let sig = data.privatekey.sign(sigin.as_bytes()).map_err(|e| {
-        VdrError::new(
-            VdrErrorKind::Input, // Using Input as it's related to input processing
-            Some(format!("Signing failed: {}", e)),
-            None,
-        )
-    })?;
-    req.set_multi_signature(&data.did, &sig)?;
+    let sig = sign_request_bytes(signer, sigin.as_bytes()).await?;
+    req.set_multi_signature(&DidValue(signer.did().to_string()), &sig)?;
     Ok(req.req_json)
 }
 
@@ -46,6 +42,23 @@ impl Default for DidInfo {
     }
 }
 
+#[async_trait]
+impl Signer for DidInfo {
+    fn did(&self) -> &str {
+        &self.did.0
+    }
+
+    fn verkey(&self) -> &str {
+        &self.verkey
+    }
+
+    async fn sign(&self, message: &[u8]) -> anyhow::Result<Vec<u8>> {
+        self.privatekey
+            .sign(message)
+            .map_err(|e| anyhow::anyhow!("Signing failed: {}", e))
+    }
+}
+
 /// Sign a NYM transaction request with a DID
 // pub fn sign_nym_request(nym_request: &mut PreparedRequest, trustee: &DidInfo) -> VdrResult<()> {
 //     let sigin = nym_request.get_signature_input()?;
@@ -63,9 +76,9 @@ mod tests {
 
     #[test]
     fn test_create_did_sov() {
-        let seed = "000000000000000000000000Trustee1".to_string();
+        let seed = "000000000000000000000000Trustee1";
         let version = 1;
-        let result = create_did(seed, version);
+        let result = create_did(seed.as_bytes(), version);
 
         assert!(result.is_ok());
 
@@ -80,9 +93,9 @@ mod tests {
 
     #[test]
     fn test_create_did_indy() {
-        let seed = "000000000000000000000000Trustee1".to_string();
+        let seed = "000000000000000000000000Trustee1";
         let version = 2;
-        let result = create_did(seed, version);
+        let result = create_did(seed.as_bytes(), version);
 
         assert!(result.is_ok());
 
@@ -98,7 +111,7 @@ mod tests {
     #[test]
     fn test_sign_transaction() {
         // Create mock DidInfo
-        let did_info = create_did("000000000000000000000000Trustee1".to_string(), 2);
+        let did_info = create_did("000000000000000000000000Trustee1".as_bytes(), 2);
         assert!(did_info.is_ok());
         let did_info = did_info.unwrap();
 
@@ -115,7 +128,10 @@ mod tests {
             "reqId": 1689080604840377700i64
         });
         // Call sign_transaction
-        let result = sign_transaction(did_info, serde_json::to_string(&txn).unwrap());
+        let result = futures_executor::block_on(sign_transaction(
+            &did_info,
+            serde_json::to_string(&txn).unwrap(),
+        ));
         // Assert function returns Ok
         assert!(result.is_ok());
 
@@ -0,0 +1,277 @@
+use crate::helper::sign_transaction;
+use crate::helpers::request_json::{is_signed, req_id_time, request_signatures};
+use crate::helpers::signer::Signer;
+use crate::helpers::txn_types::txn_type_label;
+use anyhow::{anyhow, Context, Result};
+use aries_askar::kms::{KeyAlg, LocalKey};
+use indy_vdr::pool::PreparedRequest;
+use qrcode::{Color, EcLevel, QrCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Marks files and QR codes exchanged between the online and the offline instance
+pub const HANDOFF_FORMAT: &str = "indyforge-airgap/1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HandoffStage {
+    /// Prepared online, waiting for the offline signature
+    Unsigned,
+    /// Signed offline, waiting to be verified and submitted online
+    Signed,
+}
+
+/// A request carried across the air gap, by file or QR code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Handoff {
+    pub format: String,
+    pub stage: HandoffStage,
+    /// Genesis location of the network the request was prepared for
+    pub network: Option<String>,
+    pub request: Value,
+}
+
+impl Handoff {
+    /// Wraps a request prepared without signature by the online instance
+    pub fn unsigned(request_text: &str, network: Option<&str>) -> Result<Self> {
+        let request: Value = serde_json::from_str(request_text).context("Invalid JSON")?;
+        if request["operation"]["type"].as_str().is_none() {
+            return Err(anyhow!("The request has no operation.type"));
+        }
+        if is_signed(&request) {
+            return Err(anyhow!(
+                "The request is already signed, export it before signing"
+            ));
+        }
+        Ok(Self {
+            format: HANDOFF_FORMAT.to_string(),
+            stage: HandoffStage::Unsigned,
+            network: network.map(str::to_string),
+            request,
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let handoff: Self =
+            serde_json::from_str(text.trim()).context("Not an air-gapped signing hand-off")?;
+        if handoff.format != HANDOFF_FORMAT {
+            return Err(anyhow!("Unsupported hand-off format {}", handoff.format));
+        }
+        if !handoff.request.is_object() {
+            return Err(anyhow!("The hand-off request must be a JSON object"));
+        }
+        Ok(handoff)
+    }
+
+    /// Single line JSON, as written to files and QR codes
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Suggested file name, `<stage>-<reqId>.json`
+    pub fn file_name(&self) -> String {
+        let stage = match self.stage {
+            HandoffStage::Unsigned => "unsigned",
+            HandoffStage::Signed => "signed",
+        };
+        format!("{}-{}.json", stage, self.request["reqId"])
+    }
+
+    /// Fields the signer checks before signing
+    pub fn summary(&self) -> Vec<(&'static str, String)> {
+        let request = &self.request;
+        let mut summary = vec![
+            (
+                "Type",
+                request["operation"]["type"]
+                    .as_str()
+                    .map_or("?".to_string(), txn_type_label),
+            ),
+            (
+                "Author",
+                request["identifier"].as_str().unwrap_or("?").to_string(),
+            ),
+        ];
+        if let Some(endorser) = request["endorser"].as_str() {
+            summary.push(("Endorser", endorser.to_string()));
+        }
+        if let Some(req_id) = request["reqId"].as_u64() {
+            let created = req_id_time(req_id)
+                .map(|time| chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339())
+                .unwrap_or_else(|| "unknown time".to_string());
+            summary.push(("reqId", format!("{} ({})", req_id, created)));
+        }
+        if let Some(network) = &self.network {
+            summary.push(("Network", network.clone()));
+        }
+        let signers = self.signers();
+        if !signers.is_empty() {
+            summary.push(("Signed by", signers.join(", ")));
+        }
+        summary
+    }
+
+    /// DIDs that signed the request, from `signature` and `signatures`
+    pub fn signers(&self) -> Vec<String> {
+        request_signatures(&self.request)
+            .into_iter()
+            .map(|(did, _)| did)
+            .collect()
+    }
+
+    /// Adds the signer's multi-signature, co-signing an already signed request
+    pub async fn sign(&self, signer: &dyn Signer) -> Result<Self> {
+        let signed = sign_transaction(signer, self.request.to_string())
+            .await
+            .map_err(|e| anyhow!("{}", e))?;
+        Ok(Self {
+            format: HANDOFF_FORMAT.to_string(),
+            stage: HandoffStage::Signed,
+            network: self.network.clone(),
+            request: signed,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SignatureCheck {
+    pub did: String,
+    pub result: Result<(), String>,
+}
+
+/// Checks every signature of a request against the verkeys found on the ledger,
+/// a DID without verkey fails its check
+pub fn verify_signatures(
+    request: &Value,
+    verkeys: &HashMap<String, String>,
+) -> Result<Vec<SignatureCheck>> {
+    let signature_input = PreparedRequest::from_request_json(request.to_string())
+        .and_then(|prepared| prepared.get_signature_input())
+        .map_err(|e| anyhow!("Failed to get the signature input: {}", e))?;
+
+    Ok(request_signatures(request)
+        .into_iter()
+        .map(|(did, signature)| {
+            let result = match verkeys.get(&did) {
+                Some(verkey) => verify_signature(verkey, signature_input.as_bytes(), &signature),
+                None => Err("No verkey for this DID on the ledger".to_string()),
+            };
+            SignatureCheck { did, result }
+        })
+        .collect())
+}
+
+fn verify_signature(verkey: &str, message: &[u8], signature: &str) -> Result<(), String> {
+    let signature = bs58::decode(signature)
+        .into_vec()
+        .map_err(|_| "The signature is not base58".to_string())?;
+    let verkey = bs58::decode(verkey)
+        .into_vec()
+        .map_err(|_| "The verkey is not base58".to_string())?;
+    let key = LocalKey::from_public_bytes(KeyAlg::Ed25519, &verkey).map_err(|e| e.to_string())?;
+    match key.verify_signature(message, &signature, None) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("The signature does not match the verkey".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Dark modules of a QR code, row by row
+pub struct QrMatrix {
+    pub width: usize,
+    pub modules: Vec<bool>,
+}
+
+impl QrMatrix {
+    /// Uses the lowest error correction, a hand-off is shown on a screen and
+    /// scanned at short range
+    pub fn encode(text: &str) -> Result<Self> {
+        let code =
+            QrCode::with_error_correction_level(text.as_bytes(), EcLevel::L).map_err(|e| {
+                anyhow!(
+                    "{} bytes do not fit in a QR code ({}), use the file instead",
+                    text.len(),
+                    e
+                )
+            })?;
+        Ok(Self {
+            width: code.width(),
+            modules: code
+                .to_colors()
+                .into_iter()
+                .map(|color| color == Color::Dark)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::wallet::IndyWallet;
+    use serde_json::json;
+
+    fn unsigned_request() -> String {
+        json!({
+            "identifier": "V4SGRU86Z58d6TV7PBUe6f",
+            "operation": {"type": "101", "data": {"name": "degree", "version": "1.0", "attr_names": ["name"]}},
+            "protocolVersion": 2,
+            "reqId": 1_700_000_000_000_000_000u64,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_handoff_round_trip() {
+        let handoff = Handoff::unsigned(&unsigned_request(), Some("genesis.txn")).unwrap();
+        let parsed = Handoff::parse(&handoff.to_json()).unwrap();
+        assert_eq!(parsed.stage, HandoffStage::Unsigned);
+        assert_eq!(parsed.network.as_deref(), Some("genesis.txn"));
+        assert_eq!(parsed.request, handoff.request);
+        assert_eq!(parsed.file_name(), "unsigned-1700000000000000000.json");
+        assert!(parsed
+            .summary()
+            .iter()
+            .any(|(field, value)| *field == "Type" && value == "SCHEMA (101)"));
+
+        assert!(Handoff::parse(&unsigned_request()).is_err());
+        let signed = unsigned_request().replace("\"reqId\"", "\"signature\":\"abc\",\"reqId\"");
+        assert!(Handoff::unsigned(&signed, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_signatures() {
+        let wallet = IndyWallet::new(Some("000000000000000000000000Trustee1".as_bytes()), 1)
+            .await
+            .unwrap();
+        let mut request: Value = serde_json::from_str(&unsigned_request()).unwrap();
+        let signature_input = PreparedRequest::from_request_json(request.to_string())
+            .unwrap()
+            .get_signature_input()
+            .unwrap();
+        let signature = wallet.sign(signature_input.as_bytes()).await.unwrap();
+        request["signatures"] =
+            json!({ wallet.did.clone(): bs58::encode(signature).into_string() });
+
+        let verkeys = HashMap::from([(wallet.did.clone(), wallet.verkey.clone())]);
+        let checks = verify_signatures(&request, &verkeys).unwrap();
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].result.is_ok());
+
+        // Any change after signing breaks the signature
+        request["operation"]["data"]["version"] = json!("2.0");
+        let checks = verify_signatures(&request, &verkeys).unwrap();
+        assert!(checks[0].result.is_err());
+
+        let checks = verify_signatures(&request, &HashMap::new()).unwrap();
+        assert!(checks[0].result.is_err());
+    }
+
+    #[test]
+    fn test_qr_matrix() {
+        let matrix = QrMatrix::encode(&unsigned_request()).unwrap();
+        assert_eq!(matrix.modules.len(), matrix.width * matrix.width);
+        assert!(QrMatrix::encode(&"x".repeat(4000)).is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use crate::app::MyRoles;
+use anyhow::{anyhow, Context, Result};
+use indy_vdr::ledger::requests::auth_rule::{AuthRuleData, Constraint};
+use serde_json::{json, Value};
+
+/// Role value used by auth rules to accept any role, including no role
+pub const ANY_ROLE: &str = "*";
+
+/// Human readable name of a ledger role code, `None` or `""` being no role
+pub fn role_name(code: Option<&str>) -> &'static str {
+    match code {
+        Some("0") => "Trustee",
+        Some("2") => "Steward",
+        Some("101") => "Endorser",
+        Some("201") => "Network Monitor",
+        Some(ANY_ROLE) => "Any role",
+        None | Some("") => "Author (no role)",
+        Some(_) => "Unknown role",
+    }
+}
+
+/// Ledger role code of a role, `None` for Author (no role)
+pub fn role_code(role: &MyRoles) -> Option<&'static str> {
+    match role {
+        MyRoles::Author => None,
+        MyRoles::Endorser => Some("101"),
+        MyRoles::NetworkMonitor => Some("201"),
+        MyRoles::Steward => Some("2"),
+        MyRoles::Trustee => Some("0"),
+    }
+}
+
+/// Identifies one auth rule, e.g. `EDIT` of the `role` field of a NYM (txn type `1`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthRuleKey {
+    pub auth_type: String,
+    pub auth_action: String,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: String,
+}
+
+impl AuthRuleKey {
+    /// Rule for changing the role of an existing NYM
+    pub fn nym_role_change(old_role: Option<&str>, new_role: Option<&str>) -> Self {
+        Self {
+            auth_type: "1".to_string(),
+            auth_action: "EDIT".to_string(),
+            field: "role".to_string(),
+            old_value: Some(old_role.unwrap_or_default().to_string()),
+            new_value: new_role.unwrap_or_default().to_string(),
+        }
+    }
+
+    /// Rule for adding a new value, e.g. writing a SCHEMA (`101`, `*`, `*`)
+    pub fn add(auth_type: &str, field: &str, new_value: &str) -> Self {
+        Self {
+            auth_type: auth_type.to_string(),
+            auth_action: "ADD".to_string(),
+            field: field.to_string(),
+            old_value: None,
+            new_value: new_value.to_string(),
+        }
+    }
+
+    /// Key of a rule as returned by GET_AUTH_RULE
+    pub fn from_rule(rule: &Value) -> Self {
+        let text = |field: &str| rule[field].as_str().unwrap_or_default().to_string();
+        Self {
+            auth_type: text("auth_type"),
+            auth_action: text("auth_action"),
+            field: text("field"),
+            old_value: rule["old_value"].as_str().map(str::to_string),
+            new_value: text("new_value"),
+        }
+    }
+
+    /// Rule for changing the verkey of an existing NYM
+    pub fn nym_verkey_change() -> Self {
+        Self {
+            auth_type: "1".to_string(),
+            auth_action: "EDIT".to_string(),
+            field: "verkey".to_string(),
+            old_value: Some(ANY_ROLE.to_string()),
+            new_value: ANY_ROLE.to_string(),
+        }
+    }
+}
+
+/// Result of evaluating an auth constraint for a single signer
+#[derive(Debug, Default)]
+pub struct ConstraintCheck {
+    pub satisfied: bool,
+    pub explanation: Vec<String>,
+}
+
+/// Evaluates an auth constraint for one signature from a DID with `role`.
+/// `is_owner` tells whether the signer owns the transaction target.
+pub fn check_constraint(constraint: &Value, role: Option<&str>, is_owner: bool) -> ConstraintCheck {
+    let mut check = ConstraintCheck::default();
+    check.satisfied = evaluate(constraint, role, is_owner, 0, &mut check.explanation);
+    check
+}
+
+fn evaluate(
+    constraint: &Value,
+    role: Option<&str>,
+    is_owner: bool,
+    depth: usize,
+    explanation: &mut Vec<String>,
+) -> bool {
+    let indent = "    ".repeat(depth);
+    match constraint["constraint_id"].as_str() {
+        Some("ROLE") => {
+            let required_role = constraint["role"].as_str();
+            let sig_count = constraint["sig_count"].as_u64().unwrap_or(1);
+            let need_to_be_owner = constraint["need_to_be_owner"].as_bool().unwrap_or(false);
+
+            let role_matches = match required_role {
+                Some(ANY_ROLE) => true,
+                None | Some("") => role.unwrap_or_default().is_empty(),
+                Some(required) => role == Some(required),
+            };
+            let satisfied = sig_count == 0
+                || (role_matches && sig_count <= 1 && (!need_to_be_owner || is_owner));
+
+            let mut reasons = Vec::new();
+            if sig_count > 0 && !role_matches {
+                reasons.push(format!("signer is {}", role_name(role)));
+            }
+            if sig_count > 1 {
+                reasons.push(format!("{} signatures are needed", sig_count));
+            }
+            if sig_count > 0 && need_to_be_owner && !is_owner {
+                reasons.push("signer is not the owner".to_string());
+            }
+            explanation.push(format!(
+                "{}{} {}{}",
+                indent,
+                if satisfied { "✔" } else { "✘" },
+                describe_constraint(constraint),
+                if reasons.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", reasons.join(", "))
+                }
+            ));
+            satisfied
+        }
+        Some(kind @ ("AND" | "OR")) => {
+            let position = explanation.len();
+            let results: Vec<bool> = constraint["auth_constraints"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|inner| evaluate(inner, role, is_owner, depth + 1, explanation))
+                .collect();
+            let satisfied = if kind == "AND" {
+                !results.is_empty() && results.iter().all(|r| *r)
+            } else {
+                results.iter().any(|r| *r)
+            };
+            explanation.insert(
+                position,
+                format!(
+                    "{}{} {} of:",
+                    indent,
+                    if satisfied { "✔" } else { "✘" },
+                    if kind == "AND" { "All" } else { "One" }
+                ),
+            );
+            satisfied
+        }
+        Some("FORBIDDEN") => {
+            explanation.push(format!("{}✘ Forbidden for everyone", indent));
+            false
+        }
+        _ => {
+            explanation.push(format!("{}✘ Unknown constraint: {}", indent, constraint));
+            false
+        }
+    }
+}
+
+/// One line description of a ROLE constraint, or the kind of a combined constraint
+pub fn describe_constraint(constraint: &Value) -> String {
+    match constraint["constraint_id"].as_str() {
+        Some("ROLE") => {
+            let sig_count = constraint["sig_count"].as_u64().unwrap_or(1);
+            if sig_count == 0 {
+                return "No signature required".to_string();
+            }
+            let mut description = format!(
+                "{} signature{} from {}",
+                sig_count,
+                if sig_count > 1 { "s" } else { "" },
+                role_name(constraint["role"].as_str())
+            );
+            if constraint["need_to_be_owner"].as_bool().unwrap_or(false) {
+                description.push_str(", owner only");
+            }
+            if constraint["off_ledger_signature"]
+                .as_bool()
+                .unwrap_or(false)
+            {
+                description.push_str(", off-ledger signature allowed");
+            }
+            description
+        }
+        Some("AND") => "All of".to_string(),
+        Some("OR") => "One of".to_string(),
+        Some("FORBIDDEN") => "Forbidden".to_string(),
+        _ => "Unknown constraint".to_string(),
+    }
+}
+
+/// Builds the data of an AUTH_RULE from an edited key and a constraint in JSON
+pub fn build_auth_rule(key: &AuthRuleKey, constraint: &str) -> Result<AuthRuleData> {
+    let constraint: Constraint =
+        serde_json::from_str(constraint).context("Invalid constraint JSON")?;
+    let rule = match key.auth_action.as_str() {
+        "ADD" => json!({
+            "auth_action": "ADD",
+            "auth_type": key.auth_type,
+            "field": key.field,
+            "new_value": key.new_value,
+            "constraint": constraint,
+        }),
+        "EDIT" => json!({
+            "auth_action": "EDIT",
+            "auth_type": key.auth_type,
+            "field": key.field,
+            "old_value": key.old_value,
+            "new_value": key.new_value,
+            "constraint": constraint,
+        }),
+        action => return Err(anyhow!("Unknown auth action: {}", action)),
+    };
+    if key.auth_type.is_empty() || key.field.is_empty() {
+        return Err(anyhow!("Txn type and field are required"));
+    }
+    Ok(serde_json::from_value(rule)?)
+}
+
+/// Owner of a NYM: the DID itself once it has a verkey, otherwise the DID that created it
+pub fn nym_owner(nym: &Value) -> Option<&str> {
+    if nym["verkey"].is_string() {
+        nym["dest"].as_str()
+    } else {
+        nym["identifier"].as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_check_role_constraint() {
+        let constraint = json!({
+            "constraint_id": "ROLE",
+            "role": "0",
+            "sig_count": 1,
+            "need_to_be_owner": false
+        });
+        assert!(check_constraint(&constraint, Some("0"), false).satisfied);
+        assert!(!check_constraint(&constraint, Some("2"), false).satisfied);
+        assert!(!check_constraint(&constraint, None, true).satisfied);
+
+        let owner_only = json!({
+            "constraint_id": "ROLE",
+            "role": "*",
+            "sig_count": 1,
+            "need_to_be_owner": true
+        });
+        assert!(check_constraint(&owner_only, None, true).satisfied);
+        assert!(!check_constraint(&owner_only, Some("0"), false).satisfied);
+    }
+
+    #[test]
+    fn test_check_combined_constraint() {
+        let constraint = json!({
+            "constraint_id": "OR",
+            "auth_constraints": [
+                { "constraint_id": "ROLE", "role": "0", "sig_count": 1 },
+                {
+                    "constraint_id": "AND",
+                    "auth_constraints": [
+                        { "constraint_id": "ROLE", "role": "2", "sig_count": 1 },
+                        { "constraint_id": "ROLE", "role": "2", "sig_count": 2 }
+                    ]
+                }
+            ]
+        });
+        let check = check_constraint(&constraint, Some("2"), false);
+        assert!(!check.satisfied);
+        assert_eq!(check.explanation.len(), 5);
+        assert!(check.explanation[0].starts_with("✘ One of"));
+
+        assert!(check_constraint(&constraint, Some("0"), false).satisfied);
+        assert!(
+            !check_constraint(&json!({ "constraint_id": "FORBIDDEN" }), Some("0"), true).satisfied
+        );
+    }
+
+    #[test]
+    fn test_build_auth_rule() {
+        let constraint =
+            r#"{"constraint_id": "ROLE", "role": "0", "sig_count": 2, "metadata": {}}"#;
+        let rule =
+            build_auth_rule(&AuthRuleKey::nym_role_change(Some("0"), None), constraint).unwrap();
+        let rule = serde_json::to_value(rule).unwrap();
+        assert_eq!(rule["auth_action"], "EDIT");
+        assert_eq!(rule["old_value"], "0");
+        assert_eq!(
+            AuthRuleKey::from_rule(&rule),
+            AuthRuleKey::nym_role_change(Some("0"), None)
+        );
+
+        let rule = build_auth_rule(&AuthRuleKey::add("101", "*", "*"), constraint).unwrap();
+        assert!(matches!(rule, AuthRuleData::Add(_)));
+
+        assert!(build_auth_rule(&AuthRuleKey::add("101", "*", "*"), "{}").is_err());
+        assert!(build_auth_rule(&AuthRuleKey::add("", "*", "*"), constraint).is_err());
+    }
+
+    #[test]
+    fn test_nym_owner() {
+        let nym = json!({ "dest": "A", "identifier": "B", "verkey": "key" });
+        assert_eq!(nym_owner(&nym), Some("A"));
+        let nym = json!({ "dest": "A", "identifier": "B", "verkey": null });
+        assert_eq!(nym_owner(&nym), Some("B"));
+    }
+}
@@ -0,0 +1,226 @@
+use anyhow::{anyhow, Context, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DidMethod {
+    Sov,
+    Indy(String),
+}
+
+/// A DID split into its method and the unqualified identifier used on the ledger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QualifiedDid {
+    pub method: DidMethod,
+    pub id: String,
+}
+
+impl QualifiedDid {
+    /// Accepts `did:sov:<id>`, `did:indy:<namespace>:<id>` or a bare `<id>` (treated as did:sov)
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_on_network(input, None)
+    }
+
+    /// Like [`QualifiedDid::parse`], but a bare `<id>` is qualified as did:indy when the
+    /// network namespace is known
+    pub fn parse_on_network(input: &str, namespace: Option<&str>) -> Result<Self> {
+        let input = input.trim();
+        let (method, id) = if let Some(id) = input.strip_prefix("did:sov:") {
+            (DidMethod::Sov, id)
+        } else if let Some(rest) = input.strip_prefix("did:indy:") {
+            // Namespaces may have a sub-namespace, e.g. did:indy:sovrin:staging:<id>
+            let (namespace, id) = rest
+                .rsplit_once(':')
+                .ok_or_else(|| anyhow!("did:indy DIDs must be did:indy:<namespace>:<id>"))?;
+            validate_namespace(namespace)?;
+            (DidMethod::Indy(namespace.to_string()), id)
+        } else if input.starts_with("did:") {
+            return Err(anyhow!("Only did:sov and did:indy DIDs are supported"));
+        } else {
+            (Self::network_method(namespace), input)
+        };
+
+        let decoded = bs58::decode(id)
+            .into_vec()
+            .context("DID identifier is not valid base58")?;
+        if decoded.len() != 16 {
+            return Err(anyhow!("DID identifier must be 16 bytes"));
+        }
+
+        Ok(Self {
+            method,
+            id: id.to_string(),
+        })
+    }
+
+    /// Qualifies an identifier held in the wallet for the connected network
+    pub fn on_network(id: &str, namespace: Option<&str>) -> Self {
+        Self {
+            method: Self::network_method(namespace),
+            id: id.to_string(),
+        }
+    }
+
+    fn network_method(namespace: Option<&str>) -> DidMethod {
+        match namespace {
+            Some(namespace) => DidMethod::Indy(namespace.to_string()),
+            None => DidMethod::Sov,
+        }
+    }
+
+    /// Rejects did:indy DIDs that belong to another network than the connected one
+    pub fn check_namespace(&self, namespace: Option<&str>) -> Result<()> {
+        match (&self.method, namespace) {
+            (DidMethod::Indy(did_namespace), Some(namespace)) if did_namespace != namespace => {
+                Err(anyhow!(
+                    "{} belongs to the \"{}\" network, but the connected network is \"{}\"",
+                    self,
+                    did_namespace,
+                    namespace
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for QualifiedDid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.method {
+            DidMethod::Sov => write!(f, "did:sov:{}", self.id),
+            DidMethod::Indy(namespace) => write!(f, "did:indy:{}:{}", namespace, self.id),
+        }
+    }
+}
+
+/// Validates a did:indy namespace such as `sovrin` or `sovrin:staging`
+pub fn validate_namespace(namespace: &str) -> Result<()> {
+    if namespace.is_empty() {
+        return Err(anyhow!("Namespace cannot be empty"));
+    }
+    for part in namespace.split(':') {
+        if part.is_empty()
+            || !part
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+        {
+            return Err(anyhow!(
+                "Namespace must be lowercase letters, digits, '_' or '-', with ':' between sub-namespaces"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the unqualified DID expected by ledger requests
+pub fn unqualified_did(input: &str) -> Result<String> {
+    QualifiedDid::parse(input).map(|did| did.id)
+}
+
+/// Converts a did:indy object ID (`did:indy:<ns>:<id>/anoncreds/v0/...`) into the legacy
+/// ID used by ledger requests. Legacy IDs are returned unchanged.
+pub fn legacy_object_id(id: &str) -> Result<String> {
+    let id = id.trim();
+    let Some((did, path)) = id.split_once("/anoncreds/v0/") else {
+        return Ok(id.strip_prefix("did:sov:").unwrap_or(id).to_string());
+    };
+    let did = QualifiedDid::parse(did)?.id;
+
+    let parts: Vec<&str> = path.split('/').collect();
+    match parts.as_slice() {
+        ["SCHEMA", name, version] => Ok(format!("{}:2:{}:{}", did, name, version)),
+        ["CLAIM_DEF", schema_seq_no, tag] => Ok(format!("{}:3:CL:{}:{}", did, schema_seq_no, tag)),
+        ["REV_REG_DEF", schema_seq_no, cred_def_tag, tag] => Ok(format!(
+            "{did}:4:{did}:3:CL:{}:{}:CL_ACCUM:{}",
+            schema_seq_no,
+            cred_def_tag,
+            tag,
+            did = did
+        )),
+        _ => Err(anyhow!("Unsupported did:indy object ID: {}", id)),
+    }
+}
+
+/// Converts a legacy schema, cred def or revocation registry ID into its did:indy form
+pub fn qualified_object_id(legacy_id: &str, namespace: &str) -> Option<String> {
+    let parts: Vec<&str> = legacy_id.trim().split(':').collect();
+    let path = match parts.as_slice() {
+        [_, "2", name, version] => format!("SCHEMA/{}/{}", name, version),
+        [_, "3", "CL", schema_seq_no, tag] => format!("CLAIM_DEF/{}/{}", schema_seq_no, tag),
+        [_, "4", _, "3", "CL", schema_seq_no, cred_def_tag, "CL_ACCUM", tag] => {
+            format!("REV_REG_DEF/{}/{}/{}", schema_seq_no, cred_def_tag, tag)
+        }
+        _ => return None,
+    };
+    let did = QualifiedDid::on_network(parts[0], Some(namespace));
+    Some(format!("{}/anoncreds/v0/{}", did, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qualified_did_parse() {
+        let did = QualifiedDid::parse("did:sov:V4SGRU86Z58d6TV7PBUe6f").unwrap();
+        assert_eq!(did.method, DidMethod::Sov);
+        assert_eq!(did.id, "V4SGRU86Z58d6TV7PBUe6f");
+
+        let did = QualifiedDid::parse("did:indy:sovrin:staging:GAAguaTbEHjvxL6i64YmAo").unwrap();
+        assert_eq!(did.method, DidMethod::Indy("sovrin:staging".to_string()));
+        assert_eq!(
+            did.to_string(),
+            "did:indy:sovrin:staging:GAAguaTbEHjvxL6i64YmAo"
+        );
+
+        assert_eq!(
+            QualifiedDid::parse("V4SGRU86Z58d6TV7PBUe6f")
+                .unwrap()
+                .to_string(),
+            "did:sov:V4SGRU86Z58d6TV7PBUe6f"
+        );
+        assert!(QualifiedDid::parse("did:web:example.com").is_err());
+        assert!(QualifiedDid::parse("did:sov:short").is_err());
+        assert!(QualifiedDid::parse("did:indy:Bad:GAAguaTbEHjvxL6i64YmAo").is_err());
+    }
+
+    #[test]
+    fn test_parse_on_network() {
+        let did = QualifiedDid::parse_on_network("GAAguaTbEHjvxL6i64YmAo", Some("test")).unwrap();
+        assert_eq!(did.to_string(), "did:indy:test:GAAguaTbEHjvxL6i64YmAo");
+        assert!(did.check_namespace(Some("test")).is_ok());
+        assert!(did.check_namespace(Some("other")).is_err());
+        assert!(did.check_namespace(None).is_ok());
+    }
+
+    #[test]
+    fn test_object_id_conversion() {
+        let cases = [
+            (
+                "GAAguaTbEHjvxL6i64YmAo:2:degree:1.0.0",
+                "did:indy:test:GAAguaTbEHjvxL6i64YmAo/anoncreds/v0/SCHEMA/degree/1.0.0",
+            ),
+            (
+                "GAAguaTbEHjvxL6i64YmAo:3:CL:12:default",
+                "did:indy:test:GAAguaTbEHjvxL6i64YmAo/anoncreds/v0/CLAIM_DEF/12/default",
+            ),
+            (
+                "GAAguaTbEHjvxL6i64YmAo:4:GAAguaTbEHjvxL6i64YmAo:3:CL:12:default:CL_ACCUM:tag1",
+                "did:indy:test:GAAguaTbEHjvxL6i64YmAo/anoncreds/v0/REV_REG_DEF/12/default/tag1",
+            ),
+        ];
+        for (legacy, qualified) in cases {
+            assert_eq!(qualified_object_id(legacy, "test").unwrap(), qualified);
+            assert_eq!(legacy_object_id(qualified).unwrap(), legacy);
+            assert_eq!(legacy_object_id(legacy).unwrap(), legacy);
+        }
+        assert!(qualified_object_id("not-an-id", "test").is_none());
+    }
+
+    #[test]
+    fn test_validate_namespace() {
+        assert!(validate_namespace("sovrin").is_ok());
+        assert!(validate_namespace("sovrin:staging").is_ok());
+        assert!(validate_namespace("").is_err());
+        assert!(validate_namespace("Sovrin").is_err());
+        assert!(validate_namespace("sovrin:").is_err());
+    }
+}
@@ -0,0 +1,399 @@
+use crate::helpers::did::{DidMethod, QualifiedDid};
+use crate::helpers::ledgers::IndyLedger;
+use crate::helpers::mirror::LedgerMirror;
+use crate::helpers::state_proof::ProofStatus;
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+
+const DID_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+const ED25519_2018_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2018/v1";
+
+/// NYM versions defined by did:indy for DID self-certification
+pub const NYM_VERSIONS: [(i32, &str); 3] = [
+    (0, "0 - No self-certification"),
+    (1, "1 - did:sov self-certified"),
+    (2, "2 - did:indy self-certified"),
+];
+
+/// Parses and validates the `diddocContent` of a NYM.
+/// Returns `None` when the editor is empty so the field is left out of the request.
+pub fn validate_diddoc_content(content: &str) -> Result<Option<Value>> {
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let value: Value = serde_json::from_str(content).context("DIDDoc content is not valid JSON")?;
+    let doc = value
+        .as_object()
+        .ok_or_else(|| anyhow!("DIDDoc content must be a JSON object"))?;
+
+    // The ledger assembles the id and the #verkey method from the NYM itself
+    if doc.contains_key("id") {
+        return Err(anyhow!("DIDDoc content must not contain an \"id\""));
+    }
+    if let Some(methods) = doc.get("verificationMethod") {
+        let methods = methods
+            .as_array()
+            .ok_or_else(|| anyhow!("\"verificationMethod\" must be an array"))?;
+        for method in methods {
+            let id = method
+                .get("id")
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("Each verification method needs an \"id\""))?;
+            if id.ends_with("#verkey") {
+                return Err(anyhow!(
+                    "The #verkey verification method is generated from the NYM verkey"
+                ));
+            }
+        }
+    }
+    if let Some(services) = doc.get("service") {
+        let services = services
+            .as_array()
+            .ok_or_else(|| anyhow!("\"service\" must be an array"))?;
+        for service in services {
+            for field in ["id", "type", "serviceEndpoint"] {
+                if service.get(field).is_none() {
+                    return Err(anyhow!("Each service needs a \"{}\"", field));
+                }
+            }
+        }
+    }
+
+    Ok(Some(value))
+}
+
+/// Checks that a DID is self-certified by its verkey for the given NYM version.
+/// An abbreviated verkey (`~...`) is expanded with the DID first.
+pub fn check_nym_version(did: &str, verkey: &str, version: i32) -> Result<()> {
+    let verkey = full_verkey(did, verkey)?;
+    let verkey_bytes = bs58::decode(&verkey)
+        .into_vec()
+        .context("Verkey is not valid base58")?;
+    if verkey_bytes.len() != 32 {
+        return Err(anyhow!("Verkey must be 32 bytes for self-certification"));
+    }
+
+    let expected_did = match version {
+        0 => return Ok(()),
+        1 => bs58::encode(&verkey_bytes[..16]).into_string(),
+        2 => {
+            let hash = Sha256::digest(&verkey_bytes);
+            bs58::encode(&hash[..16]).into_string()
+        }
+        _ => return Err(anyhow!("NYM version must be 0, 1 or 2")),
+    };
+
+    if did != expected_did {
+        return Err(anyhow!(
+            "DID is not self-certified for version {}: expected {}",
+            version,
+            expected_did
+        ));
+    }
+    Ok(())
+}
+
+/// Expands an abbreviated verkey (`~...`) to the full base58 key
+pub fn full_verkey(did_id: &str, verkey: &str) -> Result<String> {
+    match verkey.strip_prefix('~') {
+        Some(abbreviated) => {
+            let mut key = bs58::decode(did_id)
+                .into_vec()
+                .context("DID identifier is not valid base58")?;
+            key.extend(
+                bs58::decode(abbreviated)
+                    .into_vec()
+                    .context("Abbreviated verkey is not valid base58")?,
+            );
+            if key.len() != 32 {
+                return Err(anyhow!("Expanded verkey must be 32 bytes"));
+            }
+            Ok(bs58::encode(key).into_string())
+        }
+        None => Ok(verkey.to_string()),
+    }
+}
+
+/// Everything the resolver read from the ledger, with the assembled DID Document
+#[derive(Debug, Clone)]
+pub struct ResolvedDid {
+    pub did: QualifiedDid,
+    pub nym: Value,
+    pub diddoc_content: Option<Value>,
+    pub endpoint: Option<Value>,
+    pub document: Value,
+    /// State proof status of the ledger replies, `None` when resolved from the mirror
+    pub proof: Option<ProofStatus>,
+}
+
+/// Assembles the W3C DID Document of a NYM.
+/// For did:indy the `diddocContent` is merged into the base document; without it, or for
+/// did:sov, the legacy `endpoint` ATTRIB is turned into services.
+pub fn build_did_document(
+    did: &QualifiedDid,
+    nym: &Value,
+    endpoint: Option<&Value>,
+) -> Result<Value> {
+    let verkey = nym["verkey"]
+        .as_str()
+        .ok_or_else(|| anyhow!("The NYM has no verkey, the DID is deactivated"))?;
+    let verkey = full_verkey(&did.id, verkey)?;
+    let did_string = did.to_string();
+
+    let key_id = match did.method {
+        DidMethod::Sov => format!("{}#key-1", did_string),
+        DidMethod::Indy(_) => format!("{}#verkey", did_string),
+    };
+    let mut document = json!({
+        "@context": [DID_CONTEXT, ED25519_2018_CONTEXT],
+        "id": did_string,
+        "verificationMethod": [{
+            "id": key_id,
+            "type": "Ed25519VerificationKey2018",
+            "controller": did_string,
+            "publicKeyBase58": verkey,
+        }],
+        "authentication": [key_id],
+    });
+    if did.method == DidMethod::Sov {
+        document["assertionMethod"] = json!([key_id]);
+    }
+
+    let diddoc_content = match (&did.method, nym_diddoc_content(nym)?) {
+        (DidMethod::Indy(_), Some(content)) => Some(content),
+        _ => None,
+    };
+    match diddoc_content {
+        Some(content) => merge_diddoc_content(&mut document, &content)?,
+        None => {
+            if let Some(services) = endpoint_services(&did_string, endpoint) {
+                document["service"] = services;
+            }
+        }
+    }
+
+    Ok(document)
+}
+
+/// Reads `diddocContent` from GET_NYM data, where it may be stored as a JSON string
+pub fn nym_diddoc_content(nym: &Value) -> Result<Option<Value>> {
+    match &nym["diddocContent"] {
+        Value::Null => Ok(None),
+        Value::String(content) => validate_diddoc_content(content),
+        content => Ok(Some(content.clone())),
+    }
+}
+
+fn merge_diddoc_content(document: &mut Value, content: &Value) -> Result<()> {
+    let content = content
+        .as_object()
+        .ok_or_else(|| anyhow!("DIDDoc content must be a JSON object"))?;
+    let document = document
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("DID Document must be a JSON object"))?;
+
+    for (key, value) in content {
+        match (document.get_mut(key), value) {
+            // Arrays such as @context and verificationMethod are appended to
+            (Some(Value::Array(existing)), Value::Array(extra)) => {
+                for item in extra {
+                    if !existing.contains(item) {
+                        existing.push(item.clone());
+                    }
+                }
+            }
+            (Some(Value::Array(existing)), extra) => {
+                if !existing.contains(extra) {
+                    existing.push(extra.clone());
+                }
+            }
+            _ => {
+                document.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn endpoint_services(did: &str, endpoint: Option<&Value>) -> Option<Value> {
+    let endpoint = endpoint?.get("endpoint")?;
+    let service_endpoint = endpoint.get("endpoint")?.as_str()?;
+
+    let mut service = Map::new();
+    service.insert("id".to_string(), json!(format!("{}#endpoint", did)));
+    service.insert("type".to_string(), json!("endpoint"));
+    service.insert("serviceEndpoint".to_string(), json!(service_endpoint));
+    let mut services = vec![Value::Object(service)];
+
+    let types = endpoint["types"].as_array();
+    let is_didcomm = types.map_or(true, |types| {
+        types
+            .iter()
+            .any(|t| t == "did-communication" || t == "DIDComm")
+    });
+    if is_didcomm {
+        services.push(json!({
+            "id": format!("{}#did-communication", did),
+            "type": "did-communication",
+            "priority": 0,
+            "recipientKeys": [format!("{}#key-1", did)],
+            "routingKeys": endpoint.get("routingKeys").cloned().unwrap_or(json!([])),
+            "accept": ["didcomm/aip2;env=rfc19"],
+            "serviceEndpoint": service_endpoint,
+        }));
+    }
+
+    Some(Value::Array(services))
+}
+
+/// Resolves a did:sov or did:indy DID against the connected ledger
+pub async fn resolve_did(ledger: &IndyLedger, did: &QualifiedDid) -> Result<ResolvedDid> {
+    let nym_reply = ledger
+        .get_nym(&did.id)
+        .await
+        .context("GET_NYM request failed")?;
+    let mut proofs = vec![nym_reply.proof];
+    let nym = nym_reply
+        .data
+        .ok_or_else(|| anyhow!("{} was not found on the ledger", did))?;
+
+    // did:indy only falls back to the endpoint ATTRIB when there is no diddocContent
+    let endpoint = if diddoc_content_of(did, &nym)?.is_none() {
+        let attrib_reply = ledger
+            .get_attrib(&did.id, "endpoint")
+            .await
+            .context("GET_ATTRIB request failed")?;
+        proofs.push(attrib_reply.proof);
+        attrib_reply.data
+    } else {
+        None
+    };
+
+    let mut resolved = resolved_did(did, nym, endpoint)?;
+    resolved.proof = ProofStatus::combine(proofs);
+    Ok(resolved)
+}
+
+/// Resolves a DID from the transactions of the local ledger mirror
+pub fn resolve_did_offline(mirror: &LedgerMirror, did: &QualifiedDid) -> Result<ResolvedDid> {
+    let nym = mirror
+        .nym(&did.id)
+        .ok_or_else(|| anyhow!("{} was not found in the local mirror", did))?;
+    let endpoint = if diddoc_content_of(did, &nym)?.is_none() {
+        mirror.attrib(&did.id, "endpoint")
+    } else {
+        None
+    };
+
+    resolved_did(did, nym, endpoint)
+}
+
+fn diddoc_content_of(did: &QualifiedDid, nym: &Value) -> Result<Option<Value>> {
+    match did.method {
+        DidMethod::Indy(_) => nym_diddoc_content(nym),
+        DidMethod::Sov => Ok(None),
+    }
+}
+
+fn resolved_did(did: &QualifiedDid, nym: Value, endpoint: Option<Value>) -> Result<ResolvedDid> {
+    let diddoc_content = diddoc_content_of(did, &nym)?;
+    let document = build_did_document(did, &nym, endpoint.as_ref())?;
+    Ok(ResolvedDid {
+        did: did.clone(),
+        nym,
+        diddoc_content,
+        endpoint,
+        document,
+        proof: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERKEY: &str = "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL";
+
+    #[test]
+    fn test_check_nym_version() {
+        assert!(check_nym_version("V4SGRU86Z58d6TV7PBUe6f", VERKEY, 1).is_ok());
+        assert!(check_nym_version("GAAguaTbEHjvxL6i64YmAo", VERKEY, 2).is_ok());
+        assert!(check_nym_version("V4SGRU86Z58d6TV7PBUe6f", VERKEY, 2).is_err());
+        assert!(check_nym_version("anything", VERKEY, 0).is_ok());
+        assert!(check_nym_version("V4SGRU86Z58d6TV7PBUe6f", VERKEY, 3).is_err());
+
+        let key = bs58::decode(VERKEY).into_vec().unwrap();
+        let abbreviated = format!("~{}", bs58::encode(&key[16..]).into_string());
+        assert!(check_nym_version("V4SGRU86Z58d6TV7PBUe6f", &abbreviated, 1).is_ok());
+        assert!(check_nym_version("GAAguaTbEHjvxL6i64YmAo", &abbreviated, 2).is_err());
+    }
+
+    #[test]
+    fn test_validate_diddoc_content() {
+        assert!(validate_diddoc_content("  ").unwrap().is_none());
+        assert!(validate_diddoc_content("[]").is_err());
+        assert!(validate_diddoc_content(r#"{"id": "did:indy:test:abc"}"#).is_err());
+        assert!(validate_diddoc_content(r##"{"service": [{"id": "#agent"}]}"##).is_err());
+
+        let content = r#"{
+            "@context": ["https://identity.foundation/didcomm-messaging/service-endpoint/v1"],
+            "service": [{
+                "id": "did:indy:test:GAAguaTbEHjvxL6i64YmAo#didcomm",
+                "type": "DIDCommMessaging",
+                "serviceEndpoint": "https://agent.example.com"
+            }]
+        }"#;
+        assert!(validate_diddoc_content(content).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_full_verkey() {
+        // Abbreviated verkey is the DID bytes followed by the last 16 bytes of the key
+        let key = bs58::decode(VERKEY).into_vec().unwrap();
+        let did = bs58::encode(&key[..16]).into_string();
+        let abbreviated = format!("~{}", bs58::encode(&key[16..]).into_string());
+        assert_eq!(full_verkey(&did, &abbreviated).unwrap(), VERKEY);
+        assert_eq!(full_verkey(&did, VERKEY).unwrap(), VERKEY);
+    }
+
+    #[test]
+    fn test_build_did_document_indy_merge() {
+        let did = QualifiedDid::parse("did:indy:test:GAAguaTbEHjvxL6i64YmAo").unwrap();
+        let nym = json!({
+            "verkey": VERKEY,
+            "diddocContent": "{\"@context\": [\"https://didcomm.org/messaging/contexts/v2\"], \"service\": [{\"id\": \"#didcomm\", \"type\": \"DIDCommMessaging\", \"serviceEndpoint\": \"https://agent.example.com\"}]}"
+        });
+        let document = build_did_document(&did, &nym, None).unwrap();
+
+        assert_eq!(document["id"], "did:indy:test:GAAguaTbEHjvxL6i64YmAo");
+        assert_eq!(
+            document["verificationMethod"][0]["id"],
+            "did:indy:test:GAAguaTbEHjvxL6i64YmAo#verkey"
+        );
+        assert_eq!(document["@context"].as_array().unwrap().len(), 3);
+        assert_eq!(document["service"][0]["type"], "DIDCommMessaging");
+    }
+
+    #[test]
+    fn test_build_did_document_sov_endpoint() {
+        let did = QualifiedDid::parse("did:sov:V4SGRU86Z58d6TV7PBUe6f").unwrap();
+        let nym = json!({ "verkey": VERKEY });
+        let endpoint = json!({ "endpoint": { "endpoint": "https://agent.example.com" } });
+        let document = build_did_document(&did, &nym, Some(&endpoint)).unwrap();
+
+        assert_eq!(
+            document["authentication"][0],
+            "did:sov:V4SGRU86Z58d6TV7PBUe6f#key-1"
+        );
+        assert_eq!(
+            document["service"][0]["serviceEndpoint"],
+            "https://agent.example.com"
+        );
+        assert_eq!(document["service"][1]["type"], "did-communication");
+
+        let deactivated = json!({ "verkey": null });
+        assert!(build_did_document(&did, &deactivated, None).is_err());
+    }
+}
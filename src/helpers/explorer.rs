@@ -0,0 +1,160 @@
+use crate::helpers::auth_rules::role_name;
+use crate::helpers::txn_types::txn_type_label;
+use chrono::DateTime;
+use serde_json::Value;
+
+/// Ledgers that can be browsed, with the ledger ID used by GET_TXN
+pub const LEDGER_TYPES: [(&str, i32); 3] = [("DOMAIN", 1), ("POOL", 0), ("CONFIG", 2)];
+
+/// One row of the explorer, decoded from a ledger transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxnSummary {
+    pub seq_no: u64,
+    pub txn_type: String,
+    pub submitter: String,
+    pub time: String,
+    pub summary: String,
+}
+
+impl TxnSummary {
+    /// Case insensitive filter on the type (code or name) and on the submitter DID
+    pub fn matches(&self, type_filter: &str, submitter_filter: &str) -> bool {
+        txn_type_label(&self.txn_type)
+            .to_lowercase()
+            .contains(&type_filter.trim().to_lowercase())
+            && self
+                .submitter
+                .to_lowercase()
+                .contains(&submitter_filter.trim().to_lowercase())
+    }
+}
+
+/// Decodes a transaction as returned by GET_TXN (`txn`, `txnMetadata`, `reqSignature`)
+pub fn summarize_txn(txn: &Value) -> TxnSummary {
+    let body = &txn["txn"];
+    let data = &body["data"];
+    let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+
+    let txn_type = text(&body["type"]);
+    let summary = match txn_type.as_str() {
+        "0" => format!(
+            "{} {}",
+            text(&data["data"]["alias"]),
+            data["data"]["services"].as_array().map_or(
+                "(services unchanged)".to_string(),
+                |services| format!(
+                    "{:?}",
+                    services
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                )
+            )
+        ),
+        "1" => {
+            let mut summary = format!(
+                "{} as {}",
+                text(&data["dest"]),
+                role_name(data["role"].as_str())
+            );
+            if let Some(alias) = data["alias"].as_str() {
+                summary.push_str(&format!(" ({})", alias));
+            }
+            if data.get("verkey").is_some_and(Value::is_null) {
+                summary.push_str(", verkey removed");
+            }
+            summary
+        }
+        "100" => {
+            let attribute = data["raw"]
+                .as_str()
+                .and_then(|raw| serde_json::from_str::<Value>(raw).ok())
+                .and_then(|raw| raw.as_object().map(|raw| raw.keys().cloned().collect()))
+                .unwrap_or_else(|| vec!["hash/enc".to_string()]);
+            format!("{}: {}", text(&data["dest"]), attribute.join(", "))
+        }
+        "101" => format!(
+            "{} {} ({} attributes)",
+            text(&data["data"]["name"]),
+            text(&data["data"]["version"]),
+            data["data"]["attr_names"]
+                .as_array()
+                .map_or(0, |attributes| attributes.len())
+        ),
+        "102" => format!("schema #{} tag {}", data["ref"], text(&data["tag"])),
+        "113" => text(&data["id"]),
+        "114" => text(&data["revocRegDefId"]),
+        "109" => format!(
+            "{} {} {}",
+            text(&data["action"]),
+            text(&data["name"]),
+            text(&data["version"])
+        ),
+        "111" => format!("writes: {}, force: {}", data["writes"], data["force"]),
+        "120" => format!(
+            "{} {} {}",
+            text(&data["auth_action"]),
+            txn_type_label(&text(&data["auth_type"])),
+            text(&data["field"])
+        ),
+        "122" => format!(
+            "{} rules",
+            data["rules"].as_array().map_or(0, |rules| rules.len())
+        ),
+        "4" => format!("TAA version {}", text(&data["version"])),
+        "5" => format!("AML version {}", text(&data["version"])),
+        _ => String::new(),
+    };
+
+    TxnSummary {
+        seq_no: txn["txnMetadata"]["seqNo"].as_u64().unwrap_or_default(),
+        txn_type,
+        submitter: text(&body["metadata"]["from"]),
+        time: txn["txnMetadata"]["txnTime"]
+            .as_i64()
+            .and_then(|time| DateTime::from_timestamp(time, 0))
+            .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_else(|| "genesis".to_string()),
+        summary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_summarize_txn() {
+        let schema = json!({
+            "txn": {
+                "type": "101",
+                "data": { "data": { "name": "degree", "version": "1.0", "attr_names": ["name", "grade"] } },
+                "metadata": { "from": "V4SGRU86Z58d6TV7PBUe6f" }
+            },
+            "txnMetadata": { "seqNo": 12, "txnTime": 1700000000 }
+        });
+        let summary = summarize_txn(&schema);
+        assert_eq!(summary.seq_no, 12);
+        assert_eq!(summary.summary, "degree 1.0 (2 attributes)");
+        assert_eq!(summary.time, "2023-11-14 22:13:20 UTC");
+        assert!(summary.matches("schema", "v4sg"));
+        assert!(summary.matches("101", ""));
+        assert!(!summary.matches("NYM", ""));
+
+        let nym = json!({
+            "txn": {
+                "type": "1",
+                "data": { "dest": "GAAguaTbEHjvxL6i64YmAo", "role": "101", "verkey": null },
+                "metadata": { "from": "V4SGRU86Z58d6TV7PBUe6f" }
+            },
+            "txnMetadata": { "seqNo": 3 }
+        });
+        let summary = summarize_txn(&nym);
+        assert_eq!(
+            summary.summary,
+            "GAAguaTbEHjvxL6i64YmAo as Endorser, verkey removed"
+        );
+        assert_eq!(summary.time, "genesis");
+    }
+}
@@ -0,0 +1,260 @@
+use crate::helpers::diddoc::full_verkey;
+use crate::helpers::signer::Signer;
+use anyhow::{anyhow, Context, Result};
+use aries_askar::kms::{KeyAlg, LocalKey};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Signing services may wait for an operator to approve the request
+const SIGN_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Where the signing service listens. Only local transports are accepted, the
+/// protocol is described in `docs/external-signer.md`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerEndpoint {
+    /// `http://127.0.0.1:<port>/<path>`, one JSON request per POST
+    Http(String),
+    /// `unix:<path>`, one JSON line in and one JSON line out per connection
+    UnixSocket(String),
+}
+
+impl SignerEndpoint {
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        if let Some(path) = input.strip_prefix("unix:") {
+            if path.is_empty() {
+                return Err(anyhow!("The Unix socket path is empty"));
+            }
+            return Ok(SignerEndpoint::UnixSocket(path.to_string()));
+        }
+
+        let url = reqwest::Url::parse(input)
+            .context("Expected http://localhost:<port> or unix:<path>")?;
+        if url.scheme() != "http" {
+            return Err(anyhow!("Only http:// on localhost is supported"));
+        }
+        match url.host_str() {
+            Some("localhost") | Some("127.0.0.1") | Some("[::1]") => {
+                Ok(SignerEndpoint::Http(input.to_string()))
+            }
+            _ => Err(anyhow!(
+                "The signing service must listen on localhost, keys are not sent over the network"
+            )),
+        }
+    }
+
+    async fn call(&self, request: &Value) -> Result<Value> {
+        let reply = match self {
+            SignerEndpoint::Http(url) => {
+                let client = reqwest::Client::builder()
+                    .timeout(SIGN_TIMEOUT)
+                    .no_proxy()
+                    .build()
+                    .context("Failed to create HTTP client")?;
+                let response = client
+                    .post(url)
+                    .json(request)
+                    .send()
+                    .await
+                    .with_context(|| format!("No signing service at {}", url))?;
+                response
+                    .json::<Value>()
+                    .await
+                    .context("The signing service did not reply with JSON")?
+            }
+            SignerEndpoint::UnixSocket(path) => {
+                tokio::time::timeout(SIGN_TIMEOUT, call_unix_socket(path, request))
+                    .await
+                    .context("The signing service did not reply in time")??
+            }
+        };
+
+        match reply.get("error") {
+            Some(error) => Err(anyhow!(
+                "The signing service refused: {}",
+                error.as_str().unwrap_or(&error.to_string())
+            )),
+            None => Ok(reply),
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn call_unix_socket(path: &str, request: &Value) -> Result<Value> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let mut stream = tokio::net::UnixStream::connect(path)
+        .await
+        .with_context(|| format!("No signing service at {}", path))?;
+    stream
+        .write_all(format!("{}\n", request).as_bytes())
+        .await
+        .context("Failed to send the request to the signing service")?;
+
+    let mut line = String::new();
+    BufReader::new(stream)
+        .read_line(&mut line)
+        .await
+        .context("Failed to read the signing service reply")?;
+    serde_json::from_str(&line).context("The signing service did not reply with JSON")
+}
+
+#[cfg(not(unix))]
+async fn call_unix_socket(_path: &str, _request: &Value) -> Result<Value> {
+    Err(anyhow!("Unix sockets are not available on this platform"))
+}
+
+/// Key held by a separate signing process. The DID and verkey are read from the
+/// service once, every signature it returns is checked against that verkey.
+pub struct ExternalSigner {
+    endpoint: SignerEndpoint,
+    did: String,
+    verkey: String,
+}
+
+impl ExternalSigner {
+    pub async fn connect(endpoint: SignerEndpoint) -> Result<Self> {
+        let reply = endpoint.call(&json!({ "method": "key" })).await?;
+        let (Some(did), Some(verkey)) = (reply["did"].as_str(), reply["verkey"].as_str()) else {
+            return Err(anyhow!(
+                "The signing service did not return a did and a verkey"
+            ));
+        };
+        let verkey = full_verkey(did, verkey).context("Invalid verkey from the signing service")?;
+
+        Ok(Self {
+            endpoint,
+            did: did.to_string(),
+            verkey,
+        })
+    }
+
+    pub fn endpoint(&self) -> &SignerEndpoint {
+        &self.endpoint
+    }
+}
+
+#[async_trait]
+impl Signer for ExternalSigner {
+    fn did(&self) -> &str {
+        &self.did
+    }
+
+    fn verkey(&self) -> &str {
+        &self.verkey
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        let reply = self
+            .endpoint
+            .call(&json!({
+                "method": "sign",
+                "did": self.did,
+                "message": bs58::encode(message).into_string(),
+            }))
+            .await?;
+        let signature = reply["signature"]
+            .as_str()
+            .ok_or_else(|| anyhow!("The signing service did not return a signature"))?;
+        let signature = bs58::decode(signature)
+            .into_vec()
+            .context("The signature is not base58")?;
+
+        let verkey = bs58::decode(&self.verkey).into_vec()?;
+        let key = LocalKey::from_public_bytes(KeyAlg::Ed25519, &verkey)
+            .map_err(|e| anyhow!("Invalid verkey: {}", e))?;
+        if !key
+            .verify_signature(message, &signature, None)
+            .map_err(|e| anyhow!("Invalid signature: {}", e))?
+        {
+            return Err(anyhow!(
+                "The signature returned by the signing service does not match {}",
+                self.did
+            ));
+        }
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::wallet::IndyWallet;
+
+    #[test]
+    fn test_signer_endpoint_parse() {
+        assert_eq!(
+            SignerEndpoint::parse("http://127.0.0.1:8710/sign").unwrap(),
+            SignerEndpoint::Http("http://127.0.0.1:8710/sign".to_string())
+        );
+        assert_eq!(
+            SignerEndpoint::parse("unix:/run/indy-signer.sock").unwrap(),
+            SignerEndpoint::UnixSocket("/run/indy-signer.sock".to_string())
+        );
+        assert!(SignerEndpoint::parse("http://signer.example.com/sign").is_err());
+        assert!(SignerEndpoint::parse("https://localhost/sign").is_err());
+        assert!(SignerEndpoint::parse("unix:").is_err());
+    }
+
+    /// Stand-in signing service answering a single connection with the given wallet
+    #[cfg(unix)]
+    async fn serve_once(listener: &tokio::net::UnixListener, wallet: &IndyWallet) {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).await.unwrap();
+        let request: Value = serde_json::from_str(&line).unwrap();
+        let reply = match request["method"].as_str() {
+            Some("key") => json!({ "did": wallet.did, "verkey": wallet.verkey }),
+            Some("sign") => {
+                let message = bs58::decode(request["message"].as_str().unwrap())
+                    .into_vec()
+                    .unwrap();
+                let signature = wallet.sign(&message).await.unwrap();
+                json!({ "signature": bs58::encode(signature).into_string() })
+            }
+            _ => json!({ "error": "unknown method" }),
+        };
+        reader
+            .get_mut()
+            .write_all(format!("{}\n", reply).as_bytes())
+            .await
+            .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_external_signer_over_unix_socket() {
+        let path =
+            std::env::temp_dir().join(format!("indyforge-signer-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let wallet = IndyWallet::new(Some("000000000000000000000000Trustee1".as_bytes()), 1)
+            .await
+            .unwrap();
+
+        let endpoint = SignerEndpoint::parse(&format!("unix:{}", path.display())).unwrap();
+        let (signer, _) = tokio::join!(
+            ExternalSigner::connect(endpoint),
+            serve_once(&listener, &wallet)
+        );
+        let signer = signer.unwrap();
+        assert_eq!(signer.did(), "V4SGRU86Z58d6TV7PBUe6f");
+        assert_eq!(signer.verkey(), wallet.verkey);
+
+        let (signature, _) = tokio::join!(signer.sign(b"request"), serve_once(&listener, &wallet));
+        assert_eq!(signature.unwrap(), wallet.sign(b"request").await.unwrap());
+
+        // A service signing with another key is caught
+        let other = IndyWallet::new(None, 1).await.unwrap();
+        let (signature, _) = tokio::join!(signer.sign(b"request"), serve_once(&listener, &other));
+        assert!(signature
+            .unwrap_err()
+            .to_string()
+            .contains("does not match"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
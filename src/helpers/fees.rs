@@ -0,0 +1,298 @@
+use crate::helpers::auth_rules::{check_constraint, role_name};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// GET_FEES of the payment plugin (sovtokenfees)
+pub const GET_FEES: &str = "20001";
+
+/// Fee amounts set on the ledger, keyed by auth rule fee alias (or by transaction
+/// type on networks still using the legacy schedule)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub fees: BTreeMap<String, u64>,
+}
+
+impl FeeSchedule {
+    pub fn from_reply(reply: &str) -> Result<Self> {
+        let reply: Value = serde_json::from_str(reply).context("Invalid GET_FEES reply")?;
+        let fees = reply["result"]["fees"]
+            .as_object()
+            .ok_or_else(|| anyhow!("The GET_FEES reply has no fees"))?;
+        Ok(Self {
+            fees: fees
+                .iter()
+                .filter_map(|(alias, amount)| Some((alias.clone(), amount.as_u64()?)))
+                .collect(),
+        })
+    }
+
+    pub fn amount(&self, alias: &str) -> Option<u64> {
+        self.fees.get(alias).copied()
+    }
+}
+
+/// Fee alias of an auth rule branch, with its amount when it is in the schedule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeRequirement {
+    pub alias: String,
+    pub amount: Option<u64>,
+    /// Branch of the auth constraint carrying the alias, `None` for the legacy schedule
+    pub branch: Option<Value>,
+}
+
+/// Fee aliases found in the `metadata` of an auth constraint tree, with the branch carrying each
+pub fn fee_aliases(constraint: &Value) -> Vec<(String, Value)> {
+    let mut aliases = Vec::new();
+    collect_aliases(constraint, &mut aliases);
+    aliases
+}
+
+fn collect_aliases(constraint: &Value, aliases: &mut Vec<(String, Value)>) {
+    if let Some(alias) = constraint["metadata"]["fees"].as_str() {
+        aliases.push((alias.to_string(), constraint.clone()));
+    }
+    if let Some(children) = constraint["auth_constraints"].as_array() {
+        for child in children {
+            collect_aliases(child, aliases);
+        }
+    }
+}
+
+/// Fees that apply to a transaction type under the given auth constraint. The
+/// legacy schedule keyed by transaction type is used when the rule has no alias.
+pub fn fee_requirements(
+    txn_type: &str,
+    constraint: Option<&Value>,
+    schedule: &FeeSchedule,
+) -> Vec<FeeRequirement> {
+    let aliases = constraint.map(fee_aliases).unwrap_or_default();
+    if aliases.is_empty() && schedule.fees.contains_key(txn_type) {
+        return vec![FeeRequirement {
+            alias: txn_type.to_string(),
+            amount: schedule.amount(txn_type),
+            branch: None,
+        }];
+    }
+    aliases
+        .into_iter()
+        .map(|(alias, branch)| FeeRequirement {
+            amount: schedule.amount(&alias),
+            alias,
+            branch: Some(branch),
+        })
+        .collect()
+}
+
+/// Requirements whose branch a single signature from a DID with `role` satisfies
+pub fn applicable_fees<'a>(
+    requirements: &'a [FeeRequirement],
+    role: Option<&str>,
+) -> Vec<&'a FeeRequirement> {
+    requirements
+        .iter()
+        .filter(|requirement| {
+            requirement.branch.as_ref().map_or(true, |branch| {
+                check_constraint(branch, role, true).satisfied
+            })
+        })
+        .collect()
+}
+
+/// Exact amount to pay for a signer with `role`, fees must match and an overpayment is
+/// rejected. When the signer satisfies branches with different fees, the `chosen` alias
+/// picks one. An alias missing from the schedule costs nothing.
+pub fn required_fee(
+    requirements: &[FeeRequirement],
+    role: Option<&str>,
+    chosen: Option<&str>,
+) -> Result<u64> {
+    if requirements.is_empty() {
+        return Ok(0);
+    }
+    let applicable = applicable_fees(requirements, role);
+    let amounts: BTreeSet<u64> = applicable
+        .iter()
+        .map(|requirement| requirement.amount.unwrap_or_default())
+        .collect();
+    match amounts.len() {
+        0 => Err(anyhow!(
+            "A {} signer satisfies none of the fee branches of the auth rule",
+            role_name(role)
+        )),
+        1 => Ok(amounts.into_iter().next().unwrap_or_default()),
+        _ => applicable
+            .iter()
+            .find(|requirement| Some(requirement.alias.as_str()) == chosen)
+            .map(|requirement| requirement.amount.unwrap_or_default())
+            .ok_or_else(|| {
+                anyhow!("The signer satisfies branches with different fees, choose the one to pay")
+            }),
+    }
+}
+
+/// Source of the payment inputs added to requests on networks charging fees.
+/// Payment plugins and wallets implement it, `MockPaymentHandler` stands in for them.
+#[async_trait]
+pub trait PaymentHandler: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// `fees` field paying `amount` for the request: `[inputs, outputs, signatures]`
+    async fn build_fees(&self, request: &Value, amount: u64) -> Result<Value>;
+}
+
+/// Adds the fee inputs to a request. DID signatures leave `fees` out, the payment
+/// handler signs its inputs itself, so a signed request stays valid.
+pub async fn attach_fees(
+    request: &mut Value,
+    handler: &dyn PaymentHandler,
+    amount: u64,
+) -> Result<()> {
+    if request.get("fees").is_some_and(|fees| !fees.is_null()) {
+        return Err(anyhow!("The request already carries fees"));
+    }
+    let fees = handler
+        .build_fees(request, amount)
+        .await
+        .with_context(|| format!("{} could not pay {}", handler.name(), amount))?;
+    request["fees"] = fees;
+    Ok(())
+}
+
+/// Pays from a single fake UTXO, for trying fees on test networks without a
+/// payment wallet. Its inputs are rejected by a real payment plugin.
+#[derive(Debug, Clone)]
+pub struct MockPaymentHandler {
+    pub address: String,
+    pub balance: u64,
+}
+
+impl Default for MockPaymentHandler {
+    fn default() -> Self {
+        Self {
+            address: "pay:mock:2jS4PHWQJKcawRxdW6GVsjnZBa1ecGdCssn7KhWYJZGTXgL7Es".to_string(),
+            balance: 1_000,
+        }
+    }
+}
+
+#[async_trait]
+impl PaymentHandler for MockPaymentHandler {
+    fn name(&self) -> &str {
+        "Mock payment handler"
+    }
+
+    async fn build_fees(&self, _request: &Value, amount: u64) -> Result<Value> {
+        let change = self.balance.checked_sub(amount).ok_or_else(|| {
+            anyhow!(
+                "Insufficient balance: {} available, {} required",
+                self.balance,
+                amount
+            )
+        })?;
+        let outputs = if change > 0 {
+            json!([{"address": self.address, "amount": change}])
+        } else {
+            json!([])
+        };
+        Ok(json!([
+            [{"address": self.address, "seqNo": 1}],
+            outputs,
+            ["mock-signature"]
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_requirements() {
+        let schedule = FeeSchedule::from_reply(
+            r#"{"op":"REPLY","result":{"type":"20001","fees":{"add_schema":10,"add_nym":5,"114":1}}}"#,
+        )
+        .unwrap();
+        assert_eq!(schedule.amount("add_schema"), Some(10));
+
+        let constraint = json!({
+            "constraint_id": "OR",
+            "auth_constraints": [
+                {"constraint_id": "ROLE", "role": "0", "sig_count": 1, "metadata": {"fees": "add_schema"}},
+                {"constraint_id": "ROLE", "role": "101", "sig_count": 1, "metadata": {"fees": "add_nym"}},
+                {"constraint_id": "ROLE", "role": "2", "sig_count": 1, "metadata": {"fees": "unknown"}}
+            ]
+        });
+        let requirements = fee_requirements("101", Some(&constraint), &schedule);
+        assert_eq!(requirements.len(), 3);
+        assert_eq!(requirements[2].amount, None);
+        // The fee of the branch the signer role satisfies, not the highest one
+        assert_eq!(required_fee(&requirements, Some("0"), None).unwrap(), 10);
+        assert_eq!(required_fee(&requirements, Some("101"), None).unwrap(), 5);
+        assert_eq!(required_fee(&requirements, Some("2"), None).unwrap(), 0);
+        assert!(required_fee(&requirements, None, None).is_err());
+
+        // Legacy schedule keyed by transaction type
+        let no_alias =
+            json!({"constraint_id": "ROLE", "role": "0", "sig_count": 1, "metadata": {}});
+        assert_eq!(
+            required_fee(
+                &fee_requirements("114", Some(&no_alias), &schedule),
+                None,
+                None
+            )
+            .unwrap(),
+            1
+        );
+        assert!(fee_requirements("1", None, &schedule).is_empty());
+
+        assert!(FeeSchedule::from_reply(r#"{"op":"REQNACK","reason":"Invalid type"}"#).is_err());
+    }
+
+    #[test]
+    fn test_ambiguous_fee_branches() {
+        let schedule = FeeSchedule::from_reply(
+            r#"{"op":"REPLY","result":{"type":"20001","fees":{"trustee_schema":10,"any_schema":3}}}"#,
+        )
+        .unwrap();
+        let constraint = json!({
+            "constraint_id": "OR",
+            "auth_constraints": [
+                {"constraint_id": "ROLE", "role": "0", "sig_count": 1, "metadata": {"fees": "trustee_schema"}},
+                {"constraint_id": "ROLE", "role": "*", "sig_count": 1, "metadata": {"fees": "any_schema"}}
+            ]
+        });
+        let requirements = fee_requirements("101", Some(&constraint), &schedule);
+
+        assert_eq!(required_fee(&requirements, Some("101"), None).unwrap(), 3);
+        // A trustee meets both branches, the fee to pay must be chosen
+        assert_eq!(applicable_fees(&requirements, Some("0")).len(), 2);
+        assert!(required_fee(&requirements, Some("0"), None).is_err());
+        assert_eq!(
+            required_fee(&requirements, Some("0"), Some("trustee_schema")).unwrap(),
+            10
+        );
+        assert_eq!(
+            required_fee(&requirements, Some("0"), Some("any_schema")).unwrap(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_attach_mock_fees() {
+        let handler = MockPaymentHandler::default();
+        let mut request = json!({"identifier": "V4SGRU86Z58d6TV7PBUe6f", "operation": {"type": "101"}, "reqId": 1});
+        attach_fees(&mut request, &handler, 10).await.unwrap();
+        assert_eq!(request["fees"][0][0]["address"], json!(handler.address));
+        assert_eq!(request["fees"][1][0]["amount"], json!(990));
+
+        assert!(attach_fees(&mut request, &handler, 10).await.is_err());
+
+        request["fees"] = Value::Null;
+        let error = attach_fees(&mut request, &handler, 2_000)
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("Insufficient balance"));
+    }
+}
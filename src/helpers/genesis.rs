@@ -1,7 +1,9 @@
 use anyhow::{anyhow, Context, Result};
-use indy_vdr::pool::PoolTransactions;
+use indy_vdr::config::PoolConfig;
+use indy_vdr::pool::{PoolTransactions, ProtocolVersion};
 use reqwest;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::time::Duration;
 use tokio::time::timeout;
@@ -23,7 +25,18 @@ impl GenesisSource {
         }
     }
 
-    pub async fn load_transactions(&self) -> anyhow::Result<PoolTransactions> {
+    /// The file path or URL of the genesis source
+    pub fn location(&self) -> &str {
+        match self {
+            GenesisSource::LocalFile(path) => path,
+            GenesisSource::Url(url) => url,
+        }
+    }
+
+    pub async fn load_transactions(
+        &self,
+        settings: &PoolSettings,
+    ) -> anyhow::Result<PoolTransactions> {
         match self {
             GenesisSource::LocalFile(path) => {
                 log::debug!("Loading genesis file from path: {}", path);
@@ -34,13 +47,13 @@ impl GenesisSource {
                 log::debug!("Fetching genesis file from URL: {}", url);
 
                 let client = Client::builder()
-                    .timeout(Duration::from_secs(10))
+                    .timeout(Duration::from_secs(settings.genesis_client_timeout))
                     .build()
                     .context("Failed to create HTTP client")?;
 
                 // Wrap the request in a timeout
                 let response = timeout(
-                    Duration::from_secs(15), // Overall operation timeout
+                    Duration::from_secs(settings.genesis_request_timeout), // Overall operation timeout
                     client.get(url).send(),
                 )
                 .await
@@ -62,7 +75,7 @@ impl GenesisSource {
 
                 // Wrap content reading in a timeout as well
                 let content = timeout(
-                    Duration::from_secs(5), // Content reading timeout
+                    Duration::from_secs(settings.genesis_read_timeout), // Content reading timeout
                     response.text(),
                 )
                 .await
@@ -105,6 +118,97 @@ impl GenesisSource {
     }
 }
 
+/// Pool and connection settings of a network. The pool fields feed the indy-vdr
+/// `PoolConfig`, timeouts are in seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PoolSettings {
+    /// 1 for Node 1.3, 2 for Node 1.4
+    pub protocol_version: i64,
+    pub ack_timeout: i64,
+    pub reply_timeout: i64,
+    /// Maximum age of a state proof before the reply is not trusted alone
+    pub freshness_threshold: u64,
+    pub conn_request_limit: usize,
+    pub conn_active_timeout: i64,
+    pub request_read_nodes: usize,
+    pub genesis_client_timeout: u64,
+    pub genesis_request_timeout: u64,
+    pub genesis_read_timeout: u64,
+    pub connect_timeout: u64,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        let config = PoolConfig::default();
+        Self {
+            protocol_version: config.protocol_version.to_id(),
+            ack_timeout: config.ack_timeout,
+            reply_timeout: config.reply_timeout,
+            freshness_threshold: config.freshness_threshold,
+            conn_request_limit: config.conn_request_limit,
+            conn_active_timeout: config.conn_active_timeout,
+            request_read_nodes: config.request_read_nodes,
+            genesis_client_timeout: 10,
+            genesis_request_timeout: 15,
+            genesis_read_timeout: 5,
+            connect_timeout: 20,
+        }
+    }
+}
+
+impl PoolSettings {
+    pub fn validate(&self) -> Result<()> {
+        ProtocolVersion::from_id(self.protocol_version)
+            .map_err(|_| anyhow!("Protocol version must be 1 (Node 1.3) or 2 (Node 1.4)"))?;
+        for (name, value) in [
+            ("Ack timeout", self.ack_timeout),
+            ("Reply timeout", self.reply_timeout),
+            ("Connection active timeout", self.conn_active_timeout),
+        ] {
+            if value <= 0 {
+                return Err(anyhow!("{} must be positive", name));
+            }
+        }
+        if self.reply_timeout < self.ack_timeout {
+            return Err(anyhow!(
+                "Reply timeout must not be shorter than the ack timeout"
+            ));
+        }
+        if self.conn_request_limit == 0 || self.request_read_nodes == 0 {
+            return Err(anyhow!(
+                "Connection request limit and read nodes must be at least 1"
+            ));
+        }
+        if [
+            self.genesis_client_timeout,
+            self.genesis_request_timeout,
+            self.genesis_read_timeout,
+            self.connect_timeout,
+        ]
+        .contains(&0)
+        {
+            return Err(anyhow!("Genesis and connection timeouts must be positive"));
+        }
+        Ok(())
+    }
+
+    pub fn pool_config(&self) -> Result<PoolConfig> {
+        self.validate()?;
+        Ok(PoolConfig {
+            protocol_version: ProtocolVersion::from_id(self.protocol_version)
+                .map_err(|e| anyhow!("{}", e))?,
+            freshness_threshold: self.freshness_threshold,
+            ack_timeout: self.ack_timeout,
+            reply_timeout: self.reply_timeout,
+            conn_request_limit: self.conn_request_limit,
+            conn_active_timeout: self.conn_active_timeout,
+            request_read_nodes: self.request_read_nodes,
+            ..PoolConfig::default()
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +226,44 @@ mod tests {
         let invalid = "not/a/real/path";
         assert!(GenesisSource::from_str(invalid).is_err());
     }
+
+    #[test]
+    fn test_pool_settings() {
+        let settings = PoolSettings::default();
+        assert_eq!(settings.pool_config().unwrap(), PoolConfig::default());
+
+        let settings = PoolSettings {
+            protocol_version: 1,
+            reply_timeout: 120,
+            ..PoolSettings::default()
+        };
+        let config = settings.pool_config().unwrap();
+        assert_eq!(config.protocol_version, ProtocolVersion::Node1_3);
+        assert_eq!(config.reply_timeout, 120);
+
+        for invalid in [
+            PoolSettings {
+                protocol_version: 3,
+                ..PoolSettings::default()
+            },
+            PoolSettings {
+                reply_timeout: 5,
+                ..PoolSettings::default()
+            },
+            PoolSettings {
+                request_read_nodes: 0,
+                ..PoolSettings::default()
+            },
+            PoolSettings {
+                connect_timeout: 0,
+                ..PoolSettings::default()
+            },
+        ] {
+            assert!(invalid.pool_config().is_err());
+        }
+
+        let partial: PoolSettings = serde_json::from_str(r#"{"ack_timeout": 30}"#).unwrap();
+        assert_eq!(partial.ack_timeout, 30);
+        assert_eq!(partial.connect_timeout, 20);
+    }
 }
@@ -0,0 +1,424 @@
+use crate::helpers::diddoc::full_verkey;
+use crate::helpers::ledgers::IndyLedger;
+use crate::helpers::mirror::LedgerMirror;
+use crate::helpers::schema_lookup::{lookup_schema_id, FoundSchema};
+use crate::helpers::state_proof::ProofStatus;
+use anyhow::{anyhow, Result};
+use indy_data_types::anoncreds::schema::{
+    AttributeNames, Schema as IndySchema, SchemaV1 as IndySchemaV1,
+};
+use indy_data_types::did::DidValue;
+use indy_data_types::CredentialDefinitionId;
+use indy_vdr::ledger::RequestBuilder;
+use indy_vdr::pool::ProtocolVersion;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaEntry {
+    pub name: String,
+    pub version: String,
+    pub attr_names: BTreeSet<String>,
+    pub seq_no: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CredDefEntry {
+    pub id: String,
+    pub tag: String,
+    pub schema_seq_no: u64,
+    /// Name and version of the referenced schema, when the mirror holds it
+    pub schema: Option<(String, String)>,
+}
+
+impl CredDefEntry {
+    /// Seq nos differ between networks, cred defs are matched on their schema name,
+    /// version and tag instead
+    fn key(&self) -> String {
+        match &self.schema {
+            Some((name, version)) => format!("{} {} / {}", name, version, self.tag),
+            None => format!("schema #{} / {}", self.schema_seq_no, self.tag),
+        }
+    }
+}
+
+/// What an issuer wrote on one network
+#[derive(Debug, Clone, Default)]
+pub struct IssuerInventory {
+    pub nym: Option<Value>,
+    pub schemas: Vec<SchemaEntry>,
+    pub cred_defs: Vec<CredDefEntry>,
+}
+
+impl IssuerInventory {
+    pub fn from_mirror(mirror: &LedgerMirror, did: &str) -> Self {
+        let schemas = mirror
+            .written_by(did, "101")
+            .into_iter()
+            .map(|txn| {
+                let data = &txn["txn"]["data"]["data"];
+                SchemaEntry {
+                    name: data["name"].as_str().unwrap_or_default().to_string(),
+                    version: data["version"].as_str().unwrap_or_default().to_string(),
+                    attr_names: data["attr_names"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|name| name.as_str().map(str::to_string))
+                        .collect(),
+                    seq_no: txn["txnMetadata"]["seqNo"].as_u64(),
+                }
+            })
+            .collect();
+
+        let cred_defs = mirror
+            .written_by(did, "102")
+            .into_iter()
+            .filter_map(|txn| {
+                let data = &txn["txn"]["data"];
+                let schema_seq_no = data["ref"].as_u64()?;
+                let tag = data["tag"].as_str().unwrap_or_default().to_string();
+                let schema = mirror.get(schema_seq_no).and_then(|schema| {
+                    let schema = &schema["txn"]["data"]["data"];
+                    Some((
+                        schema["name"].as_str()?.to_string(),
+                        schema["version"].as_str()?.to_string(),
+                    ))
+                });
+                Some(CredDefEntry {
+                    id: format!(
+                        "{}:3:{}:{}:{}",
+                        did,
+                        data["signature_type"].as_str().unwrap_or("CL"),
+                        schema_seq_no,
+                        tag
+                    ),
+                    tag,
+                    schema_seq_no,
+                    schema,
+                })
+            })
+            .collect();
+
+        Self {
+            nym: mirror.nym(did),
+            schemas,
+            cred_defs,
+        }
+    }
+
+    fn has_schema(&self, name: &str, version: &str) -> bool {
+        self.schemas
+            .iter()
+            .any(|schema| schema.name == name && schema.version == version)
+    }
+
+    /// Completes an inventory read from a mirror with what the ledger reports: the
+    /// current NYM, and the schemas and cred defs of `other` that the mirror lacks,
+    /// as they may have been written after it was synced.
+    /// Returns the state proof status of the replies.
+    pub async fn complete_from_ledger(
+        &mut self,
+        ledger: &IndyLedger,
+        did: &str,
+        other: &IssuerInventory,
+    ) -> Result<Option<ProofStatus>> {
+        let mut proofs = Vec::new();
+        let nym = ledger
+            .get_nym(did)
+            .await
+            .map_err(|e| anyhow!("GET_NYM failed: {}", e))?;
+        proofs.push(nym.proof);
+        self.nym = nym.data;
+
+        for schema in &other.schemas {
+            if self.has_schema(&schema.name, &schema.version) {
+                continue;
+            }
+            let id = lookup_schema_id(did, &schema.name, &schema.version)?;
+            let reply = ledger
+                .get_schema(&id)
+                .await
+                .map_err(|e| anyhow!("GET_SCHEMA failed: {}", e))?;
+            proofs.push(reply.proof);
+            if let Some(found) = FoundSchema::from_reply(&id, &reply.data)? {
+                self.schemas.push(SchemaEntry {
+                    name: schema.name.clone(),
+                    version: schema.version.clone(),
+                    attr_names: found.attr_names.into_iter().collect(),
+                    seq_no: Some(found.seq_no),
+                });
+            }
+        }
+
+        for cred_def in &other.cred_defs {
+            if self
+                .cred_defs
+                .iter()
+                .any(|known| known.key() == cred_def.key())
+            {
+                continue;
+            }
+            // Without its schema on this ledger, the cred def cannot be on it either
+            let Some((name, version)) = &cred_def.schema else {
+                continue;
+            };
+            let Some(schema_seq_no) = self
+                .schemas
+                .iter()
+                .find(|schema| &schema.name == name && &schema.version == version)
+                .and_then(|schema| schema.seq_no)
+            else {
+                continue;
+            };
+            let signature_type = cred_def.id.split(':').nth(2).unwrap_or("CL");
+            let id = format!(
+                "{}:3:{}:{}:{}",
+                did, signature_type, schema_seq_no, cred_def.tag
+            );
+            let reply = ledger
+                .get_cred_def(&CredentialDefinitionId(id.clone()))
+                .await
+                .map_err(|e| anyhow!("GET_CRED_DEF failed: {}", e))?;
+            proofs.push(reply.proof);
+            if reply.data.is_some() {
+                self.cred_defs.push(CredDefEntry {
+                    id,
+                    tag: cred_def.tag.clone(),
+                    schema_seq_no,
+                    schema: cred_def.schema.clone(),
+                });
+            }
+        }
+
+        Ok(ProofStatus::combine(proofs))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffStatus {
+    OnlySource,
+    OnlyTarget,
+    /// On both networks with different content, the fields that differ
+    Differs(String),
+    Same,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffEntry {
+    pub kind: &'static str,
+    pub item: String,
+    pub status: DiffStatus,
+}
+
+/// Compares what an issuer wrote on two networks
+pub fn diff_inventories(
+    did: &str,
+    source: &IssuerInventory,
+    target: &IssuerInventory,
+) -> Vec<DiffEntry> {
+    let mut entries = Vec::new();
+
+    let nym_status = match (&source.nym, &target.nym) {
+        (None, None) => None,
+        (Some(_), None) => Some(DiffStatus::OnlySource),
+        (None, Some(_)) => Some(DiffStatus::OnlyTarget),
+        (Some(source), Some(target)) => {
+            let verkey = |nym: &Value| {
+                nym["verkey"]
+                    .as_str()
+                    .map(|verkey| full_verkey(did, verkey).unwrap_or(verkey.to_string()))
+            };
+            let mut differences = Vec::new();
+            if verkey(source) != verkey(target) {
+                differences.push("verkey");
+            }
+            if source["role"] != target["role"] {
+                differences.push("role");
+            }
+            Some(if differences.is_empty() {
+                DiffStatus::Same
+            } else {
+                DiffStatus::Differs(differences.join(", "))
+            })
+        }
+    };
+    if let Some(status) = nym_status {
+        entries.push(DiffEntry {
+            kind: "NYM",
+            item: did.to_string(),
+            status,
+        });
+    }
+
+    let schema_key = |schema: &SchemaEntry| format!("{} {}", schema.name, schema.version);
+    let source_schemas = keyed(&source.schemas, schema_key);
+    let target_schemas = keyed(&target.schemas, schema_key);
+    for (item, status) in compare_keys(&source_schemas, &target_schemas, |source, target| {
+        (source.attr_names != target.attr_names).then(|| "attributes".to_string())
+    }) {
+        entries.push(DiffEntry {
+            kind: "Schema",
+            item,
+            status,
+        });
+    }
+
+    let source_cred_defs = keyed(&source.cred_defs, CredDefEntry::key);
+    let target_cred_defs = keyed(&target.cred_defs, CredDefEntry::key);
+    for (item, status) in compare_keys(&source_cred_defs, &target_cred_defs, |_, _| None) {
+        entries.push(DiffEntry {
+            kind: "Cred def",
+            item,
+            status,
+        });
+    }
+    entries
+}
+
+fn keyed<T>(items: &[T], key: impl Fn(&T) -> String) -> BTreeMap<String, &T> {
+    items.iter().map(|item| (key(item), item)).collect()
+}
+
+fn compare_keys<T>(
+    source: &BTreeMap<String, T>,
+    target: &BTreeMap<String, T>,
+    differences: impl Fn(&T, &T) -> Option<String>,
+) -> Vec<(String, DiffStatus)> {
+    let keys: BTreeSet<&String> = source.keys().chain(target.keys()).collect();
+    keys.into_iter()
+        .map(|key| {
+            let status = match (source.get(key), target.get(key)) {
+                (Some(source), Some(target)) => {
+                    differences(source, target).map_or(DiffStatus::Same, DiffStatus::Differs)
+                }
+                (Some(_), None) => DiffStatus::OnlySource,
+                _ => DiffStatus::OnlyTarget,
+            };
+            (key.clone(), status)
+        })
+        .collect()
+}
+
+/// Unsigned SCHEMA requests writing the source schemas missing on the target,
+/// with the schema they write. `protocol_version` is the one of the target pool.
+pub fn replication_requests(
+    did: &str,
+    source: &IssuerInventory,
+    target: &IssuerInventory,
+    protocol_version: ProtocolVersion,
+) -> Result<Vec<(String, String)>> {
+    let request_builder = RequestBuilder::new(protocol_version);
+    source
+        .schemas
+        .iter()
+        .filter(|schema| !target.has_schema(&schema.name, &schema.version))
+        .map(|schema| {
+            let item = format!("{} {}", schema.name, schema.version);
+            let id = lookup_schema_id(did, &schema.name, &schema.version)?;
+            let schema = IndySchema::SchemaV1(IndySchemaV1 {
+                id,
+                name: schema.name.clone(),
+                version: schema.version.clone(),
+                attr_names: AttributeNames::from(
+                    schema.attr_names.iter().cloned().collect::<Vec<_>>(),
+                ),
+                seq_no: None,
+            });
+            let request = request_builder
+                .build_schema_request(&DidValue(did.to_string()), schema)
+                .map_err(|e| anyhow!("Failed to build the SCHEMA request: {}", e))?;
+            Ok((item, serde_json::to_string_pretty(&request.req_json)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const DID: &str = "GAAguaTbEHjvxL6i64YmAo";
+
+    fn schema(name: &str, attr_names: &[&str]) -> SchemaEntry {
+        SchemaEntry {
+            name: name.to_string(),
+            version: "1.0".to_string(),
+            attr_names: attr_names.iter().map(|name| name.to_string()).collect(),
+            seq_no: None,
+        }
+    }
+
+    fn cred_def(schema_seq_no: u64, schema_name: &str) -> CredDefEntry {
+        CredDefEntry {
+            id: format!("{}:3:CL:{}:default", DID, schema_seq_no),
+            tag: "default".to_string(),
+            schema_seq_no,
+            schema: Some((schema_name.to_string(), "1.0".to_string())),
+        }
+    }
+
+    #[test]
+    fn test_diff_inventories() {
+        let source = IssuerInventory {
+            nym: Some(json!({"dest": DID, "verkey": "~CoRER63DVYnWZtK8uAzNbx", "role": "101"})),
+            schemas: vec![schema("Degree", &["name"]), schema("Badge", &["level"])],
+            cred_defs: vec![cred_def(10, "Degree")],
+        };
+        let target = IssuerInventory {
+            nym: Some(json!({"dest": DID, "verkey": "~CoRER63DVYnWZtK8uAzNbx", "role": null})),
+            schemas: vec![schema("Degree", &["name"]), schema("Badge", &["rank"])],
+            // Same cred def, the schema got another seq_no on the target
+            cred_defs: vec![cred_def(250, "Degree"), cred_def(251, "Badge")],
+        };
+        let entries = diff_inventories(DID, &source, &target);
+        let status = |kind: &str, item: &str| {
+            entries
+                .iter()
+                .find(|entry| entry.kind == kind && entry.item == item)
+                .map(|entry| entry.status.clone())
+        };
+        assert_eq!(
+            status("NYM", DID),
+            Some(DiffStatus::Differs("role".to_string()))
+        );
+        assert_eq!(status("Schema", "Degree 1.0"), Some(DiffStatus::Same));
+        assert_eq!(
+            status("Schema", "Badge 1.0"),
+            Some(DiffStatus::Differs("attributes".to_string()))
+        );
+        assert_eq!(
+            status("Cred def", "Degree 1.0 / default"),
+            Some(DiffStatus::Same)
+        );
+        assert_eq!(
+            status("Cred def", "Badge 1.0 / default"),
+            Some(DiffStatus::OnlyTarget)
+        );
+    }
+
+    #[test]
+    fn test_replication_requests() {
+        let source = IssuerInventory {
+            schemas: vec![schema("Degree", &["name"]), schema("Badge", &["level"])],
+            ..Default::default()
+        };
+        let target = IssuerInventory {
+            schemas: vec![schema("Degree", &["name"])],
+            ..Default::default()
+        };
+        let requests =
+            replication_requests(DID, &source, &target, ProtocolVersion::Node1_3).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].0, "Badge 1.0");
+        let request: Value = serde_json::from_str(&requests[0].1).unwrap();
+        assert_eq!(request["identifier"], DID);
+        assert_eq!(request["operation"]["type"], "101");
+        assert_eq!(request["protocolVersion"], 1);
+        assert!(
+            replication_requests(DID, &target, &source, ProtocolVersion::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -1,30 +1,74 @@
 use crate::app::{NymInfo, TransactionOptions};
-use crate::helpers::genesis::GenesisSource;
-use crate::helpers::wallet::IndyWallet;
+use crate::helpers::auth_rules::AuthRuleKey;
+use crate::helpers::did::unqualified_did;
+use crate::helpers::diddoc::{full_verkey, validate_diddoc_content};
+use crate::helpers::fees::{FeeSchedule, GET_FEES};
+use crate::helpers::genesis::{GenesisSource, PoolSettings};
+use crate::helpers::pool_admin::PoolUpgrade;
+use crate::helpers::request_json::{is_signed, new_req_id, refresh_request};
+use crate::helpers::signer::{sign_request_bytes, Signer};
+use crate::helpers::state_proof::ProofStatus;
+use indy_data_types::anoncreds::rev_reg::RevocationRegistryDelta;
+use indy_data_types::anoncreds::rev_reg_def::{RegistryType, RevocationRegistryDefinition};
 use indy_data_types::anoncreds::schema::Schema;
 use indy_data_types::did::DidValue;
+use indy_data_types::{CredentialDefinitionId, RevocationRegistryId, SchemaId};
 use indy_vdr::common::error::{VdrError, VdrErrorKind, VdrResult};
-use indy_vdr::config::PoolConfig;
 use indy_vdr::ledger::constants::UpdateRole;
+use indy_vdr::ledger::requests::auth_rule::{AuthRuleData, AuthRules};
+use indy_vdr::ledger::requests::node::NodeOperationData;
 use indy_vdr::pool::helpers::perform_ledger_request;
 use indy_vdr::pool::{LocalPool, Pool, PoolBuilder, PreparedRequest, RequestResult};
+use serde_json::{json, Value};
 
 #[derive(Clone)]
 pub struct IndyLedger {
     pub pool: LocalPool,
+    /// Reject verkey lookups whose reply has no verified state proof
+    pub strict_proofs: bool,
+}
+
+/// Identifier of read requests sent without a DID. Nodes need one on every request
+/// but do not check it on reads, this is the one the indy-vdr builders use by default.
+const DEFAULT_READ_SUBMITTER: &str = "LibindyDid111111111111";
+
+/// Data of a read request with the state proof status of its reply
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadReply<T> {
+    pub data: T,
+    pub proof: ProofStatus,
+}
+
+/// Outcome of a key rotation the ledger accepted, with the NYM reply
+#[derive(Debug, Clone, PartialEq)]
+pub enum RotationOutcome {
+    /// GET_NYM returns the new verkey
+    Verified(String),
+    /// The write was accepted but GET_NYM did not confirm the new verkey in time
+    Unverified(String),
 }
 
 impl IndyLedger {
-    pub async fn new(genesis_source: GenesisSource) -> VdrResult<Self> {
-        let pool_transactions = genesis_source.load_transactions().await.map_err(|e| {
+    pub async fn new(genesis_source: GenesisSource, settings: &PoolSettings) -> VdrResult<Self> {
+        let config = settings.pool_config().map_err(|e| {
             VdrError::new(
-                VdrErrorKind::Input,
-                Some(format!("Failed to load genesis transactions: {}", e)),
+                VdrErrorKind::Config,
+                Some(format!("Invalid pool settings: {}", e)),
                 None,
             )
         })?;
+        let pool_transactions = genesis_source
+            .load_transactions(settings)
+            .await
+            .map_err(|e| {
+                VdrError::new(
+                    VdrErrorKind::Input,
+                    Some(format!("Failed to load genesis transactions: {}", e)),
+                    None,
+                )
+            })?;
 
-        let pool = PoolBuilder::new(PoolConfig::default(), pool_transactions)
+        let pool = PoolBuilder::new(config, pool_transactions)
             .into_local()
             .map_err(|e| {
                 VdrError::new(
@@ -34,7 +78,10 @@ impl IndyLedger {
                 )
             })?;
 
-        Ok(IndyLedger { pool })
+        Ok(IndyLedger {
+            pool,
+            strict_proofs: false,
+        })
     }
 
     pub async fn check_connection(&self) -> VdrResult<bool> {
@@ -57,9 +104,205 @@ impl IndyLedger {
         }
     }
 
+    /// Fetches the current NYM data of a DID, `None` if the DID is not on the ledger.
+    /// In strict mode the reply must carry a verified state proof.
+    pub async fn get_nym(&self, did: &str) -> VdrResult<ReadReply<Option<Value>>> {
+        let request = self.pool.get_request_builder().build_get_nym_request(
+            None,
+            &DidValue(did.to_string()),
+            None,
+            None,
+        )?;
+
+        self._submit_read(&request, true).await
+    }
+
+    /// Full verkey of a DID on the ledger, `None` if the DID has no NYM
+    pub async fn get_verkey(&self, did: &str) -> VdrResult<Option<String>> {
+        self.get_nym(did)
+            .await?
+            .data
+            .and_then(|nym| nym["verkey"].as_str().map(str::to_string))
+            .map(|verkey| full_verkey(did, &verkey))
+            .transpose()
+            .map_err(|e| {
+                VdrError::new(
+                    VdrErrorKind::Input,
+                    Some(format!("Invalid verkey returned by the ledger: {}", e)),
+                    None,
+                )
+            })
+    }
+
+    /// Fetches a raw ATTRIB (e.g. `endpoint`) of a DID, `None` if it was never set
+    pub async fn get_attrib(&self, did: &str, raw: &str) -> VdrResult<ReadReply<Option<Value>>> {
+        let request = self.pool.get_request_builder().build_get_attrib_request(
+            None,
+            &DidValue(did.to_string()),
+            Some(raw.to_string()),
+            None,
+            None,
+            None,
+            None,
+        )?;
+
+        self._submit_read(&request, false).await
+    }
+
+    /// Raw GET_SCHEMA reply, the seqNo of the schema is next to its data
+    pub async fn get_schema(&self, schema_id: &SchemaId) -> VdrResult<ReadReply<String>> {
+        let request = self
+            .pool
+            .get_request_builder()
+            .build_get_schema_request(None, schema_id)?;
+        self._submit_read_raw(&request, false).await
+    }
+
+    /// Fetches a credential definition, `None` when the ledger has no such cred def
+    pub async fn get_cred_def(
+        &self,
+        cred_def_id: &CredentialDefinitionId,
+    ) -> VdrResult<ReadReply<Option<Value>>> {
+        let request = self
+            .pool
+            .get_request_builder()
+            .build_get_cred_def_request(None, cred_def_id)?;
+        self._submit_read(&request, false).await
+    }
+
+    /// Fetches one transaction of a ledger (0 = POOL, 1 = DOMAIN, 2 = CONFIG),
+    /// `None` past the end of the ledger
+    pub async fn get_txn(
+        &self,
+        ledger_type: i32,
+        seq_no: i32,
+    ) -> VdrResult<ReadReply<Option<Value>>> {
+        let request =
+            self.pool
+                .get_request_builder()
+                .build_get_txn_request(None, ledger_type, seq_no)?;
+
+        self._submit_read(&request, false).await
+    }
+
+    /// Fetches the transactions `from..from + count`, stopping at the end of the ledger
+    pub async fn get_txn_range(
+        &self,
+        ledger_type: i32,
+        from: i32,
+        count: i32,
+    ) -> VdrResult<ReadReply<Vec<Value>>> {
+        let mut txns = Vec::new();
+        let mut proofs = Vec::new();
+        for seq_no in from..from.saturating_add(count) {
+            let reply = self.get_txn(ledger_type, seq_no).await?;
+            proofs.push(reply.proof);
+            match reply.data {
+                Some(txn) => txns.push(txn),
+                None => break,
+            }
+        }
+        Ok(ReadReply {
+            data: txns,
+            proof: ProofStatus::combine(proofs).unwrap_or(ProofStatus::Unverified {
+                reason: "no reply".to_string(),
+            }),
+        })
+    }
+
+    /// Protocol version the pool was configured with, as used in `protocolVersion`
+    pub fn protocol_version(&self) -> i64 {
+        self.pool.get_request_builder().protocol_version.to_id()
+    }
+
+    /// Seq no of the transaction written from a signed NYM request, found by comparing
+    /// the submitter and reqId of the latest NYM of its dest
+    pub async fn find_written_nym(&self, request: &Value) -> VdrResult<Option<u64>> {
+        let (Some("1"), Some(dest), Some(identifier), Some(req_id)) = (
+            request["operation"]["type"].as_str(),
+            request["operation"]["dest"].as_str(),
+            request["identifier"].as_str(),
+            request["reqId"].as_u64(),
+        ) else {
+            return Ok(None);
+        };
+        let Some(seq_no) = self
+            .get_nym(dest)
+            .await?
+            .data
+            .and_then(|nym| nym["seqNo"].as_i64())
+        else {
+            return Ok(None);
+        };
+
+        let txn = self.get_txn(1, seq_no as i32).await?.data;
+        Ok(txn
+            .filter(|txn| {
+                let metadata = &txn["txn"]["metadata"];
+                metadata["from"].as_str() == Some(identifier)
+                    && metadata["reqId"].as_u64() == Some(req_id)
+            })
+            .map(|_| seq_no as u64))
+    }
+
+    /// Fetches auth rules, all of them when no key is given
+    pub async fn get_auth_rules(
+        &self,
+        key: Option<&AuthRuleKey>,
+    ) -> VdrResult<ReadReply<Vec<Value>>> {
+        let request = match key {
+            Some(key) => self
+                .pool
+                .get_request_builder()
+                .build_get_auth_rule_request(
+                    None,
+                    Some(key.auth_type.clone()),
+                    Some(key.auth_action.clone()),
+                    Some(key.field.clone()),
+                    key.old_value.clone(),
+                    Some(key.new_value.clone()),
+                )?,
+            None => self
+                .pool
+                .get_request_builder()
+                .build_get_auth_rule_request(None, None, None, None, None, None)?,
+        };
+
+        let reply = self._submit_read(&request, false).await?;
+        let rules = match reply.data {
+            Some(Value::Array(rules)) => rules,
+            Some(rule) => vec![rule],
+            None => Vec::new(),
+        };
+        Ok(ReadReply {
+            data: rules,
+            proof: reply.proof,
+        })
+    }
+
+    /// Fee schedule of the payment plugin. Networks without the plugin reject GET_FEES.
+    /// The payment plugin request has no builder, it is sent as `submitter_did` when given.
+    pub async fn get_fees(&self, submitter_did: Option<&str>) -> VdrResult<ReadReply<FeeSchedule>> {
+        let request = PreparedRequest::from_request_json(
+            json!({
+                "identifier": submitter_did.unwrap_or(DEFAULT_READ_SUBMITTER),
+                "operation": {"type": GET_FEES},
+                "protocolVersion": self.protocol_version(),
+                "reqId": new_req_id(),
+            })
+            .to_string(),
+        )?;
+        let reply = self._submit_read_raw(&request, false).await?;
+        Ok(ReadReply {
+            data: FeeSchedule::from_reply(&reply.data)
+                .map_err(|e| VdrError::new(VdrErrorKind::Input, Some(format!("{:#}", e)), None))?,
+            proof: reply.proof,
+        })
+    }
+
     // pub async fn publish_cred_def(
     //     &self,
-    //     wallet: &IndyWallet,
+    //     signer: &dyn Signer,
     //     submitter_did: &str,
     //     cred_def: &CredentialDefinition,
     // ) -> VdrResult<String> {
@@ -73,11 +316,11 @@ impl IndyLedger {
     //         .build_cred_def_request(&DidValue(submitter_did.to_string()), cred_def)
     //         .unwrap();
     //
-    //     self._sign_and_submit_request(wallet, &mut request).await
+    //     self._sign_and_submit_request(signer, &mut request).await
     // }
     pub async fn publish_schema(
         &self,
-        wallet: &IndyWallet,
+        signer: &dyn Signer,
         submitter_did: &str,
         schema: &Schema,
         options: &TransactionOptions,
@@ -89,7 +332,7 @@ impl IndyLedger {
 
         let result = if options.sign {
             let sig_bytes = request.get_signature_input()?;
-            let signature = wallet.sign(sig_bytes.as_bytes()).await;
+            let signature = sign_request_bytes(signer, sig_bytes.as_bytes()).await?;
             request.set_signature(&signature)?;
             serde_json::to_string_pretty(&request.req_json).map_err(|e| {
                 VdrError::new(
@@ -123,26 +366,40 @@ impl IndyLedger {
 
     pub async fn publish_nym(
         &self,
-        wallet: &IndyWallet,
+        signer: &dyn Signer,
         submitter_did: &str,
         nym_info: &mut NymInfo,
         role: UpdateRole,
         options: &TransactionOptions,
     ) -> VdrResult<String> {
         let alias = nym_info.alias.clone().filter(|a| !a.trim().is_empty());
+        let diddoc_content = validate_diddoc_content(&nym_info.diddoc_content).map_err(|e| {
+            VdrError::new(
+                VdrErrorKind::Input,
+                Some(format!("Invalid DIDDoc content: {}", e)),
+                None,
+            )
+        })?;
+        let dest = unqualified_did(&nym_info.did).map_err(|e| {
+            VdrError::new(
+                VdrErrorKind::Input,
+                Some(format!("Invalid NYM DID: {}", e)),
+                None,
+            )
+        })?;
         let mut request = self.pool.get_request_builder().build_nym_request(
             &DidValue(submitter_did.to_string()),
-            &DidValue(nym_info.did.to_string()),
+            &DidValue(dest),
             Some(nym_info.verkey.to_string()),
             alias,
             Some(role),
-            None,
-            None,
+            diddoc_content.as_ref(),
+            nym_info.version,
         )?;
 
         let result = if options.sign {
             let sig_bytes = request.get_signature_input()?;
-            let signature = wallet.sign(sig_bytes.as_bytes()).await;
+            let signature = sign_request_bytes(signer, sig_bytes.as_bytes()).await?;
             request.set_signature(&signature)?;
             serde_json::to_string_pretty(&request.req_json).map_err(|e| {
                 VdrError::new(
@@ -168,13 +425,274 @@ impl IndyLedger {
         }
     }
 
+    pub async fn publish_pool_upgrade(
+        &self,
+        signer: &dyn Signer,
+        upgrade: &PoolUpgrade,
+        options: &TransactionOptions,
+    ) -> VdrResult<String> {
+        let mut request = self.pool.get_request_builder().build_pool_upgrade_request(
+            &DidValue(signer.did().to_string()),
+            &upgrade.name,
+            &upgrade.version,
+            &upgrade.action,
+            &upgrade.sha256,
+            upgrade.timeout,
+            upgrade.schedule.clone(),
+            upgrade.justification.as_deref(),
+            upgrade.reinstall,
+            upgrade.force,
+            upgrade.package.as_deref(),
+        )?;
+
+        self._sign_and_finalize_request(signer, &mut request, options)
+            .await
+    }
+
+    /// Adds or updates a validator node, signed by its steward
+    pub async fn publish_node(
+        &self,
+        signer: &dyn Signer,
+        dest: &str,
+        data: NodeOperationData,
+        options: &TransactionOptions,
+    ) -> VdrResult<String> {
+        let mut request = self.pool.get_request_builder().build_node_request(
+            &DidValue(signer.did().to_string()),
+            &DidValue(dest.to_string()),
+            data,
+        )?;
+
+        self._sign_and_finalize_request(signer, &mut request, options)
+            .await
+    }
+
+    pub async fn publish_pool_config(
+        &self,
+        signer: &dyn Signer,
+        writes: bool,
+        force: bool,
+        options: &TransactionOptions,
+    ) -> VdrResult<String> {
+        let mut request = self.pool.get_request_builder().build_pool_config_request(
+            &DidValue(signer.did().to_string()),
+            writes,
+            force,
+        )?;
+
+        self._sign_and_finalize_request(signer, &mut request, options)
+            .await
+    }
+
+    pub async fn publish_pool_restart(
+        &self,
+        signer: &dyn Signer,
+        action: &str,
+        datetime: &str,
+        options: &TransactionOptions,
+    ) -> VdrResult<String> {
+        let mut request = self.pool.get_request_builder().build_pool_restart_request(
+            &DidValue(signer.did().to_string()),
+            action,
+            Some(datetime),
+        )?;
+
+        self._sign_and_finalize_request(signer, &mut request, options)
+            .await
+    }
+
+    /// Updates the role of an existing NYM and/or suspends it by setting its verkey to null
+    pub async fn update_nym(
+        &self,
+        signer: &dyn Signer,
+        dest: &str,
+        role: Option<UpdateRole>,
+        suspend: bool,
+        options: &TransactionOptions,
+    ) -> VdrResult<String> {
+        let mut request = self.pool.get_request_builder().build_nym_request(
+            &DidValue(signer.did().to_string()),
+            &DidValue(dest.to_string()),
+            None,
+            None,
+            role,
+            None,
+            None,
+        )?;
+        if suspend {
+            // The builder omits a missing verkey, which would leave it unchanged
+            request.req_json["operation"]["verkey"] = Value::Null;
+        }
+
+        self._sign_and_finalize_request(signer, &mut request, options)
+            .await
+    }
+
+    /// Writes a single auth rule (AUTH_RULE)
+    pub async fn publish_auth_rule(
+        &self,
+        signer: &dyn Signer,
+        rule: &AuthRuleData,
+        options: &TransactionOptions,
+    ) -> VdrResult<String> {
+        let (action, auth_type, field, old_value, new_value, constraint) = match rule.clone() {
+            AuthRuleData::Add(data) => (
+                "ADD",
+                data.auth_type,
+                data.field,
+                None,
+                data.new_value,
+                data.constraint,
+            ),
+            AuthRuleData::Edit(data) => (
+                "EDIT",
+                data.auth_type,
+                data.field,
+                data.old_value,
+                data.new_value,
+                data.constraint,
+            ),
+        };
+        let mut request = self.pool.get_request_builder().build_auth_rule_request(
+            &DidValue(signer.did().to_string()),
+            auth_type,
+            action.to_string(),
+            field,
+            old_value,
+            new_value,
+            constraint,
+        )?;
+
+        self._sign_and_finalize_request(signer, &mut request, options)
+            .await
+    }
+
+    /// Writes several auth rules in one transaction (AUTH_RULES)
+    pub async fn publish_auth_rules(
+        &self,
+        signer: &dyn Signer,
+        rules: AuthRules,
+        options: &TransactionOptions,
+    ) -> VdrResult<String> {
+        let mut request = self
+            .pool
+            .get_request_builder()
+            .build_auth_rules_request(&DidValue(signer.did().to_string()), rules)?;
+
+        self._sign_and_finalize_request(signer, &mut request, options)
+            .await
+    }
+
+    pub async fn publish_revoc_reg_def(
+        &self,
+        signer: &dyn Signer,
+        submitter_did: &str,
+        revoc_reg_def: &RevocationRegistryDefinition,
+        options: &TransactionOptions,
+    ) -> VdrResult<String> {
+        let mut request = self
+            .pool
+            .get_request_builder()
+            .build_revoc_reg_def_request(
+                &DidValue(submitter_did.to_string()),
+                revoc_reg_def.clone(),
+            )?;
+
+        self._sign_and_finalize_request(signer, &mut request, options)
+            .await
+    }
+
+    pub async fn publish_revoc_reg_entry(
+        &self,
+        signer: &dyn Signer,
+        submitter_did: &str,
+        revoc_reg_def_id: &RevocationRegistryId,
+        revoc_reg_entry: &RevocationRegistryDelta,
+        options: &TransactionOptions,
+    ) -> VdrResult<String> {
+        let mut request = self
+            .pool
+            .get_request_builder()
+            .build_revoc_reg_entry_request(
+                &DidValue(submitter_did.to_string()),
+                revoc_reg_def_id,
+                &RegistryType::CL_ACCUM,
+                revoc_reg_entry.clone(),
+            )?;
+
+        self._sign_and_finalize_request(signer, &mut request, options)
+            .await
+    }
+
+    /// Builds the NYM rotating the verkey of the wallet DID, signed with the current key.
+    /// It is sent once reviewed, then `check_rotated_verkey` tells whether it took effect.
+    pub async fn rotate_verkey(&self, signer: &dyn Signer, new_verkey: &str) -> VdrResult<String> {
+        let did = DidValue(signer.did().to_string());
+        let mut request = self.pool.get_request_builder().build_nym_request(
+            &did,
+            &did,
+            Some(new_verkey.to_string()),
+            None,
+            None,
+            None,
+            None,
+        )?;
+        let options = TransactionOptions {
+            sign: true,
+            send: false,
+            refresh_req_id: false,
+        };
+        self._sign_and_finalize_request(signer, &mut request, &options)
+            .await
+    }
+
+    /// Whether GET_NYM reports the rotated verkey. A node may not have caught up with
+    /// the write yet, so it is read up to `attempts` times, a second apart.
+    pub async fn check_rotated_verkey(&self, did: &str, new_verkey: &str, attempts: u32) -> bool {
+        for attempt in 1..=attempts {
+            match self.get_verkey(did).await {
+                Ok(ledger_verkey) if ledger_verkey.as_deref() == Some(new_verkey) => return true,
+                Ok(_) => log::debug!(
+                    "GET_NYM does not report the new verkey yet (attempt {})",
+                    attempt
+                ),
+                Err(e) => log::debug!("GET_NYM failed (attempt {}): {}", attempt, e),
+            }
+            if attempt < attempts {
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            }
+        }
+        false
+    }
+
+    /// Submits a request signed elsewhere without touching it
+    pub async fn submit_signed(&self, request: &Value) -> VdrResult<String> {
+        let request = PreparedRequest::from_request_json(request.to_string())?;
+        self._submit_request(&request).await
+    }
+
     // function to only send a transaction that is already signed
     pub async fn prepare_transaction(
         &self,
-        wallet: &IndyWallet,
+        signer: &dyn Signer,
         signed_txn: &mut String,
         options: &TransactionOptions,
     ) -> VdrResult<String> {
+        if options.refresh_req_id {
+            let unsigned = serde_json::from_str::<Value>(signed_txn)
+                .map(|request| !is_signed(&request))
+                .unwrap_or(false);
+            if unsigned {
+                *signed_txn =
+                    refresh_request(signed_txn, self.protocol_version()).map_err(|e| {
+                        VdrError::new(
+                            VdrErrorKind::Input,
+                            Some(format!("Failed to refresh the reqId: {}", e)),
+                            None,
+                        )
+                    })?;
+            }
+        }
         let mut req = PreparedRequest::from_request_json(signed_txn)?;
 
         println!("Request: {:?}", req.req_json.to_string());
@@ -206,8 +724,8 @@ impl IndyLedger {
 
             match req.get_signature_input() {
                 Ok(sig_bytes) => {
-                    let signature = wallet.sign(sig_bytes.as_bytes()).await;
-                    req.set_multi_signature(&DidValue(wallet.did.clone()), &signature)
+                    let signature = sign_request_bytes(signer, sig_bytes.as_bytes()).await?;
+                    req.set_multi_signature(&DidValue(signer.did().to_string()), &signature)
                         .map_err(|e| {
                             VdrError::new(
                                 VdrErrorKind::Input,
@@ -241,19 +759,86 @@ impl IndyLedger {
 
     async fn _sign_and_submit_request(
         &self,
-        wallet: &IndyWallet,
+        signer: &dyn Signer,
         request: &mut PreparedRequest,
     ) -> VdrResult<String> {
         let sig_bytes = request.get_signature_input()?;
-        let signature = wallet.sign(sig_bytes.as_bytes()).await;
+        let signature = sign_request_bytes(signer, sig_bytes.as_bytes()).await?;
         request.set_signature(&signature)?;
         self._submit_request(request).await
     }
 
+    // Signs and/or submits a request according to the transaction options
+    async fn _sign_and_finalize_request(
+        &self,
+        signer: &dyn Signer,
+        request: &mut PreparedRequest,
+        options: &TransactionOptions,
+    ) -> VdrResult<String> {
+        if options.sign {
+            let sig_bytes = request.get_signature_input()?;
+            let signature = sign_request_bytes(signer, sig_bytes.as_bytes()).await?;
+            request.set_signature(&signature)?;
+        }
+
+        if options.send {
+            self._submit_request(request).await
+        } else {
+            serde_json::to_string_pretty(&request.req_json).map_err(|e| {
+                VdrError::new(
+                    VdrErrorKind::Input,
+                    Some(format!("Failed to serialize transaction: {}", e)),
+                    None,
+                )
+            })
+        }
+    }
+
+    // Submits a read request and parses its data, `sensitive` replies are rejected
+    // without a verified state proof in strict mode
+    async fn _submit_read(
+        &self,
+        request: &PreparedRequest,
+        sensitive: bool,
+    ) -> VdrResult<ReadReply<Option<Value>>> {
+        let reply = self._submit_read_raw(request, sensitive).await?;
+        Ok(ReadReply {
+            data: parse_reply_data(&reply.data)?,
+            proof: reply.proof,
+        })
+    }
+
+    // Same as `_submit_read` for replies whose content is read beyond `result.data`
+    async fn _submit_read_raw(
+        &self,
+        request: &PreparedRequest,
+        sensitive: bool,
+    ) -> VdrResult<ReadReply<String>> {
+        let (request_result, meta) = perform_ledger_request(&self.pool, request, None).await?;
+        let reply = match request_result {
+            RequestResult::Reply(message) => message,
+            RequestResult::Failed(error) => return Err(error),
+        };
+
+        let proof = ProofStatus::from_reply(&reply, &meta.state_proof);
+        log::debug!("Read reply proof status: {}", proof.label());
+        if sensitive && self.strict_proofs && !proof.is_verified() {
+            return Err(VdrError::new(
+                VdrErrorKind::Unexpected,
+                Some(format!(
+                    "Strict mode rejected a reply without a verified state proof ({})",
+                    proof.label()
+                )),
+                None,
+            ));
+        }
+
+        Ok(ReadReply { data: reply, proof })
+    }
+
     async fn _submit_request(&self, request: &PreparedRequest) -> VdrResult<String> {
-        let (request_result, _) = perform_ledger_request(&self.pool, request, None)
-            .await
-            .unwrap();
+        // A pool failure is returned like a rejected request rather than panicking
+        let (request_result, _) = perform_ledger_request(&self.pool, request, None).await?;
         // std::thread::sleep(Duration::from_millis(500));
         match request_result {
             RequestResult::Reply(message) => {
@@ -271,3 +856,27 @@ impl IndyLedger {
         }
     }
 }
+
+/// Extracts `result.data` from a read reply. The ledger returns it either as an
+/// object or as a JSON encoded string, and `null` when nothing was found.
+pub fn parse_reply_data(reply: &str) -> VdrResult<Option<Value>> {
+    let reply: Value = serde_json::from_str(reply).map_err(|e| {
+        VdrError::new(
+            VdrErrorKind::Input,
+            Some(format!("Failed to parse ledger reply: {}", e)),
+            None,
+        )
+    })?;
+
+    match &reply["result"]["data"] {
+        Value::Null => Ok(None),
+        Value::String(data) => serde_json::from_str(data).map(Some).map_err(|e| {
+            VdrError::new(
+                VdrErrorKind::Input,
+                Some(format!("Failed to parse reply data: {}", e)),
+                None,
+            )
+        }),
+        data => Ok(Some(data.clone())),
+    }
+}
@@ -0,0 +1,412 @@
+use crate::helpers::genesis::GenesisSource;
+use crate::helpers::ledgers::IndyLedger;
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// DOMAIN ledger ID used by GET_TXN
+pub const DOMAIN_LEDGER: i32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorQuery {
+    Did,
+    SchemaName,
+    CredDefTag,
+    TxnType,
+}
+
+impl MirrorQuery {
+    pub const ALL: [MirrorQuery; 4] = [
+        MirrorQuery::Did,
+        MirrorQuery::SchemaName,
+        MirrorQuery::CredDefTag,
+        MirrorQuery::TxnType,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MirrorQuery::Did => "DID",
+            MirrorQuery::SchemaName => "Schema name",
+            MirrorQuery::CredDefTag => "Cred def tag",
+            MirrorQuery::TxnType => "Txn type",
+        }
+    }
+}
+
+/// Local copy of the DOMAIN ledger of a network, stored as one transaction per line
+/// and indexed in memory. Syncing resumes after the last stored seq_no.
+pub struct LedgerMirror {
+    location: String,
+    path: PathBuf,
+    txns: Vec<Value>,
+    by_did: HashMap<String, Vec<usize>>,
+    by_schema_name: HashMap<String, Vec<usize>>,
+    by_cred_def_tag: HashMap<String, Vec<usize>>,
+    by_type: HashMap<String, Vec<usize>>,
+}
+
+impl LedgerMirror {
+    /// Opens the mirror of a network in the application data directory
+    pub fn for_network(genesis_source: &GenesisSource) -> Result<Self> {
+        let dir = eframe::storage_dir("IndyForge")
+            .ok_or_else(|| anyhow!("No data directory available for the mirror"))?
+            .join("mirrors");
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let location = genesis_source.location();
+        let file_name = format!("{:x}.jsonl", Sha256::digest(location.as_bytes()));
+        let mut mirror = Self::open(&dir.join(file_name))?;
+        mirror.location = location.to_string();
+        Ok(mirror)
+    }
+
+    /// Loads a mirror file, a missing file being an empty mirror
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut mirror = Self {
+            location: String::new(),
+            path: path.to_path_buf(),
+            txns: Vec::new(),
+            by_did: HashMap::new(),
+            by_schema_name: HashMap::new(),
+            by_cred_def_tag: HashMap::new(),
+            by_type: HashMap::new(),
+        };
+        if !path.exists() {
+            return Ok(mirror);
+        }
+
+        let file =
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        for (line_number, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let txn: Value = serde_json::from_str(&line)
+                .with_context(|| format!("Corrupted mirror at line {}", line_number + 1))?;
+            mirror.push(txn)?;
+        }
+        Ok(mirror)
+    }
+
+    /// Genesis file path or URL of the mirrored network
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    pub fn len(&self) -> usize {
+        self.txns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.txns.is_empty()
+    }
+
+    pub fn last_seq_no(&self) -> u64 {
+        self.txns.len() as u64
+    }
+
+    pub fn get(&self, seq_no: u64) -> Option<&Value> {
+        seq_no
+            .checked_sub(1)
+            .and_then(|index| self.txns.get(index as usize))
+    }
+
+    /// Transactions `from..from + count` of the mirror
+    pub fn range(&self, from: u64, count: usize) -> Vec<Value> {
+        (from..from + count as u64)
+            .map_while(|seq_no| self.get(seq_no).cloned())
+            .collect()
+    }
+
+    /// Fetches up to `count` new transactions from the ledger, returns how many were added
+    pub async fn sync(&mut self, ledger: &IndyLedger, count: i32) -> Result<usize> {
+        let from = i32::try_from(self.last_seq_no() + 1).context("Mirror is too large")?;
+        let txns = ledger
+            .get_txn_range(DOMAIN_LEDGER, from, count)
+            .await
+            .context("Failed to fetch transactions")?
+            .data;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        let added = txns.len();
+        for txn in txns {
+            let line = serde_json::to_string(&txn)?;
+            self.push(txn)?;
+            writeln!(file, "{}", line)?;
+        }
+        Ok(added)
+    }
+
+    fn push(&mut self, txn: Value) -> Result<()> {
+        let expected = self.last_seq_no() + 1;
+        let seq_no = txn["txnMetadata"]["seqNo"].as_u64();
+        if seq_no != Some(expected) {
+            return Err(anyhow!(
+                "Expected transaction {} in the mirror, got {:?}",
+                expected,
+                seq_no
+            ));
+        }
+
+        let index = self.txns.len();
+        let body = &txn["txn"];
+        let data = &body["data"];
+        let add = |map: &mut HashMap<String, Vec<usize>>, key: Option<&str>| {
+            if let Some(key) = key.filter(|key| !key.is_empty()) {
+                let entries = map.entry(key.to_lowercase()).or_default();
+                if entries.last() != Some(&index) {
+                    entries.push(index);
+                }
+            }
+        };
+        add(&mut self.by_type, body["type"].as_str());
+        add(&mut self.by_did, body["metadata"]["from"].as_str());
+        add(&mut self.by_did, data["dest"].as_str());
+        match body["type"].as_str() {
+            Some("101") => add(&mut self.by_schema_name, data["data"]["name"].as_str()),
+            Some("102") => add(&mut self.by_cred_def_tag, data["tag"].as_str()),
+            _ => {}
+        }
+
+        self.txns.push(txn);
+        Ok(())
+    }
+
+    /// Exact, case insensitive lookup in one of the indexes
+    pub fn query(&self, query: MirrorQuery, value: &str) -> Vec<&Value> {
+        let index = match query {
+            MirrorQuery::Did => &self.by_did,
+            MirrorQuery::SchemaName => &self.by_schema_name,
+            MirrorQuery::CredDefTag => &self.by_cred_def_tag,
+            MirrorQuery::TxnType => &self.by_type,
+        };
+        index
+            .get(&value.trim().to_lowercase())
+            .into_iter()
+            .flatten()
+            .map(|index| &self.txns[*index])
+            .collect()
+    }
+
+    /// Transaction written from a request of `identifier` with this reqId, if any
+    pub fn find_request(&self, identifier: &str, req_id: u64) -> Option<&Value> {
+        self.query(MirrorQuery::Did, identifier)
+            .into_iter()
+            .find(|txn| {
+                let metadata = &txn["txn"]["metadata"];
+                metadata["from"].as_str() == Some(identifier)
+                    && metadata["reqId"].as_u64() == Some(req_id)
+            })
+    }
+
+    fn txns_of_type<'a>(
+        &'a self,
+        did: &'a str,
+        txn_type: &'a str,
+    ) -> impl Iterator<Item = &'a Value> + 'a {
+        self.query(MirrorQuery::Did, did)
+            .into_iter()
+            .filter(move |txn| {
+                txn["txn"]["type"].as_str() == Some(txn_type)
+                    && txn["txn"]["data"]["dest"].as_str() == Some(did)
+            })
+    }
+
+    /// Current NYM of a DID replayed from the mirror, in the shape of GET_NYM data
+    pub fn nym(&self, did: &str) -> Option<Value> {
+        let mut nym: Option<Value> = None;
+        for txn in self.txns_of_type(did, "1") {
+            let data = &txn["txn"]["data"];
+            let nym = nym.get_or_insert_with(|| {
+                json!({
+                    "dest": did,
+                    "identifier": txn["txn"]["metadata"]["from"],
+                    "role": null,
+                    "verkey": null,
+                })
+            });
+            for field in ["role", "verkey", "alias", "diddocContent", "version"] {
+                if let Some(value) = data.get(field) {
+                    nym[field] = value.clone();
+                }
+            }
+            nym["seqNo"] = txn["txnMetadata"]["seqNo"].clone();
+            nym["txnTime"] = txn["txnMetadata"]["txnTime"].clone();
+        }
+        nym
+    }
+
+    /// Latest raw ATTRIB of a DID holding `raw` (e.g. `endpoint`), as GET_ATTRIB returns it
+    pub fn attrib(&self, did: &str, raw: &str) -> Option<Value> {
+        self.txns_of_type(did, "100")
+            .filter_map(|txn| txn["txn"]["data"]["raw"].as_str())
+            .filter_map(|data| serde_json::from_str::<Value>(data).ok())
+            .filter(|data| data.get(raw).is_some())
+            .last()
+    }
+
+    /// Transactions of a type submitted by a DID, oldest first
+    pub fn written_by(&self, did: &str, txn_type: &str) -> Vec<&Value> {
+        self.query(MirrorQuery::Did, did)
+            .into_iter()
+            .filter(|txn| {
+                txn["txn"]["type"].as_str() == Some(txn_type)
+                    && txn["txn"]["metadata"]["from"].as_str() == Some(did)
+            })
+            .collect()
+    }
+
+    /// SCHEMA written by an issuer with this exact name and version
+    pub fn schema(&self, issuer_did: &str, name: &str, version: &str) -> Option<&Value> {
+        self.query(MirrorQuery::SchemaName, name)
+            .into_iter()
+            .find(|txn| {
+                let data = &txn["txn"]["data"]["data"];
+                txn["txn"]["type"].as_str() == Some("101")
+                    && txn["txn"]["metadata"]["from"].as_str() == Some(issuer_did)
+                    && data["name"].as_str() == Some(name)
+                    && data["version"].as_str() == Some(version)
+            })
+    }
+
+    /// CLAIM_DEF transactions referencing the schema written at `schema_seq_no`
+    pub fn cred_defs_for_schema(&self, schema_seq_no: u64) -> Vec<&Value> {
+        self.query(MirrorQuery::TxnType, "102")
+            .into_iter()
+            .filter(|txn| txn["txn"]["data"]["ref"].as_u64() == Some(schema_seq_no))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn txn(seq_no: u64, txn_type: &str, from: &str, data: Value) -> Value {
+        json!({
+            "txn": { "type": txn_type, "data": data, "metadata": { "from": from, "reqId": seq_no } },
+            "txnMetadata": { "seqNo": seq_no, "txnTime": 1700000000 + seq_no }
+        })
+    }
+
+    fn test_mirror(name: &str) -> (LedgerMirror, PathBuf) {
+        let path =
+            std::env::temp_dir().join(format!("indyforge-{}-{}.jsonl", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let txns = [
+            txn(
+                1,
+                "1",
+                "Trustee1111111111111111",
+                json!({ "dest": "GAAguaTbEHjvxL6i64YmAo", "verkey": "~abc", "role": "101" }),
+            ),
+            txn(
+                2,
+                "101",
+                "GAAguaTbEHjvxL6i64YmAo",
+                json!({ "data": { "name": "Degree", "version": "1.0", "attr_names": ["name"] } }),
+            ),
+            txn(
+                3,
+                "102",
+                "GAAguaTbEHjvxL6i64YmAo",
+                json!({ "ref": 2, "tag": "default", "signature_type": "CL" }),
+            ),
+            txn(
+                4,
+                "100",
+                "GAAguaTbEHjvxL6i64YmAo",
+                json!({ "dest": "GAAguaTbEHjvxL6i64YmAo", "raw": "{\"endpoint\":{\"endpoint\":\"https://agent.example.com\"}}" }),
+            ),
+            txn(
+                5,
+                "1",
+                "Trustee1111111111111111",
+                json!({ "dest": "GAAguaTbEHjvxL6i64YmAo", "role": null }),
+            ),
+        ];
+        let lines: Vec<String> = txns.iter().map(|txn| txn.to_string()).collect();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        (LedgerMirror::open(&path).unwrap(), path)
+    }
+
+    #[test]
+    fn test_mirror_indexes() {
+        let (mirror, path) = test_mirror("indexes");
+        assert_eq!(mirror.last_seq_no(), 5);
+        assert_eq!(
+            mirror
+                .query(MirrorQuery::Did, "GAAguaTbEHjvxL6i64YmAo")
+                .len(),
+            5
+        );
+        assert_eq!(mirror.query(MirrorQuery::SchemaName, "degree").len(), 1);
+        assert_eq!(mirror.query(MirrorQuery::CredDefTag, "default").len(), 1);
+        assert_eq!(mirror.query(MirrorQuery::TxnType, "1").len(), 2);
+        assert_eq!(mirror.range(4, 10).len(), 2);
+        assert_eq!(
+            mirror.find_request("GAAguaTbEHjvxL6i64YmAo", 3).unwrap()["txnMetadata"]["seqNo"],
+            3
+        );
+        assert!(mirror.find_request("GAAguaTbEHjvxL6i64YmAo", 5).is_none());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_mirror_schema_and_cred_defs() {
+        let (mirror, path) = test_mirror("schema");
+        let schema = mirror
+            .schema("GAAguaTbEHjvxL6i64YmAo", "Degree", "1.0")
+            .unwrap();
+        assert_eq!(schema["txnMetadata"]["seqNo"], 2);
+        assert!(mirror
+            .schema("GAAguaTbEHjvxL6i64YmAo", "Degree", "2.0")
+            .is_none());
+        assert!(mirror
+            .schema("Trustee1111111111111111", "Degree", "1.0")
+            .is_none());
+        assert_eq!(mirror.cred_defs_for_schema(2).len(), 1);
+        assert_eq!(mirror.written_by("GAAguaTbEHjvxL6i64YmAo", "101").len(), 1);
+        assert!(mirror
+            .written_by("Trustee1111111111111111", "101")
+            .is_empty());
+        assert!(mirror.cred_defs_for_schema(3).is_empty());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_mirror_nym_and_attrib() {
+        let (mirror, path) = test_mirror("nym");
+        let nym = mirror.nym("GAAguaTbEHjvxL6i64YmAo").unwrap();
+        assert_eq!(nym["verkey"], "~abc");
+        assert_eq!(nym["role"], Value::Null);
+        assert_eq!(nym["identifier"], "Trustee1111111111111111");
+        assert_eq!(nym["seqNo"], 5);
+        assert!(mirror.nym("V4SGRU86Z58d6TV7PBUe6f").is_none());
+
+        let endpoint = mirror.attrib("GAAguaTbEHjvxL6i64YmAo", "endpoint").unwrap();
+        assert_eq!(
+            endpoint["endpoint"]["endpoint"],
+            "https://agent.example.com"
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_mirror_rejects_gaps() {
+        let path = std::env::temp_dir().join(format!("indyforge-gap-{}.jsonl", std::process::id()));
+        std::fs::write(&path, txn(2, "1", "A", json!({})).to_string()).unwrap();
+        assert!(LedgerMirror::open(&path).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
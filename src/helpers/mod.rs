@@ -1,4 +1,23 @@
+pub mod airgap;
+pub mod auth_rules;
+pub mod did;
+pub mod diddoc;
+pub mod explorer;
+pub mod external_signer;
+pub mod fees;
 pub mod genesis;
+pub mod ledger_diff;
 pub mod ledgers;
+pub mod mirror;
+pub mod pool_admin;
+pub mod request_json;
+pub mod revocation;
+pub mod schema_lookup;
+pub mod seed;
+pub mod session;
+pub mod signer;
+pub mod state_proof;
+pub mod txn_types;
 pub mod wallet;
 pub mod workflow_guide;
+pub mod write_review;
@@ -0,0 +1,479 @@
+use crate::app::{NodeInfo, PoolAdminInfo};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, SecondsFormat, TimeDelta};
+use indy_vdr::ledger::requests::node::{NodeOperationData, Services};
+use serde_json::{Map, Value};
+use std::net::IpAddr;
+
+/// Size of a BLS public key once base58 decoded
+const BLS_KEY_LENGTH: usize = 128;
+
+pub const POOL_ACTIONS: [&str; 2] = ["start", "cancel"];
+
+/// A node of the pool as described by the NODE transactions of the genesis file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenesisNode {
+    pub alias: String,
+    pub dest: String,
+    pub steward: String,
+    pub node_ip: String,
+    pub node_port: u64,
+    pub client_ip: String,
+    pub client_port: u64,
+    pub services: Vec<String>,
+    pub blskey: String,
+    pub blskey_pop: String,
+}
+
+impl GenesisNode {
+    pub fn is_validator(&self) -> bool {
+        self.services.iter().any(|service| service == "VALIDATOR")
+    }
+}
+
+/// One line of a POOL_UPGRADE schedule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleEntry {
+    pub alias: String,
+    pub dest: String,
+    pub time: String,
+}
+
+/// Validated POOL_UPGRADE parameters
+#[derive(Debug)]
+pub struct PoolUpgrade {
+    pub name: String,
+    pub version: String,
+    pub action: String,
+    pub sha256: String,
+    pub timeout: Option<u32>,
+    pub schedule: Option<Value>,
+    pub justification: Option<String>,
+    pub reinstall: bool,
+    pub force: bool,
+    pub package: Option<String>,
+}
+
+/// Lists the nodes of the pool transactions. Later NODE transactions for the same
+/// `dest` update the earlier ones, as on the pool ledger.
+pub fn genesis_nodes(transactions: &[String]) -> Result<Vec<GenesisNode>> {
+    let mut nodes: Vec<GenesisNode> = Vec::new();
+    for transaction in transactions {
+        let transaction: Value =
+            serde_json::from_str(transaction).context("Invalid pool transaction")?;
+        let txn = &transaction["txn"];
+        if txn["type"].as_str() != Some("0") {
+            continue;
+        }
+        let Some(dest) = txn["data"]["dest"].as_str() else {
+            continue;
+        };
+        let data = &txn["data"]["data"];
+
+        let index = match nodes.iter().position(|node| node.dest == dest) {
+            Some(index) => index,
+            None => {
+                nodes.push(GenesisNode {
+                    dest: dest.to_string(),
+                    steward: txn["metadata"]["from"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    ..Default::default()
+                });
+                nodes.len() - 1
+            }
+        };
+        let node = &mut nodes[index];
+        let text = |field: &str, current: &mut String| {
+            if let Some(value) = data[field].as_str() {
+                *current = value.to_string();
+            }
+        };
+        text("alias", &mut node.alias);
+        text("node_ip", &mut node.node_ip);
+        text("client_ip", &mut node.client_ip);
+        text("blskey", &mut node.blskey);
+        text("blskey_pop", &mut node.blskey_pop);
+        if let Some(port) = data["node_port"].as_u64() {
+            node.node_port = port;
+        }
+        if let Some(port) = data["client_port"].as_u64() {
+            node.client_port = port;
+        }
+        if let Some(services) = data["services"].as_array() {
+            node.services = services
+                .iter()
+                .filter_map(|service| service.as_str().map(str::to_string))
+                .collect();
+        }
+    }
+    Ok(nodes)
+}
+
+/// Schedules the upgrade of each validator `interval_minutes` apart, starting at `start`
+/// (RFC 3339), so that the pool keeps its consensus while nodes restart.
+pub fn generate_schedule(
+    nodes: &[GenesisNode],
+    start: &str,
+    interval_minutes: &str,
+) -> Result<Vec<ScheduleEntry>> {
+    let start = DateTime::parse_from_rfc3339(start.trim())
+        .context("Start time must be RFC 3339, e.g. 2025-01-31T22:00:00+00:00")?;
+    let interval: i64 = interval_minutes
+        .trim()
+        .parse()
+        .context("Interval must be a number of minutes")?;
+    if interval < 0 {
+        return Err(anyhow!("Interval cannot be negative"));
+    }
+
+    nodes
+        .iter()
+        .filter(|node| node.is_validator())
+        .enumerate()
+        .map(|(index, node)| {
+            let time = interval
+                .checked_mul(index as i64)
+                .and_then(TimeDelta::try_minutes)
+                .and_then(|offset| start.checked_add_signed(offset))
+                .ok_or_else(|| anyhow!("Interval too large, the schedule is out of range"))?;
+            Ok(ScheduleEntry {
+                alias: node.alias.clone(),
+                dest: node.dest.clone(),
+                time: time.to_rfc3339_opts(SecondsFormat::Micros, false),
+            })
+        })
+        .collect()
+}
+
+/// Assembles the POOL_UPGRADE parameters from the form fields
+pub fn build_pool_upgrade(info: &PoolAdminInfo) -> Result<PoolUpgrade> {
+    let name = info.upgrade_name.trim();
+    if name.is_empty() {
+        return Err(anyhow!("Upgrade name is required"));
+    }
+    let version = info.upgrade_version.trim();
+    if version.is_empty()
+        || !version.starts_with(|c: char| c.is_ascii_digit())
+        || version.contains(char::is_whitespace)
+    {
+        return Err(anyhow!("Version must look like 1.12.6"));
+    }
+    if !POOL_ACTIONS.contains(&info.upgrade_action.as_str()) {
+        return Err(anyhow!("Action must be start or cancel"));
+    }
+    let sha256 = info.upgrade_sha256.trim().to_lowercase();
+    if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow!("SHA256 must be 64 hexadecimal characters"));
+    }
+    let timeout = match info.upgrade_timeout.trim() {
+        "" => None,
+        timeout => Some(
+            timeout
+                .parse()
+                .context("Timeout must be a number of minutes")?,
+        ),
+    };
+
+    let mut schedule = Map::new();
+    for entry in &info.schedule {
+        DateTime::parse_from_rfc3339(entry.time.trim())
+            .with_context(|| format!("Invalid time for {}", entry.alias))?;
+        schedule.insert(
+            entry.dest.clone(),
+            Value::String(entry.time.trim().to_string()),
+        );
+    }
+    if info.upgrade_action == "start" && schedule.is_empty() {
+        return Err(anyhow!("A schedule is required to start an upgrade"));
+    }
+
+    let optional = |value: &str| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+    Ok(PoolUpgrade {
+        name: name.to_string(),
+        version: version.to_string(),
+        action: info.upgrade_action.clone(),
+        sha256,
+        timeout,
+        schedule: (!schedule.is_empty()).then_some(Value::Object(schedule)),
+        justification: optional(&info.upgrade_justification),
+        reinstall: info.upgrade_reinstall,
+        force: info.upgrade_force,
+        package: optional(&info.upgrade_package),
+    })
+}
+
+/// POOL_RESTART datetime: `"0"` restarts immediately, otherwise an RFC 3339 time
+pub fn restart_datetime(datetime: &str) -> Result<String> {
+    match datetime.trim() {
+        "" | "0" => Ok("0".to_string()),
+        datetime => {
+            DateTime::parse_from_rfc3339(datetime)
+                .context("Restart time must be RFC 3339, e.g. 2025-01-31T22:00:00+00:00")?;
+            Ok(datetime.to_string())
+        }
+    }
+}
+
+/// Fills the NODE form with the current state of a node from the genesis file
+pub fn prefill_node_info(info: &mut NodeInfo, node: &GenesisNode) {
+    info.dest = node.dest.clone();
+    info.alias = node.alias.clone();
+    info.node_ip = node.node_ip.clone();
+    info.node_port = node.node_port.to_string();
+    info.client_ip = node.client_ip.clone();
+    info.client_port = node.client_port.to_string();
+    info.validator = node.is_validator();
+    info.blskey = node.blskey.clone();
+    info.blskey_pop = node.blskey_pop.clone();
+    info.existing = Some(node.clone());
+}
+
+fn validate_ip(label: &str, ip: &str) -> Result<String> {
+    ip.trim()
+        .parse::<IpAddr>()
+        .with_context(|| format!("{} is not a valid IP address", label))?;
+    Ok(ip.trim().to_string())
+}
+
+fn validate_port(label: &str, port: &str) -> Result<i32> {
+    match port.trim().parse::<u16>() {
+        Ok(port) if port > 0 => Ok(port as i32),
+        _ => Err(anyhow!("{} must be between 1 and 65535", label)),
+    }
+}
+
+fn validate_bls_key(blskey: &str, blskey_pop: &str) -> Result<(String, String)> {
+    let decoded = bs58::decode(blskey.trim())
+        .into_vec()
+        .context("BLS key is not valid base58")?;
+    if decoded.len() != BLS_KEY_LENGTH {
+        return Err(anyhow!(
+            "BLS key must be {} bytes, got {}",
+            BLS_KEY_LENGTH,
+            decoded.len()
+        ));
+    }
+    if blskey_pop.trim().is_empty() {
+        return Err(anyhow!("The BLS key proof of possession is required"));
+    }
+    bs58::decode(blskey_pop.trim())
+        .into_vec()
+        .context("BLS key proof of possession is not valid base58")?;
+    Ok((blskey.trim().to_string(), blskey_pop.trim().to_string()))
+}
+
+/// Assembles the NODE operation from the form. When updating a node from the genesis
+/// file, only the fields that changed are sent, addresses and BLS keys going in pairs.
+pub fn build_node_data(info: &NodeInfo) -> Result<(String, NodeOperationData)> {
+    let dest = info.dest.trim();
+    let decoded = bs58::decode(dest)
+        .into_vec()
+        .context("Node DID is not valid base58")?;
+    if decoded.len() != 32 {
+        return Err(anyhow!("Node DID must be the 32 bytes verkey of the node"));
+    }
+    let alias = info.alias.trim();
+    if alias.is_empty() {
+        return Err(anyhow!("Alias is required"));
+    }
+
+    let node_ip = validate_ip("Node IP", &info.node_ip)?;
+    let node_port = validate_port("Node port", &info.node_port)?;
+    let client_ip = validate_ip("Client IP", &info.client_ip)?;
+    let client_port = validate_port("Client port", &info.client_port)?;
+    if node_ip == client_ip && node_port == client_port {
+        return Err(anyhow!("Node and client ports must differ on the same IP"));
+    }
+    // A demoted node leaves consensus, its BLS key is neither needed nor sent
+    let bls = if info.validator {
+        Some(validate_bls_key(&info.blskey, &info.blskey_pop)?)
+    } else {
+        None
+    };
+    let services = if info.validator {
+        vec![Services::VALIDATOR]
+    } else {
+        Vec::new()
+    };
+
+    let existing = info.existing.as_ref().filter(|node| node.dest == dest);
+    let node_changed = existing.map_or(true, |node| {
+        node.node_ip != node_ip || node.node_port != node_port as u64
+    });
+    let client_changed = existing.map_or(true, |node| {
+        node.client_ip != client_ip || node.client_port != client_port as u64
+    });
+    let bls = bls.filter(|(blskey, blskey_pop)| {
+        existing.map_or(true, |node| {
+            &node.blskey != blskey || &node.blskey_pop != blskey_pop
+        })
+    });
+
+    let data = NodeOperationData {
+        node_ip: node_changed.then_some(node_ip),
+        node_port: node_changed.then_some(node_port),
+        client_ip: client_changed.then_some(client_ip),
+        client_port: client_changed.then_some(client_port),
+        alias: alias.to_string(),
+        services: Some(services),
+        blskey: bls.as_ref().map(|(blskey, _)| blskey.clone()),
+        blskey_pop: bls.map(|(_, blskey_pop)| blskey_pop),
+    };
+    Ok((dest.to_string(), data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node_txn(dest: &str, data: &str) -> String {
+        format!(
+            r#"{{"txn":{{"type":"0","data":{{"dest":"{}","data":{}}},"metadata":{{"from":"Th7MpTaRZVRYnPiabds81Y"}}}}}}"#,
+            dest, data
+        )
+    }
+
+    #[test]
+    fn test_genesis_nodes_and_schedule() {
+        let transactions = vec![
+            node_txn(
+                "Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv",
+                r#"{"alias":"Node1","node_ip":"10.0.0.2","node_port":9701,"client_ip":"10.0.0.2","client_port":9702,"services":["VALIDATOR"]}"#,
+            ),
+            node_txn(
+                "8ECVSk179mjsjKRLWiQtssMLgp6EPhWXtaYyStWPSGAb",
+                r#"{"alias":"Node2","node_ip":"10.0.0.3","node_port":9703,"client_ip":"10.0.0.3","client_port":9704,"services":["VALIDATOR"]}"#,
+            ),
+            node_txn(
+                "8ECVSk179mjsjKRLWiQtssMLgp6EPhWXtaYyStWPSGAb",
+                r#"{"alias":"Node2","services":[]}"#,
+            ),
+            r#"{"txn":{"type":"1","data":{"dest":"Th7MpTaRZVRYnPiabds81Y"}}}"#.to_string(),
+        ];
+
+        let nodes = genesis_nodes(&transactions).unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1].node_ip, "10.0.0.3");
+        assert!(!nodes[1].is_validator());
+
+        let schedule = generate_schedule(&nodes, "2025-01-31T22:00:00+00:00", "5").unwrap();
+        assert_eq!(schedule.len(), 1);
+        assert_eq!(schedule[0].alias, "Node1");
+        assert_eq!(schedule[0].time, "2025-01-31T22:00:00.000000+00:00");
+
+        assert!(generate_schedule(&nodes, "tomorrow", "5").is_err());
+
+        let validators = vec![nodes[0].clone(); 3];
+        let schedule = generate_schedule(&validators, "2025-01-31T22:00:00+00:00", "5").unwrap();
+        assert_eq!(schedule[1].time, "2025-01-31T22:05:00.000000+00:00");
+        for interval in ["9223372036854775807", "153722867280912930", "5000000000000"] {
+            assert!(generate_schedule(&validators, "2025-01-31T22:00:00+00:00", interval).is_err());
+        }
+    }
+
+    #[test]
+    fn test_build_pool_upgrade() {
+        let mut info = PoolAdminInfo {
+            upgrade_name: "upgrade-1126".to_string(),
+            upgrade_version: "1.12.6".to_string(),
+            upgrade_sha256: "a".repeat(64),
+            ..Default::default()
+        };
+        assert!(build_pool_upgrade(&info).is_err(), "schedule is required");
+
+        info.schedule.push(ScheduleEntry {
+            alias: "Node1".to_string(),
+            dest: "Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv".to_string(),
+            time: "2025-01-31T22:00:00+00:00".to_string(),
+        });
+        let upgrade = build_pool_upgrade(&info).unwrap();
+        assert_eq!(upgrade.timeout, Some(10));
+        assert_eq!(upgrade.package.as_deref(), Some("indy-node"));
+        assert!(
+            upgrade.schedule.unwrap()["Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv"].is_string()
+        );
+
+        info.upgrade_sha256 = "not-a-hash".to_string();
+        assert!(build_pool_upgrade(&info).is_err());
+    }
+
+    #[test]
+    fn test_restart_datetime() {
+        assert_eq!(restart_datetime("").unwrap(), "0");
+        assert!(restart_datetime("2025-01-31T22:00:00+00:00").is_ok());
+        assert!(restart_datetime("tonight").is_err());
+    }
+
+    fn node_info() -> NodeInfo {
+        NodeInfo {
+            dest: "Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv".to_string(),
+            alias: "Node5".to_string(),
+            node_ip: "10.0.0.6".to_string(),
+            node_port: "9709".to_string(),
+            client_ip: "10.0.0.6".to_string(),
+            client_port: "9710".to_string(),
+            validator: true,
+            blskey: bs58::encode([7u8; BLS_KEY_LENGTH]).into_string(),
+            blskey_pop: bs58::encode([9u8; 64]).into_string(),
+            existing: None,
+            genesis_nodes: None,
+        }
+    }
+
+    #[test]
+    fn test_build_node_data() {
+        let mut info = node_info();
+        let (dest, data) = build_node_data(&info).unwrap();
+        assert_eq!(dest, "Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv");
+        assert_eq!(data.node_port, Some(9709));
+        assert_eq!(data.services, Some(vec![Services::VALIDATOR]));
+
+        info.node_ip = "10.0.0".to_string();
+        assert!(build_node_data(&info).is_err());
+        info.node_ip = "10.0.0.6".to_string();
+        info.client_port = "70000".to_string();
+        assert!(build_node_data(&info).is_err());
+        info.client_port = "9710".to_string();
+        info.blskey = bs58::encode([7u8; 32]).into_string();
+        assert!(build_node_data(&info).is_err());
+    }
+
+    #[test]
+    fn test_node_update_sends_changes_only() {
+        let mut info = node_info();
+        let node = GenesisNode {
+            alias: "Node5".to_string(),
+            dest: info.dest.clone(),
+            steward: "Th7MpTaRZVRYnPiabds81Y".to_string(),
+            node_ip: "10.0.0.6".to_string(),
+            node_port: 9709,
+            client_ip: "10.0.0.6".to_string(),
+            client_port: 9710,
+            services: vec!["VALIDATOR".to_string()],
+            blskey: info.blskey.clone(),
+            blskey_pop: info.blskey_pop.clone(),
+        };
+        prefill_node_info(&mut info, &node);
+        info.validator = false;
+        info.client_ip = "10.0.0.7".to_string();
+
+        let (_, data) = build_node_data(&info).unwrap();
+        assert_eq!(data.services, Some(Vec::new()));
+        assert_eq!(data.node_ip, None);
+        assert_eq!(data.client_ip.as_deref(), Some("10.0.0.7"));
+        assert_eq!(data.client_port, Some(9710));
+        assert_eq!(data.blskey, None);
+
+        // Demoting does not need a valid BLS key
+        info.blskey.clear();
+        info.blskey_pop.clear();
+        let (_, data) = build_node_data(&info).unwrap();
+        assert_eq!(data.services, Some(Vec::new()));
+        assert_eq!(data.blskey, None);
+        assert_eq!(data.blskey_pop, None);
+        info.validator = true;
+        assert!(build_node_data(&info).is_err());
+    }
+}
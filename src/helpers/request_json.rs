@@ -0,0 +1,466 @@
+use crate::helpers::txn_types::txn_type_label;
+use anyhow::{anyhow, Context, Result};
+use egui::text::LayoutJob;
+use egui::{Color32, FontId, TextFormat};
+use serde_json::Value;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A signed request older than this is most likely a replay of an old transaction
+const STALE_REQ_ID: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Operation fields the ledger requires for each request type
+const OPERATION_FIELDS: &[(&str, &[&str])] = &[
+    ("0", &["dest", "data"]),
+    ("1", &["dest"]),
+    ("3", &["ledgerId", "data"]),
+    ("4", &["version"]),
+    ("5", &["version", "aml", "amlContext"]),
+    ("100", &["dest"]),
+    ("101", &["data"]),
+    ("102", &["ref", "signature_type", "tag", "data"]),
+    ("104", &["dest"]),
+    ("105", &["dest"]),
+    ("107", &["dest", "data"]),
+    ("108", &["ref", "signature_type", "origin"]),
+    ("109", &["name", "version", "action", "sha256"]),
+    ("111", &["writes", "force"]),
+    ("113", &["id", "credDefId", "revocDefType", "tag", "value"]),
+    ("114", &["revocRegDefId", "revocDefType", "value"]),
+    ("118", &["action"]),
+    (
+        "120",
+        &[
+            "auth_type",
+            "auth_action",
+            "field",
+            "new_value",
+            "constraint",
+        ],
+    ),
+    ("121", &[]),
+    ("122", &["rules"]),
+];
+
+/// A problem found in a request before it is prepared, warnings do not block it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestIssue {
+    pub message: String,
+    pub warning: bool,
+}
+
+impl RequestIssue {
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            warning: false,
+        }
+    }
+
+    fn warning(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            warning: true,
+        }
+    }
+}
+
+/// Checks a request JSON against the shape indy-vdr and the ledger expect
+pub fn validate_request(text: &str) -> Vec<RequestIssue> {
+    let request: Value = match serde_json::from_str(text) {
+        Ok(request) => request,
+        Err(e) => {
+            return vec![RequestIssue::error(format!(
+                "Invalid JSON at line {}, column {}: {}",
+                e.line(),
+                e.column(),
+                e
+            ))]
+        }
+    };
+    let Some(request) = request.as_object() else {
+        return vec![RequestIssue::error("The request must be a JSON object")];
+    };
+
+    let mut issues = Vec::new();
+    match request.get("identifier").and_then(Value::as_str) {
+        Some(identifier) => match bs58::decode(identifier).into_vec() {
+            Ok(bytes) if bytes.len() == 16 || bytes.len() == 32 => {}
+            _ => issues.push(RequestIssue::error(
+                "identifier must be a base58 DID (16 bytes) or verkey (32 bytes)",
+            )),
+        },
+        None => issues.push(RequestIssue::error("identifier is missing or not a string")),
+    }
+    if !request.get("reqId").is_some_and(Value::is_u64) {
+        issues.push(RequestIssue::error(
+            "reqId is missing or not a positive integer",
+        ));
+    }
+    match request.get("protocolVersion").and_then(Value::as_u64) {
+        Some(1) | Some(2) => {}
+        Some(version) => issues.push(RequestIssue::error(format!(
+            "protocolVersion {} is not supported, use 2 (or 1 for Node 1.3)",
+            version
+        ))),
+        None => issues.push(RequestIssue::error(
+            "protocolVersion is missing or not a number",
+        )),
+    }
+
+    let Some(operation) = request.get("operation").and_then(Value::as_object) else {
+        issues.push(RequestIssue::error("operation is missing or not an object"));
+        return issues;
+    };
+    let Some(txn_type) = operation.get("type").and_then(Value::as_str) else {
+        issues.push(RequestIssue::error(
+            "operation.type is missing or not a string (e.g. \"1\" for NYM)",
+        ));
+        return issues;
+    };
+
+    match OPERATION_FIELDS.iter().find(|(code, _)| *code == txn_type) {
+        Some((_, fields)) => {
+            for field in fields.iter() {
+                if operation.get(*field).map_or(true, Value::is_null) {
+                    issues.push(RequestIssue::error(format!(
+                        "operation.{} is required for {}",
+                        field,
+                        txn_type_label(txn_type)
+                    )));
+                }
+            }
+        }
+        None => issues.push(RequestIssue::warning(format!(
+            "Unknown operation type {}, its fields are not checked",
+            txn_type_label(txn_type)
+        ))),
+    }
+    if txn_type == "100"
+        && !["raw", "hash", "enc"]
+            .iter()
+            .any(|field| operation.contains_key(*field))
+    {
+        issues.push(RequestIssue::error(
+            "An ATTRIB needs one of operation.raw, operation.hash or operation.enc",
+        ));
+    }
+    if txn_type == "101" {
+        for field in ["name", "version", "attr_names"] {
+            if operation
+                .get("data")
+                .and_then(|data| data.get(field))
+                .is_none()
+            {
+                issues.push(RequestIssue::error(format!(
+                    "operation.data.{} is required for a SCHEMA",
+                    field
+                )));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Re-formats a JSON text, indented or on a single line
+pub fn format_json(text: &str, pretty: bool) -> Result<String> {
+    let value: Value = serde_json::from_str(text).context("Invalid JSON")?;
+    Ok(if pretty {
+        serde_json::to_string_pretty(&value)?
+    } else {
+        serde_json::to_string(&value)?
+    })
+}
+
+/// A fresh reqId, nanoseconds since the epoch like indy-vdr generates them
+pub fn new_req_id() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+/// Whether a request already carries a signature, changing it would invalidate them
+pub fn is_signed(request: &Value) -> bool {
+    ["signature", "signatures"]
+        .iter()
+        .any(|field| request.get(*field).is_some_and(|value| !value.is_null()))
+}
+
+/// (DID, base58 signature) pairs of a request, from `signature` and `signatures`
+pub fn request_signatures(request: &Value) -> Vec<(String, String)> {
+    let mut signatures = Vec::new();
+    if let (Some(did), Some(signature)) = (
+        request["identifier"].as_str(),
+        request["signature"].as_str(),
+    ) {
+        signatures.push((did.to_string(), signature.to_string()));
+    }
+    if let Some(multi) = request["signatures"].as_object() {
+        for (did, signature) in multi {
+            if let Some(signature) = signature.as_str() {
+                signatures.push((did.clone(), signature.to_string()));
+            }
+        }
+    }
+    signatures
+}
+
+/// Sets a new reqId and the protocol version on a request that has no signature yet
+pub fn refresh_request(text: &str, protocol_version: i64) -> Result<String> {
+    let mut request: Value = serde_json::from_str(text).context("Invalid JSON")?;
+    if !request.is_object() {
+        return Err(anyhow!("The request must be a JSON object"));
+    }
+    if is_signed(&request) {
+        return Err(anyhow!(
+            "The request is already signed, a new reqId would invalidate its signatures"
+        ));
+    }
+    request["reqId"] = new_req_id().into();
+    request["protocolVersion"] = protocol_version.into();
+    Ok(serde_json::to_string_pretty(&request)?)
+}
+
+/// Time a reqId was created at, guessing its unit from its magnitude. SDKs use
+/// nanoseconds, microseconds or milliseconds since the epoch.
+pub fn req_id_time(req_id: u64) -> Option<SystemTime> {
+    let since_epoch = match req_id {
+        id if id >= 100_000_000_000_000_000 => Duration::from_nanos(id),
+        id if id >= 100_000_000_000_000 => Duration::from_micros(id),
+        id if id >= 100_000_000_000 => Duration::from_millis(id),
+        _ => return None,
+    };
+    UNIX_EPOCH.checked_add(since_epoch)
+}
+
+/// Warnings for a signed request whose reqId may be rejected as a replay
+pub fn freshness_warnings(text: &str, now: SystemTime) -> Vec<RequestIssue> {
+    let Ok(request) = serde_json::from_str::<Value>(text) else {
+        return Vec::new();
+    };
+    if !is_signed(&request) {
+        return Vec::new();
+    }
+    let Some(req_id) = request["reqId"].as_u64() else {
+        return Vec::new();
+    };
+
+    match req_id_time(req_id) {
+        Some(created) => match now.duration_since(created) {
+            Ok(age) if age > STALE_REQ_ID => vec![RequestIssue::warning(format!(
+                "The request is signed and its reqId is {} hours old, it may be a replay the ledger rejects as a duplicate",
+                age.as_secs() / 3600
+            ))],
+            Ok(_) => Vec::new(),
+            Err(_) => vec![RequestIssue::warning(
+                "The reqId of this signed request is in the future",
+            )],
+        },
+        None => vec![RequestIssue::warning(
+            "The reqId of this signed request is not a timestamp, its age is unknown",
+        )],
+    }
+}
+
+const KEY_COLOR: Color32 = Color32::from_rgb(156, 220, 254);
+const STRING_COLOR: Color32 = Color32::from_rgb(206, 145, 120);
+const NUMBER_COLOR: Color32 = Color32::from_rgb(181, 206, 168);
+const KEYWORD_COLOR: Color32 = Color32::from_rgb(86, 156, 214);
+const PUNCTUATION_COLOR: Color32 = Color32::GRAY;
+
+/// Colors the tokens of a JSON text for a code editor layouter. Every byte of the
+/// text ends up in a section, so invalid JSON is highlighted as far as it goes.
+pub fn highlight_json(text: &str) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    let font = FontId::monospace(13.0);
+    let mut rest = text;
+    while let Some(first) = rest.chars().next() {
+        let (len, color) = if first == '"' {
+            let len = string_len(rest);
+            if rest[len..].trim_start().starts_with(':') {
+                (len, KEY_COLOR)
+            } else {
+                (len, STRING_COLOR)
+            }
+        } else if first == '-' || first.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+                .unwrap_or(rest.len());
+            (len, NUMBER_COLOR)
+        } else if first.is_alphabetic() {
+            let len = rest
+                .find(|c: char| !c.is_alphanumeric())
+                .unwrap_or(rest.len());
+            (len, KEYWORD_COLOR)
+        } else if first.is_whitespace() {
+            let len = rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len());
+            (len, PUNCTUATION_COLOR)
+        } else {
+            (first.len_utf8(), PUNCTUATION_COLOR)
+        };
+        job.append(&rest[..len], 0.0, TextFormat::simple(font.clone(), color));
+        rest = &rest[len..];
+    }
+    job
+}
+
+/// Length of the string token at the start of `text`, up to the end when unterminated
+fn string_len(text: &str) -> usize {
+    let mut escaped = false;
+    for (index, c) in text.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            return index + 1;
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn errors(request: &Value) -> Vec<String> {
+        validate_request(&request.to_string())
+            .into_iter()
+            .filter(|issue| !issue.warning)
+            .map(|issue| issue.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_request() {
+        let mut request = json!({
+            "identifier": "V4SGRU86Z58d6TV7PBUe6f",
+            "reqId": 1700000000000u64,
+            "protocolVersion": 2,
+            "operation": { "type": "1", "dest": "GAAguaTbEHjvxL6i64YmAo", "role": "101" }
+        });
+        assert!(validate_request(&request.to_string()).is_empty());
+
+        request["operation"] = json!({ "type": "101", "data": { "name": "degree" } });
+        assert_eq!(
+            errors(&request),
+            vec![
+                "operation.data.version is required for a SCHEMA",
+                "operation.data.attr_names is required for a SCHEMA"
+            ]
+        );
+
+        request["operation"] = json!({ "type": "101" });
+        let found = errors(&request);
+        assert_eq!(found.len(), 4);
+        assert_eq!(found[0], "operation.data is required for SCHEMA (101)");
+
+        request["operation"] = json!({ "type": "102", "ref": 10, "tag": "default" });
+        request["reqId"] = json!("1");
+        request["protocolVersion"] = json!(3);
+        let found = errors(&request);
+        assert_eq!(found.len(), 4);
+        assert!(found[0].starts_with("reqId"));
+        assert!(found[1].starts_with("protocolVersion 3"));
+        assert_eq!(
+            found[2],
+            "operation.signature_type is required for CLAIM_DEF (102)"
+        );
+
+        request = json!({
+            "identifier": "not base58 0OIl",
+            "reqId": 1,
+            "protocolVersion": 2,
+            "operation": { "type": "99999" }
+        });
+        let issues = validate_request(&request.to_string());
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.starts_with("identifier"));
+        assert!(issues[1].warning);
+
+        let issues = validate_request("{\n  \"reqId\": 1,\n}");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.starts_with("Invalid JSON at line 3"));
+    }
+
+    #[test]
+    fn test_format_and_highlight_json() {
+        let pretty = format_json(r#"{"a":[1,true,"x"]}"#, true).unwrap();
+        assert_eq!(
+            pretty,
+            "{\n  \"a\": [\n    1,\n    true,\n    \"x\"\n  ]\n}"
+        );
+        assert_eq!(
+            format_json(&pretty, false).unwrap(),
+            r#"{"a":[1,true,"x"]}"#
+        );
+        assert!(format_json("{", true).is_err());
+
+        let text = r#"{"key": "va\"lue", "n": -1.5e3, "ok": null, "open": "unterminated"#;
+        let job = highlight_json(text);
+        assert_eq!(job.text, text);
+        let color_of = |token: &str| {
+            let start = text.find(token).unwrap();
+            job.sections
+                .iter()
+                .find(|section| section.byte_range.start == start)
+                .map(|section| section.format.color)
+        };
+        assert_eq!(color_of("\"key\""), Some(KEY_COLOR));
+        assert_eq!(color_of("\"va"), Some(STRING_COLOR));
+        assert_eq!(color_of("-1.5e3"), Some(NUMBER_COLOR));
+        assert_eq!(color_of("null"), Some(KEYWORD_COLOR));
+        assert_eq!(job.sections.last().unwrap().byte_range.end, text.len());
+    }
+
+    #[test]
+    fn test_refresh_request() {
+        let unsigned = json!({
+            "identifier": "V4SGRU86Z58d6TV7PBUe6f",
+            "reqId": 1,
+            "protocolVersion": 1,
+            "operation": { "type": "1", "dest": "GAAguaTbEHjvxL6i64YmAo" }
+        });
+        let refreshed: Value =
+            serde_json::from_str(&refresh_request(&unsigned.to_string(), 2).unwrap()).unwrap();
+        assert_eq!(refreshed["protocolVersion"], 2);
+        assert!(refreshed["reqId"].as_u64().unwrap() > 1);
+        assert_eq!(refreshed["operation"], unsigned["operation"]);
+
+        let mut signed = unsigned.clone();
+        signed["signatures"] = json!({ "V4SGRU86Z58d6TV7PBUe6f": "sig" });
+        assert!(refresh_request(&signed.to_string(), 2).is_err());
+    }
+
+    #[test]
+    fn test_freshness_warnings() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let request = |req_id: u64, signed: bool| {
+            let mut request = json!({ "identifier": "V4SGRU86Z58d6TV7PBUe6f", "reqId": req_id });
+            if signed {
+                request["signature"] = json!("sig");
+            }
+            request.to_string()
+        };
+
+        // An hour old in nanoseconds, two days old in milliseconds
+        let recent = 1_699_996_400_000_000_000;
+        let stale = 1_699_827_200_000;
+        assert_eq!(
+            req_id_time(stale),
+            Some(now - Duration::from_secs(48 * 3600))
+        );
+        assert!(freshness_warnings(&request(recent, true), now).is_empty());
+        assert!(freshness_warnings(&request(stale, false), now).is_empty());
+
+        let warnings = freshness_warnings(&request(stale, true), now);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("48 hours old"));
+        assert!(freshness_warnings(&request(42, true), now)[0]
+            .message
+            .contains("not a timestamp"));
+    }
+}
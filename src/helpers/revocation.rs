@@ -0,0 +1,269 @@
+use crate::app::{RevocRegDefInfo, RevocRegEntryInfo};
+use crate::helpers::did::legacy_object_id;
+use anyhow::{anyhow, Context, Result};
+use indy_data_types::anoncreds::rev_reg::RevocationRegistryDelta;
+use indy_data_types::anoncreds::rev_reg_def::RevocationRegistryDefinition;
+use indy_data_types::did::DidValue;
+use indy_data_types::{CredentialDefinitionId, RevocationRegistryId};
+use serde_json::json;
+use std::collections::BTreeSet;
+
+pub const REVOC_DEF_TYPE: &str = "CL_ACCUM";
+
+pub const ISSUANCE_TYPES: [&str; 2] = ["ISSUANCE_BY_DEFAULT", "ISSUANCE_ON_DEMAND"];
+
+/// Indices in one REVOC_REG_ENTRY, a larger update exceeds the ledger's 128 KiB request limit
+pub const MAX_INDICES_PER_ENTRY: usize = 10_000;
+
+/// Builds the revocation registry ID the same way issuers do: `<did>:4:<cred_def_id>:CL_ACCUM:<tag>`
+pub fn revoc_reg_id(issuer_did: &str, cred_def_id: &str, tag: &str) -> RevocationRegistryId {
+    RevocationRegistryId::new(
+        &DidValue(issuer_did.to_string()),
+        &CredentialDefinitionId(cred_def_id.to_string()),
+        REVOC_DEF_TYPE,
+        tag,
+    )
+}
+
+/// Assembles a REVOC_REG_DEF from the form fields.
+/// The accumulator public key comes from the issuer agent that generated the tails file.
+pub fn build_revoc_reg_def(
+    issuer_did: &str,
+    info: &RevocRegDefInfo,
+) -> Result<(RevocationRegistryId, RevocationRegistryDefinition)> {
+    if info.cred_def_id.trim().is_empty() {
+        return Err(anyhow!("Credential definition ID is required"));
+    }
+    let cred_def_id = legacy_object_id(&info.cred_def_id)?;
+    if info.tag.trim().is_empty() {
+        return Err(anyhow!("Tag is required"));
+    }
+    let max_cred_num = parse_max_cred_num(&info.max_cred_num)?;
+    if info.tails_hash.trim().is_empty() || info.tails_location.trim().is_empty() {
+        return Err(anyhow!("Tails hash and tails location are required"));
+    }
+    bs58::decode(info.tails_hash.trim())
+        .into_vec()
+        .context("Tails hash must be base58 encoded")?;
+    if info.accum_key.trim().is_empty() {
+        return Err(anyhow!("Accumulator public key is required"));
+    }
+
+    let id = revoc_reg_id(issuer_did, &cred_def_id, info.tag.trim());
+    let revoc_reg_def = json!({
+        "ver": "1.0",
+        "id": id.0.clone(),
+        "revocDefType": REVOC_DEF_TYPE,
+        "tag": info.tag.trim(),
+        "credDefId": cred_def_id,
+        "value": {
+            "issuanceType": info.issuance_type,
+            "maxCredNum": max_cred_num,
+            "publicKeys": {
+                "accumKey": {
+                    "z": info.accum_key.trim()
+                }
+            },
+            "tailsHash": info.tails_hash.trim(),
+            "tailsLocation": info.tails_location.trim(),
+        }
+    });
+
+    let revoc_reg_def =
+        serde_json::from_value(revoc_reg_def).context("Invalid revocation registry definition")?;
+
+    Ok((id, revoc_reg_def))
+}
+
+/// Assembles a REVOC_REG_ENTRY accumulator update from the form fields
+pub fn build_revoc_reg_entry(
+    info: &RevocRegEntryInfo,
+) -> Result<(RevocationRegistryId, RevocationRegistryDelta)> {
+    let revoc_reg_def_id = legacy_object_id(&info.revoc_reg_def_id)?;
+    if revoc_reg_def_id.split(':').nth(1) != Some("4") {
+        return Err(anyhow!(
+            "Revocation registry ID must have the form <did>:4:<cred_def_id>:CL_ACCUM:<tag>"
+        ));
+    }
+    if info.accum.trim().is_empty() {
+        return Err(anyhow!("Accumulator value is required"));
+    }
+    let max_cred_num = parse_max_cred_num(&info.max_cred_num)?;
+    let issued = parse_indices(&info.issued, max_cred_num).context("Issued indices")?;
+    let revoked = parse_indices(&info.revoked, max_cred_num).context("Revoked indices")?;
+    // The ledger rejects an entry issuing and revoking the same credential
+    let overlap: Vec<String> = issued
+        .iter()
+        .filter(|index| revoked.binary_search(index).is_ok())
+        .map(u32::to_string)
+        .collect();
+    if !overlap.is_empty() {
+        return Err(anyhow!(
+            "Indices both issued and revoked: {}",
+            overlap.join(", ")
+        ));
+    }
+
+    let mut value = json!({
+        "accum": info.accum.trim(),
+        "issued": issued,
+        "revoked": revoked,
+    });
+    if !info.prev_accum.trim().is_empty() {
+        value["prevAccum"] = json!(info.prev_accum.trim());
+    }
+
+    let delta = serde_json::from_value(json!({ "ver": "1.0", "value": value }))
+        .context("Invalid revocation registry entry")?;
+
+    Ok((RevocationRegistryId(revoc_reg_def_id), delta))
+}
+
+fn parse_max_cred_num(input: &str) -> Result<u32> {
+    let max_cred_num: u32 = input
+        .trim()
+        .parse()
+        .context("Max credential number must be a positive integer")?;
+    if max_cred_num == 0 {
+        return Err(anyhow!("Max credential number must be greater than 0"));
+    }
+    Ok(max_cred_num)
+}
+
+/// Parses a list of credential indices such as `1, 4, 7-9`, all within the registry's
+/// `max_cred_num`. Repeated indices count once towards `MAX_INDICES_PER_ENTRY`, a
+/// range is expanded only until the limit is reached.
+pub fn parse_indices(input: &str, max_cred_num: u32) -> Result<Vec<u32>> {
+    let mut indices = BTreeSet::new();
+    for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => {
+                let start: u32 = start
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Invalid index range: {}", part))?;
+                let end: u32 = end
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Invalid index range: {}", part))?;
+                if start > end {
+                    return Err(anyhow!("Invalid index range: {}", part));
+                }
+                (start, end)
+            }
+            None => {
+                let index: u32 = part
+                    .parse()
+                    .with_context(|| format!("Invalid index: {}", part))?;
+                (index, index)
+            }
+        };
+        if start == 0 {
+            return Err(anyhow!("Credential indices start at 1"));
+        }
+        if end > max_cred_num {
+            return Err(anyhow!(
+                "Index {} is above the max credential number {}",
+                end,
+                max_cred_num
+            ));
+        }
+        for index in start..=end {
+            indices.insert(index);
+            if indices.len() > MAX_INDICES_PER_ENTRY {
+                return Err(anyhow!(
+                    "More than {} indices, split the update into several entries",
+                    MAX_INDICES_PER_ENTRY
+                ));
+            }
+        }
+    }
+    Ok(indices.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_indices() {
+        assert_eq!(parse_indices("", 10).unwrap(), Vec::<u32>::new());
+        assert_eq!(parse_indices("3, 1,2", 10).unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_indices("1, 4-6, 5", 10).unwrap(), vec![1, 4, 5, 6]);
+        assert!(parse_indices("0", 10).is_err());
+        assert!(parse_indices("6-4", 10).is_err());
+        assert!(parse_indices("a", 10).is_err());
+        assert!(parse_indices("11", 10).is_err());
+        assert!(parse_indices("5-11", 10).is_err());
+        assert!(parse_indices("1-4000000000", u32::MAX).is_err());
+        assert_eq!(
+            parse_indices("1-10000", u32::MAX).unwrap().len(),
+            MAX_INDICES_PER_ENTRY
+        );
+        assert!(parse_indices("1-10000, 20000", u32::MAX).is_err());
+        // Duplicates are removed before the limit is checked
+        assert_eq!(
+            parse_indices("1-10000, 1-10000, 5000", u32::MAX)
+                .unwrap()
+                .len(),
+            MAX_INDICES_PER_ENTRY
+        );
+    }
+
+    #[test]
+    fn test_revoc_reg_id() {
+        let id = revoc_reg_id(
+            "V4SGRU86Z58d6TV7PBUe6f",
+            "V4SGRU86Z58d6TV7PBUe6f:3:CL:12:default",
+            "tag1",
+        );
+        assert_eq!(
+            id.0,
+            "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:12:default:CL_ACCUM:tag1"
+        );
+    }
+
+    #[test]
+    fn test_build_revoc_reg_def_requires_accum_key() {
+        let info = RevocRegDefInfo {
+            cred_def_id: "V4SGRU86Z58d6TV7PBUe6f:3:CL:12:default".to_string(),
+            tag: "tag1".to_string(),
+            max_cred_num: "100".to_string(),
+            tails_hash: "2Hw4YkhWjmKcHg5oM1ufWrEDFaUCJrTbUW4VNWtgmzzF".to_string(),
+            tails_location: "https://tails.example.com/1".to_string(),
+            ..Default::default()
+        };
+        let error = build_revoc_reg_def("V4SGRU86Z58d6TV7PBUe6f", &info).unwrap_err();
+        assert_eq!(error.to_string(), "Accumulator public key is required");
+    }
+
+    #[test]
+    fn test_build_revoc_reg_entry_rejects_bad_id() {
+        let info = RevocRegEntryInfo {
+            revoc_reg_def_id: "V4SGRU86Z58d6TV7PBUe6f:3:CL:12:default".to_string(),
+            accum: "1 0 1".to_string(),
+            max_cred_num: "100".to_string(),
+            ..Default::default()
+        };
+        assert!(build_revoc_reg_entry(&info).is_err());
+    }
+
+    #[test]
+    fn test_build_revoc_reg_entry_rejects_overlap() {
+        let mut info = RevocRegEntryInfo {
+            revoc_reg_def_id:
+                "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:12:default:CL_ACCUM:tag1"
+                    .to_string(),
+            accum: "1 0 1".to_string(),
+            max_cred_num: "100".to_string(),
+            issued: "1-5".to_string(),
+            revoked: "7, 3, 5".to_string(),
+            ..Default::default()
+        };
+        let error = build_revoc_reg_entry(&info).unwrap_err();
+        assert_eq!(error.to_string(), "Indices both issued and revoked: 3, 5");
+
+        info.revoked = "7, 8".to_string();
+        assert!(build_revoc_reg_entry(&info).is_ok());
+    }
+}
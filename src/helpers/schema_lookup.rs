@@ -0,0 +1,189 @@
+use crate::helpers::did::unqualified_did;
+use crate::helpers::ledgers::IndyLedger;
+use crate::helpers::mirror::DOMAIN_LEDGER;
+use anyhow::{anyhow, Context, Result};
+use indy_data_types::did::DidValue;
+use indy_data_types::SchemaId;
+use serde_json::Value;
+
+/// Schema ID of an issuer's schema, built like the Publish Tool builds it
+pub fn lookup_schema_id(issuer_did: &str, name: &str, version: &str) -> Result<SchemaId> {
+    let issuer = unqualified_did(issuer_did.trim()).context("Invalid issuer DID")?;
+    let (name, version) = (name.trim(), version.trim());
+    if name.is_empty() || version.is_empty() {
+        return Err(anyhow!("Schema name and version are required"));
+    }
+    if name.contains(':') || version.contains(':') {
+        return Err(anyhow!("Schema name and version cannot contain ':'"));
+    }
+    Ok(SchemaId::new(&DidValue(issuer), name, version))
+}
+
+/// A schema written on the ledger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundSchema {
+    pub id: String,
+    pub attr_names: Vec<String>,
+    pub seq_no: u64,
+    pub txn_time: Option<u64>,
+}
+
+impl FoundSchema {
+    /// Reads a GET_SCHEMA reply, `None` when the ledger has no such schema
+    pub fn from_reply(id: &SchemaId, reply: &str) -> Result<Option<Self>> {
+        let reply: Value = serde_json::from_str(reply).context("Invalid GET_SCHEMA reply")?;
+        let result = &reply["result"];
+        if result.is_null() {
+            return Err(anyhow!(
+                "GET_SCHEMA failed: {}",
+                reply["reason"].as_str().unwrap_or("no result in the reply")
+            ));
+        }
+        let Some(seq_no) = result["seqNo"].as_u64() else {
+            return Ok(None);
+        };
+        let data = match &result["data"] {
+            Value::String(text) => serde_json::from_str(text).unwrap_or(Value::Null),
+            data => data.clone(),
+        };
+        Ok(Some(Self {
+            id: id.0.clone(),
+            attr_names: attr_names(&data),
+            seq_no,
+            txn_time: result["txnTime"].as_u64(),
+        }))
+    }
+
+    /// Reads a SCHEMA transaction, as stored by the mirror
+    pub fn from_txn(id: &SchemaId, txn: &Value) -> Option<Self> {
+        Some(Self {
+            id: id.0.clone(),
+            attr_names: attr_names(&txn["txn"]["data"]["data"]),
+            seq_no: txn["txnMetadata"]["seqNo"].as_u64()?,
+            txn_time: txn["txnMetadata"]["txnTime"].as_u64(),
+        })
+    }
+}
+
+fn attr_names(data: &Value) -> Vec<String> {
+    data["attr_names"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|name| name.as_str().map(str::to_string))
+        .collect()
+}
+
+/// A credential definition referencing a schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CredDefSummary {
+    pub id: String,
+    pub issuer: String,
+    pub tag: String,
+    pub seq_no: Option<u64>,
+    pub txn_time: Option<u64>,
+}
+
+impl CredDefSummary {
+    /// Reads a CLAIM_DEF transaction, `None` unless it references `schema_seq_no`
+    pub fn from_txn(txn: &Value, schema_seq_no: u64) -> Option<Self> {
+        let body = &txn["txn"];
+        if body["type"].as_str() != Some("102")
+            || body["data"]["ref"].as_u64() != Some(schema_seq_no)
+        {
+            return None;
+        }
+        let issuer = body["metadata"]["from"].as_str()?.to_string();
+        let tag = body["data"]["tag"].as_str().unwrap_or_default().to_string();
+        let signature_type = body["data"]["signature_type"].as_str().unwrap_or("CL");
+        Some(Self {
+            id: format!("{}:3:{}:{}:{}", issuer, signature_type, schema_seq_no, tag),
+            issuer,
+            tag,
+            seq_no: txn["txnMetadata"]["seqNo"].as_u64(),
+            txn_time: txn["txnMetadata"]["txnTime"].as_u64(),
+        })
+    }
+}
+
+/// Reads `count` DOMAIN transactions from `from` looking for cred defs of the schema.
+/// Returns them with the seq_no to resume from, `None` once the end of the ledger is reached.
+pub async fn scan_cred_defs(
+    ledger: &IndyLedger,
+    schema_seq_no: u64,
+    from: u64,
+    count: i32,
+) -> Result<(Vec<CredDefSummary>, Option<u64>)> {
+    let start = i32::try_from(from).context("Sequence number out of range")?;
+    let txns = ledger
+        .get_txn_range(DOMAIN_LEDGER, start, count)
+        .await
+        .context("Failed to fetch transactions")?
+        .data;
+    let next = (txns.len() == count as usize).then_some(from + txns.len() as u64);
+    Ok((
+        txns.iter()
+            .filter_map(|txn| CredDefSummary::from_txn(txn, schema_seq_no))
+            .collect(),
+        next,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_lookup_schema_id() {
+        let id = lookup_schema_id("did:sov:GAAguaTbEHjvxL6i64YmAo", " Degree ", "1.0").unwrap();
+        assert_eq!(id.0, "GAAguaTbEHjvxL6i64YmAo:2:Degree:1.0");
+        assert!(lookup_schema_id("GAAguaTbEHjvxL6i64YmAo", "", "1.0").is_err());
+        assert!(lookup_schema_id("GAAguaTbEHjvxL6i64YmAo", "a:b", "1.0").is_err());
+        assert!(lookup_schema_id("not a did", "Degree", "1.0").is_err());
+    }
+
+    #[test]
+    fn test_found_schema_from_reply() {
+        let id = lookup_schema_id("GAAguaTbEHjvxL6i64YmAo", "Degree", "1.0").unwrap();
+        let reply = json!({"op": "REPLY", "result": {
+            "type": "107",
+            "dest": "GAAguaTbEHjvxL6i64YmAo",
+            "data": {"name": "Degree", "version": "1.0", "attr_names": ["name", "score"]},
+            "seqNo": 42,
+            "txnTime": 1700000000
+        }});
+        let schema = FoundSchema::from_reply(&id, &reply.to_string())
+            .unwrap()
+            .unwrap();
+        assert_eq!(schema.seq_no, 42);
+        assert_eq!(schema.attr_names, vec!["name", "score"]);
+
+        let missing = json!({"op": "REPLY", "result": {
+            "type": "107",
+            "data": {"name": "Degree", "version": "1.0"},
+            "seqNo": null
+        }});
+        assert!(FoundSchema::from_reply(&id, &missing.to_string())
+            .unwrap()
+            .is_none());
+        let nack = json!({"op": "REQNACK", "reason": "client request invalid"});
+        assert!(FoundSchema::from_reply(&id, &nack.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_cred_def_from_txn() {
+        let txn = json!({
+            "txn": {
+                "type": "102",
+                "data": {"ref": 42, "tag": "default", "signature_type": "CL"},
+                "metadata": {"from": "GAAguaTbEHjvxL6i64YmAo"}
+            },
+            "txnMetadata": {"seqNo": 43, "txnTime": 1700000100}
+        });
+        let cred_def = CredDefSummary::from_txn(&txn, 42).unwrap();
+        assert_eq!(cred_def.id, "GAAguaTbEHjvxL6i64YmAo:3:CL:42:default");
+        assert_eq!(cred_def.seq_no, Some(43));
+        assert!(CredDefSummary::from_txn(&txn, 41).is_none());
+    }
+}
@@ -0,0 +1,328 @@
+use anyhow::{anyhow, Context, Result};
+use aries_askar::kms::{KeyAlg, LocalKey};
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+use bip39::{Language, Mnemonic};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Ed25519 keys are derived from 32 bytes
+pub const SEED_LENGTH: usize = 32;
+
+/// Words of a BIP-39 mnemonic carrying 32 bytes of entropy
+pub const MNEMONIC_WORDS: usize = 24;
+
+/// Encodings accepted for a key seed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeedFormat {
+    /// 32 characters used as-is, the historical Indy seed
+    #[default]
+    Raw,
+    Hex,
+    Base64,
+    /// 32 bytes seed, or 64 bytes secret key (seed followed by the verkey)
+    Base58,
+    /// 24 BIP-39 English words, the seed is the mnemonic entropy
+    Mnemonic,
+}
+
+impl SeedFormat {
+    pub const ALL: [SeedFormat; 5] = [
+        SeedFormat::Raw,
+        SeedFormat::Hex,
+        SeedFormat::Base64,
+        SeedFormat::Base58,
+        SeedFormat::Mnemonic,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SeedFormat::Raw => "32 characters",
+            SeedFormat::Hex => "Hex",
+            SeedFormat::Base64 => "Base64",
+            SeedFormat::Base58 => "Base58 private key",
+            SeedFormat::Mnemonic => "BIP-39 mnemonic",
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            SeedFormat::Raw => "Enter 32 bytes seed",
+            SeedFormat::Hex => "64 hex digits, optional 0x prefix",
+            SeedFormat::Base64 => "44 characters, standard or URL-safe alphabet",
+            SeedFormat::Base58 => "32 bytes seed or 64 bytes secret key",
+            SeedFormat::Mnemonic => "24 words separated by spaces",
+        }
+    }
+}
+
+/// A secret typed in the UI. The text is wiped when it is cleared or dropped.
+#[derive(Default)]
+pub struct SeedInput {
+    pub format: SeedFormat,
+    pub text: Zeroizing<String>,
+    /// Show the secret instead of masking it
+    pub reveal: bool,
+}
+
+impl SeedInput {
+    pub fn decode(&self) -> Result<Zeroizing<[u8; SEED_LENGTH]>> {
+        decode_seed(self.format, &self.text)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Wipes the secret, e.g. once the key was derived from it
+    pub fn clear(&mut self) {
+        self.text.zeroize();
+        self.reveal = false;
+    }
+}
+
+/// Decodes a seed typed in the given format, with an error telling what is wrong with it
+pub fn decode_seed(format: SeedFormat, text: &str) -> Result<Zeroizing<[u8; SEED_LENGTH]>> {
+    let bytes = match format {
+        SeedFormat::Raw => {
+            if !text.is_ascii() {
+                return Err(anyhow!(
+                    "Non-ASCII characters take several bytes, use 32 ASCII characters or another format"
+                ));
+            }
+            Zeroizing::new(text.as_bytes().to_vec())
+        }
+        SeedFormat::Hex => decode_hex(text.trim())?,
+        SeedFormat::Base64 => decode_base64(text.trim())?,
+        SeedFormat::Base58 => return decode_base58(text.trim()),
+        SeedFormat::Mnemonic => decode_mnemonic(text)?,
+    };
+    to_seed(&bytes)
+}
+
+fn to_seed(bytes: &[u8]) -> Result<Zeroizing<[u8; SEED_LENGTH]>> {
+    if bytes.len() != SEED_LENGTH {
+        return Err(anyhow!(
+            "{} bytes expected, got {}",
+            SEED_LENGTH,
+            bytes.len()
+        ));
+    }
+    let mut seed = Zeroizing::new([0u8; SEED_LENGTH]);
+    seed.copy_from_slice(bytes);
+    Ok(seed)
+}
+
+fn decode_hex(text: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let digits = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    if let Some((position, character)) = digits
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !c.is_ascii_hexdigit())
+    {
+        return Err(anyhow!(
+            "'{}' at position {} is not a hex digit",
+            character,
+            position + 1
+        ));
+    }
+    if digits.len() != SEED_LENGTH * 2 {
+        return Err(anyhow!(
+            "{} hex digits expected, got {}",
+            SEED_LENGTH * 2,
+            digits.len()
+        ));
+    }
+    Ok(Zeroizing::new(
+        (0..digits.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&digits[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()?,
+    ))
+}
+
+fn decode_base64(text: &str) -> Result<Zeroizing<Vec<u8>>> {
+    for engine in [&STANDARD, &STANDARD_NO_PAD, &URL_SAFE, &URL_SAFE_NO_PAD] {
+        if let Ok(bytes) = engine.decode(text) {
+            return Ok(Zeroizing::new(bytes));
+        }
+    }
+    let error = STANDARD.decode(text).err().map(|e| e.to_string());
+    Err(anyhow!(
+        "Invalid base64: {}",
+        error.unwrap_or_else(|| "unknown error".to_string())
+    ))
+}
+
+fn decode_base58(text: &str) -> Result<Zeroizing<[u8; SEED_LENGTH]>> {
+    let bytes = Zeroizing::new(
+        bs58::decode(text)
+            .into_vec()
+            .map_err(|e| anyhow!("Invalid base58: {}", e))?,
+    );
+    match bytes.len() {
+        SEED_LENGTH => to_seed(&bytes),
+        len if len == SEED_LENGTH * 2 => {
+            // libsodium secret keys end with the public key, check it belongs to the seed
+            let seed = to_seed(&bytes[..SEED_LENGTH])?;
+            let public = LocalKey::from_secret_bytes(KeyAlg::Ed25519, seed.as_ref())
+                .context("Failed to derive the public key")?
+                .to_public_bytes()
+                .context("Failed to derive the public key")?;
+            if public.as_ref() != &bytes[SEED_LENGTH..] {
+                return Err(anyhow!(
+                    "The last 32 bytes of the secret key are not the public key of its seed"
+                ));
+            }
+            Ok(seed)
+        }
+        len => Err(anyhow!(
+            "{} or {} bytes expected, got {}",
+            SEED_LENGTH,
+            SEED_LENGTH * 2,
+            len
+        )),
+    }
+}
+
+fn decode_mnemonic(text: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let words = Zeroizing::new(
+        text.split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    let count = words.split(' ').filter(|word| !word.is_empty()).count();
+    if count != MNEMONIC_WORDS {
+        return Err(anyhow!(
+            "{} words expected, got {}. Shorter mnemonics do not carry 32 bytes",
+            MNEMONIC_WORDS,
+            count
+        ));
+    }
+
+    let mnemonic =
+        Mnemonic::parse_in_normalized(Language::English, &words).map_err(|e| match e {
+            bip39::Error::UnknownWord(index) => anyhow!(
+                "Word {} ({}) is not in the BIP-39 English word list",
+                index + 1,
+                words.split(' ').nth(index).unwrap_or_default()
+            ),
+            bip39::Error::InvalidChecksum => {
+                anyhow!("Checksum mismatch, a word is wrong or out of order")
+            }
+            e => anyhow!("Invalid mnemonic: {}", e),
+        })?;
+    let (mut entropy, len) = mnemonic.to_entropy_array();
+    let bytes = Zeroizing::new(entropy[..len].to_vec());
+    entropy.zeroize();
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAW: &str = "000000000000000000000000Trustee1";
+
+    #[test]
+    fn test_decode_seed_formats() {
+        let expected = RAW.as_bytes();
+        let hex: String = expected.iter().map(|b| format!("{:02x}", b)).collect();
+        let base64 = STANDARD.encode(expected);
+        let base58 = bs58::encode(expected).into_string();
+
+        assert_eq!(
+            decode_seed(SeedFormat::Raw, RAW).unwrap().as_ref(),
+            expected
+        );
+        assert_eq!(
+            decode_seed(SeedFormat::Hex, &hex).unwrap().as_ref(),
+            expected
+        );
+        assert_eq!(
+            decode_seed(SeedFormat::Hex, &format!("0x{}", hex.to_uppercase()))
+                .unwrap()
+                .as_ref(),
+            expected
+        );
+        assert_eq!(
+            decode_seed(SeedFormat::Base64, &base64).unwrap().as_ref(),
+            expected
+        );
+        assert_eq!(
+            decode_seed(SeedFormat::Base64, base64.trim_end_matches('='))
+                .unwrap()
+                .as_ref(),
+            expected
+        );
+        assert_eq!(
+            decode_seed(SeedFormat::Base58, &base58).unwrap().as_ref(),
+            expected
+        );
+
+        let mnemonic = Mnemonic::from_entropy(expected).unwrap().to_string();
+        assert_eq!(
+            decode_seed(
+                SeedFormat::Mnemonic,
+                &format!("  {}\n", mnemonic.to_uppercase())
+            )
+            .unwrap()
+            .as_ref(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_decode_base58_secret_key() {
+        let key = LocalKey::from_secret_bytes(KeyAlg::Ed25519, RAW.as_bytes()).unwrap();
+        let mut secret = RAW.as_bytes().to_vec();
+        secret.extend_from_slice(&key.to_public_bytes().unwrap());
+        let seed = decode_seed(SeedFormat::Base58, &bs58::encode(&secret).into_string()).unwrap();
+        assert_eq!(seed.as_ref(), RAW.as_bytes());
+
+        secret[40] ^= 1;
+        assert!(decode_seed(SeedFormat::Base58, &bs58::encode(&secret).into_string()).is_err());
+    }
+
+    #[test]
+    fn test_decode_seed_errors() {
+        let error = |format, text: &str| decode_seed(format, text).unwrap_err().to_string();
+
+        assert_eq!(error(SeedFormat::Raw, "short"), "32 bytes expected, got 5");
+        assert!(error(SeedFormat::Raw, "é0000000000000000000000Trustee1").contains("Non-ASCII"));
+        assert_eq!(
+            error(SeedFormat::Hex, "00zz"),
+            "'z' at position 3 is not a hex digit"
+        );
+        assert_eq!(
+            error(SeedFormat::Hex, "00ff"),
+            "64 hex digits expected, got 4"
+        );
+        assert!(error(SeedFormat::Base64, "not base64!").starts_with("Invalid base64"));
+        assert!(error(SeedFormat::Base58, "0OIl").starts_with("Invalid base58"));
+
+        let mnemonic = Mnemonic::from_entropy(RAW.as_bytes()).unwrap().to_string();
+        let twelve: Vec<&str> = mnemonic.split(' ').take(12).collect();
+        assert!(
+            error(SeedFormat::Mnemonic, &twelve.join(" ")).starts_with("24 words expected, got 12")
+        );
+        let unknown = mnemonic.replacen(mnemonic.split(' ').next().unwrap(), "indyforge", 1);
+        assert!(error(SeedFormat::Mnemonic, &unknown).starts_with("Word 1 (indyforge)"));
+    }
+
+    #[test]
+    fn test_seed_input_clear() {
+        let mut input = SeedInput {
+            text: Zeroizing::new(RAW.to_string()),
+            reveal: true,
+            ..Default::default()
+        };
+        assert!(input.decode().is_ok());
+        input.clear();
+        assert!(input.is_empty());
+        assert!(!input.reveal);
+    }
+}
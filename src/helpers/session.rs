@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Inactivity timer of the session, secrets are dropped when it expires
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionLock {
+    /// Minutes without input before locking, 0 disables the auto-lock
+    pub timeout_minutes: u64,
+    #[serde(skip, default = "Instant::now")]
+    last_activity: Instant,
+}
+
+impl Default for SessionLock {
+    fn default() -> Self {
+        Self {
+            timeout_minutes: 10,
+            last_activity: Instant::now(),
+        }
+    }
+}
+
+impl SessionLock {
+    /// Records user input
+    pub fn touch(&mut self, now: Instant) {
+        self.last_activity = now;
+    }
+
+    /// Time left before the auto-lock, `None` when it is disabled
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        if self.timeout_minutes == 0 {
+            return None;
+        }
+        let timeout = Duration::from_secs(self.timeout_minutes * 60);
+        Some(timeout.saturating_sub(now.saturating_duration_since(self.last_activity)))
+    }
+
+    pub fn expired(&self, now: Instant) -> bool {
+        self.remaining(now)
+            .is_some_and(|remaining| remaining.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_lock_expiry() {
+        let start = Instant::now();
+        let mut lock = SessionLock {
+            timeout_minutes: 5,
+            last_activity: start,
+        };
+        assert_eq!(lock.remaining(start), Some(Duration::from_secs(300)));
+        assert!(!lock.expired(start + Duration::from_secs(299)));
+        assert!(lock.expired(start + Duration::from_secs(300)));
+
+        lock.touch(start + Duration::from_secs(200));
+        assert!(!lock.expired(start + Duration::from_secs(300)));
+
+        lock.timeout_minutes = 0;
+        assert_eq!(lock.remaining(start + Duration::from_secs(3600)), None);
+        assert!(!lock.expired(start + Duration::from_secs(3600)));
+    }
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use indy_vdr::common::error::{VdrError, VdrErrorKind, VdrResult};
+
+/// A DID with the key that signs its requests. Implemented by the wallet (askar key)
+/// and by `DidInfo` (indy-data-types key), other key backends plug in the same way.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Unqualified DID, used as `identifier` and as the multi-signature key
+    fn did(&self) -> &str;
+
+    /// Full base58 verkey of the DID
+    fn verkey(&self) -> &str;
+
+    /// Ed25519 signature of the message
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Signs with any signer, reporting a failure as a ledger request error
+pub async fn sign_request_bytes(signer: &dyn Signer, message: &[u8]) -> VdrResult<Vec<u8>> {
+    signer.sign(message).await.map_err(|e| {
+        VdrError::new(
+            VdrErrorKind::Input,
+            Some(format!("Signing with {} failed: {:#}", signer.did(), e)),
+            None,
+        )
+    })
+}
@@ -0,0 +1,136 @@
+use egui::{Color32, Ui};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Whether a read reply was backed by a BLS multi-signed state proof.
+///
+/// indy-vdr checks the state proof of each replying node and records the outcome per
+/// node alias in the request metadata. A reply it accepted without a verified proof
+/// comes from a consensus of nodes instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofStatus {
+    Verified { node: String },
+    Unverified { reason: String },
+}
+
+impl ProofStatus {
+    pub fn from_reply(reply: &str, node_results: &HashMap<String, Value>) -> Self {
+        if let Some(node) = node_results
+            .iter()
+            .find(|(_, result)| is_verified(result))
+            .map(|(node, _)| node.clone())
+        {
+            return ProofStatus::Verified { node };
+        }
+
+        let mut failures: Vec<String> = node_results
+            .iter()
+            .map(|(node, result)| match result {
+                Value::String(result) => format!("{}: {}", node, result),
+                result => format!("{}: {}", node, result),
+            })
+            .collect();
+        failures.sort();
+
+        let has_proof = serde_json::from_str::<Value>(reply)
+            .map(|reply| !reply["result"]["state_proof"]["multi_signature"].is_null())
+            .unwrap_or(false);
+        let reason = match (has_proof, failures.is_empty()) {
+            (_, false) => format!("state proof rejected ({})", failures.join(", ")),
+            (true, true) => "state proof present but not checked".to_string(),
+            (false, true) => "no state proof, accepted on node consensus".to_string(),
+        };
+        ProofStatus::Unverified { reason }
+    }
+
+    /// Status of several replies, verified only when every reply was
+    pub fn combine(statuses: impl IntoIterator<Item = ProofStatus>) -> Option<Self> {
+        let mut combined = None;
+        for status in statuses {
+            combined = match (combined, status) {
+                (Some(ProofStatus::Unverified { reason }), _) => {
+                    Some(ProofStatus::Unverified { reason })
+                }
+                (_, status) => Some(status),
+            };
+        }
+        combined
+    }
+
+    pub fn is_verified(&self) -> bool {
+        matches!(self, ProofStatus::Verified { .. })
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            ProofStatus::Verified { node } => {
+                format!("🔒 State proof verified (multi-signature, via {})", node)
+            }
+            ProofStatus::Unverified { reason } => format!("⚠ Not verified: {}", reason),
+        }
+    }
+
+    pub fn show(&self, ui: &mut Ui) {
+        let color = if self.is_verified() {
+            Color32::LIGHT_GREEN
+        } else {
+            Color32::YELLOW
+        };
+        ui.colored_label(color, self.label());
+    }
+}
+
+fn is_verified(result: &Value) -> bool {
+    match result {
+        Value::String(result) => result.eq_ignore_ascii_case("verified"),
+        Value::Object(result) => result.contains_key("Verified"),
+        Value::Bool(verified) => *verified,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_proof_status() {
+        let reply = json!({
+            "op": "REPLY",
+            "result": { "data": null, "state_proof": { "multi_signature": { "signature": "abc" } } }
+        })
+        .to_string();
+
+        let verified = HashMap::from([
+            ("Node1".to_string(), json!("Missing")),
+            ("Node2".to_string(), json!("Verified")),
+        ]);
+        assert_eq!(
+            ProofStatus::from_reply(&reply, &verified),
+            ProofStatus::Verified {
+                node: "Node2".to_string()
+            }
+        );
+
+        let rejected = HashMap::from([("Node1".to_string(), json!("Expired"))]);
+        assert_eq!(
+            ProofStatus::from_reply(&reply, &rejected),
+            ProofStatus::Unverified {
+                reason: "state proof rejected (Node1: Expired)".to_string()
+            }
+        );
+
+        let status = ProofStatus::from_reply(r#"{"result":{"data":null}}"#, &HashMap::new());
+        assert!(!status.is_verified());
+        assert!(status.label().contains("consensus"));
+
+        let combined = ProofStatus::combine([
+            ProofStatus::from_reply(&reply, &verified),
+            status.clone(),
+            ProofStatus::from_reply(&reply, &verified),
+        ]);
+        assert_eq!(combined, Some(status));
+        assert_eq!(ProofStatus::combine([]), None);
+    }
+}
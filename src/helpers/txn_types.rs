@@ -0,0 +1,39 @@
+/// Ledger transaction types, as they appear in `operation.type` and in auth rules
+pub const TXN_TYPES: &[(&str, &str)] = &[
+    ("0", "NODE"),
+    ("1", "NYM"),
+    ("4", "TXN_AUTHOR_AGREEMENT"),
+    ("5", "TXN_AUTHOR_AGREEMENT_AML"),
+    ("8", "DISABLE_ALL_TXN_AUTHR_AGRMTS"),
+    ("100", "ATTRIB"),
+    ("101", "SCHEMA"),
+    ("102", "CLAIM_DEF"),
+    ("109", "POOL_UPGRADE"),
+    ("110", "NODE_UPGRADE"),
+    ("111", "POOL_CONFIG"),
+    ("113", "REVOC_REG_DEF"),
+    ("114", "REVOC_REG_ENTRY"),
+    ("118", "POOL_RESTART"),
+    ("119", "VALIDATOR_INFO"),
+    ("120", "AUTH_RULE"),
+    ("122", "AUTH_RULES"),
+    ("200", "SET_CONTEXT"),
+    ("201", "SET_RICH_SCHEMA"),
+    ("20000", "SET_FEES"),
+];
+
+/// Name of a transaction type code, e.g. `SCHEMA` for `101`
+pub fn txn_type_name(code: &str) -> Option<&'static str> {
+    TXN_TYPES
+        .iter()
+        .find(|(txn_code, _)| *txn_code == code)
+        .map(|(_, name)| *name)
+}
+
+/// Name of a transaction type code, falling back to the code itself
+pub fn txn_type_label(code: &str) -> String {
+    match txn_type_name(code) {
+        Some(name) => format!("{} ({})", name, code),
+        None => code.to_string(),
+    }
+}
@@ -1,5 +1,7 @@
+use crate::helpers::signer::Signer;
 use anyhow::{anyhow, Context, Result};
 use aries_askar::kms::{KeyAlg, LocalKey};
+use async_trait::async_trait;
 use sha2::{Digest, Sha256};
 
 const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
@@ -11,36 +13,39 @@ pub struct IndyWallet {
 }
 
 impl IndyWallet {
-    pub async fn new(seed: Option<&str>, did_version_value: usize) -> anyhow::Result<IndyWallet> {
+    pub async fn new(seed: Option<&[u8]>, did_version_value: usize) -> anyhow::Result<IndyWallet> {
         let (did, key, verkey) = IndyWallet::create_did(seed, Some(did_version_value))
             .await
             .context("Failed to create DID")?;
         Ok(IndyWallet { did, verkey, key })
     }
 
-    pub async fn sign(&self, bytes: &[u8]) -> Vec<u8> {
-        self.key
-            .sign_message(bytes, None)
-            .expect("Signing operation failed")
+    /// Drops the signing key, keeping the DID and verkey to check the secret given to unlock
+    pub fn lock(self) -> LockedWallet {
+        LockedWallet {
+            did: self.did,
+            verkey: self.verkey,
+        }
     }
 
-    pub async fn create_did(
-        seed: Option<&str>,
-        version: Option<usize>,
-    ) -> anyhow::Result<(String, LocalKey, String)> {
+    /// Replaces the signing key after the ledger accepted a key rotation for this DID
+    pub fn replace_key(&mut self, key: LocalKey, verkey: String) {
+        self.key = key;
+        self.verkey = verkey;
+    }
+
+    /// Creates an Ed25519 key from a 32 bytes seed, or a random key, with its base58 verkey
+    pub fn create_key(seed: Option<&[u8]>) -> anyhow::Result<(LocalKey, String)> {
         let key = match seed {
-            Some(seed) => LocalKey::from_secret_bytes(KeyAlg::Ed25519, seed.as_bytes())
+            Some(seed) => LocalKey::from_secret_bytes(KeyAlg::Ed25519, seed)
                 .context("Failed to create key from seed")?,
             None => LocalKey::generate(KeyAlg::Ed25519, false)
                 .context("Failed to generate random key")?,
         };
 
-        // Get public key bytes
         let verkey_bytes = key
             .to_public_bytes()
             .context("Failed to get public key bytes")?;
-
-        // Verify public key length
         if verkey_bytes.len() != ED25519_PUBLIC_KEY_LENGTH {
             return Err(anyhow!(
                 "Invalid public key length: expected {}, got {}",
@@ -49,8 +54,37 @@ impl IndyWallet {
             ));
         }
 
-        // Generate verkey (base58 of public key)
-        let verkey = bs58::encode(verkey_bytes.as_ref()).into_string();
+        Ok((key, bs58::encode(verkey_bytes.as_ref()).into_string()))
+    }
+
+    /// Generates a random 32 characters seed, compatible with the seed input of the tools
+    pub fn generate_seed() -> anyhow::Result<String> {
+        const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+        // Bytes from 248 up would favour the first characters of the charset, draw again
+        const LIMIT: u8 = (256 / CHARSET.len() * CHARSET.len()) as u8;
+        let mut seed = String::with_capacity(32);
+        let mut bytes = [0u8; 32];
+        while seed.len() < 32 {
+            getrandom::getrandom(&mut bytes).context("Failed to generate random seed")?;
+            seed.extend(
+                bytes
+                    .iter()
+                    .filter(|b| **b < LIMIT)
+                    .map(|b| CHARSET[*b as usize % CHARSET.len()] as char)
+                    .take(32 - seed.len()),
+            );
+        }
+        Ok(seed)
+    }
+
+    pub async fn create_did(
+        seed: Option<&[u8]>,
+        version: Option<usize>,
+    ) -> anyhow::Result<(String, LocalKey, String)> {
+        let (key, verkey) = Self::create_key(seed)?;
+        let verkey_bytes = key
+            .to_public_bytes()
+            .context("Failed to get public key bytes")?;
 
         let did = match version {
             Some(1) => {
@@ -101,6 +135,47 @@ impl IndyWallet {
     }
 }
 
+/// A wallet whose key was dropped by a session lock
+pub struct LockedWallet {
+    pub did: String,
+    pub verkey: String,
+}
+
+impl LockedWallet {
+    /// Restores the wallet if the seed derives the same verkey
+    pub fn unlock(&self, seed: &[u8]) -> Result<IndyWallet> {
+        let (key, verkey) = IndyWallet::create_key(Some(seed))?;
+        if verkey != self.verkey {
+            return Err(anyhow!(
+                "This seed does not match the verkey of {}",
+                self.did
+            ));
+        }
+        Ok(IndyWallet {
+            did: self.did.clone(),
+            verkey,
+            key,
+        })
+    }
+}
+
+#[async_trait]
+impl Signer for IndyWallet {
+    fn did(&self) -> &str {
+        &self.did
+    }
+
+    fn verkey(&self) -> &str {
+        &self.verkey
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.key
+            .sign_message(message, None)
+            .map_err(|e| anyhow!("Signing with the wallet key failed: {}", e))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,12 +183,42 @@ mod tests {
     #[tokio::test]
     async fn test_did_indy_generation() {
         let seed = "000000000000000000000000Trustee1";
-        let (did, _, verkey) = IndyWallet::create_did(Some(seed), Some(2)).await.unwrap();
+        let (did, _, verkey) = IndyWallet::create_did(Some(seed.as_bytes()), Some(2))
+            .await
+            .unwrap();
 
         // Verify DID-verkey relationship
         IndyWallet::verify_did_verkey_relationship(&did, &verkey).unwrap();
     }
 
+    #[test]
+    fn test_create_key_from_seed() {
+        let (_, verkey) =
+            IndyWallet::create_key(Some("000000000000000000000000Trustee1".as_bytes())).unwrap();
+        assert_eq!(verkey, "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL");
+
+        let seed = IndyWallet::generate_seed().unwrap();
+        assert_eq!(seed.len(), 32);
+        assert!(seed.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert!(IndyWallet::create_key(Some(seed.as_bytes())).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_lock_and_unlock() {
+        let seed = "000000000000000000000000Trustee1".as_bytes();
+        let locked = IndyWallet::new(Some(seed), 2).await.unwrap().lock();
+        assert!(locked
+            .unlock("000000000000000000000000Trustee2".as_bytes())
+            .is_err());
+
+        let wallet = locked.unlock(seed).unwrap();
+        assert_eq!(wallet.did, locked.did);
+        assert_eq!(
+            wallet.verkey,
+            "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL"
+        );
+    }
+
     #[tokio::test]
     async fn test_invalid_verkey() {
         let invalid_verkey = bs58::encode(vec![0u8; 31]).into_string(); // Wrong length
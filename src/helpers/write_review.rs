@@ -0,0 +1,141 @@
+use crate::helpers::request_json::request_signatures;
+use crate::helpers::txn_types::txn_type_label;
+use anyhow::{anyhow, Result};
+use chrono::DateTime;
+use serde_json::Value;
+
+/// Longest value listed for an operation field, the full request stays in the output
+const MAX_VALUE_LENGTH: usize = 120;
+
+/// What a write request does, listed for review before it is sent
+#[derive(Debug, Clone)]
+pub struct WriteSummary {
+    pub txn_type: String,
+    /// DIDs that signed the request
+    pub signers: Vec<String>,
+    /// Operation fields, nested objects flattened to dotted keys
+    pub operation: Vec<(String, String)>,
+    pub taa: String,
+    pub endorser: Option<String>,
+    /// Payment inputs attached for the ledger fees
+    pub fees: String,
+}
+
+impl WriteSummary {
+    pub fn from_request(request: &Value) -> Result<Self> {
+        let operation = request["operation"]
+            .as_object()
+            .ok_or_else(|| anyhow!("The request has no operation"))?;
+        let txn_type = operation
+            .get("type")
+            .and_then(Value::as_str)
+            .map(txn_type_label)
+            .ok_or_else(|| anyhow!("The request has no operation.type"))?;
+
+        let mut fields = Vec::new();
+        for (key, value) in operation.iter().filter(|(key, _)| *key != "type") {
+            flatten_field(key.clone(), value, &mut fields);
+        }
+
+        let taa = match request.get("taaAcceptance").filter(|taa| !taa.is_null()) {
+            Some(acceptance) => {
+                let accepted = acceptance["time"]
+                    .as_i64()
+                    .and_then(|time| DateTime::from_timestamp(time, 0))
+                    .map_or("unknown date".to_string(), |time| {
+                        time.format("%Y-%m-%d").to_string()
+                    });
+                format!(
+                    "Attached, {} accepted on {}",
+                    acceptance["mechanism"]
+                        .as_str()
+                        .unwrap_or("unknown mechanism"),
+                    accepted
+                )
+            }
+            None => "Not attached".to_string(),
+        };
+
+        Ok(Self {
+            txn_type,
+            signers: request_signatures(request)
+                .into_iter()
+                .map(|(did, _)| did)
+                .collect(),
+            operation: fields,
+            taa,
+            endorser: request["endorser"].as_str().map(str::to_string),
+            fees: match request["fees"][0].as_array() {
+                Some(inputs) => format!("Attached, {} input(s)", inputs.len()),
+                None => "None".to_string(),
+            },
+        })
+    }
+}
+
+fn flatten_field(key: String, value: &Value, fields: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            for (name, value) in object {
+                flatten_field(format!("{}.{}", key, name), value, fields);
+            }
+        }
+        Value::String(text) => fields.push((key, truncate(text))),
+        other => fields.push((key, truncate(&other.to_string()))),
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() > MAX_VALUE_LENGTH {
+        let shown: String = text.chars().take(MAX_VALUE_LENGTH).collect();
+        format!("{}…", shown)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_write_summary() {
+        let request = json!({
+            "identifier": "V4SGRU86Z58d6TV7PBUe6f",
+            "endorser": "Th7MpTaRZVRYnPiabds81Y",
+            "operation": {
+                "type": "101",
+                "data": {"name": "degree", "version": "1.0", "attr_names": ["name"]},
+                "comment": "x".repeat(200),
+            },
+            "taaAcceptance": {"mechanism": "for_session", "taaDigest": "abc", "time": 1_700_006_400},
+            "signatures": {"V4SGRU86Z58d6TV7PBUe6f": "sig"},
+            "reqId": 1,
+        });
+        let summary = WriteSummary::from_request(&request).unwrap();
+        assert_eq!(summary.txn_type, "SCHEMA (101)");
+        assert_eq!(summary.signers, vec!["V4SGRU86Z58d6TV7PBUe6f"]);
+        assert_eq!(summary.endorser.as_deref(), Some("Th7MpTaRZVRYnPiabds81Y"));
+        assert_eq!(summary.taa, "Attached, for_session accepted on 2023-11-15");
+        assert!(summary
+            .operation
+            .contains(&("data.attr_names".to_string(), "[\"name\"]".to_string())));
+        assert!(summary
+            .operation
+            .contains(&("data.version".to_string(), "1.0".to_string())));
+        let comment = summary
+            .operation
+            .iter()
+            .find(|(key, _)| key == "comment")
+            .unwrap();
+        assert_eq!(comment.1.chars().count(), MAX_VALUE_LENGTH + 1);
+
+        let unsigned = json!({"identifier": "V4SGRU86Z58d6TV7PBUe6f", "operation": {"type": "1"}});
+        let summary = WriteSummary::from_request(&unsigned).unwrap();
+        assert!(summary.signers.is_empty());
+        assert_eq!(summary.taa, "Not attached");
+        assert_eq!(summary.fees, "None");
+        assert!(WriteSummary::from_request(&json!({"reqId": 1})).is_err());
+    }
+}
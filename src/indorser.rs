@@ -1,26 +1,107 @@
 use crate::app::DIDVersion;
-use crate::helper::{create_did, sign_transaction};
+use crate::helper::{create_did, sign_transaction, DidInfo};
+use crate::helpers::ledgers::IndyLedger;
+use crate::helpers::mirror::LedgerMirror;
+use crate::helpers::request_json::{is_signed, refresh_request};
+use crate::helpers::seed::SeedInput;
+use crate::helpers::signer::Signer;
+use crate::publish_tool::request_freshness_ui;
+use crate::wallet_tool::seed_input_ui;
 use egui::Ui;
+use futures_executor::block_on;
+use indy_vdr::pool::ProtocolVersion;
 
+#[derive(Default)]
+pub struct EndorserState {
+    pub seed: SeedInput,
+    /// Endorser key derived once from the seed, the seed is wiped afterwards
+    pub key: Option<DidInfo>,
+    pub key_error: Option<String>,
+    pub signed_txn_result: Option<String>,
+}
+
+impl EndorserState {
+    /// Derives the endorser key from the seed, which is cleared on success
+    pub fn load_key(&mut self, did_version: &DIDVersion) {
+        let derived = self
+            .seed
+            .decode()
+            .and_then(|seed| create_did(seed.as_ref(), did_version.to_usize()));
+        match derived {
+            Ok(key) => {
+                self.key = Some(key);
+                self.key_error = None;
+                self.seed.clear();
+            }
+            Err(e) => self.key_error = Some(format!("Failed to load the endorser key: {:#}", e)),
+        }
+        self.signed_txn_result = None;
+    }
+
+    pub fn unload_key(&mut self) {
+        self.key = None;
+        self.signed_txn_result = None;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn endorser_tool(
     ui: &mut Ui,
-    endorser_seed: &mut String,
+    state: &mut EndorserState,
     txn: &mut String,
-    signed_txn_result: &mut Option<String>,
     did_version: &mut DIDVersion,
+    refresh_req_id: &mut bool,
+    ledger: Option<&IndyLedger>,
+    mirror: Option<&LedgerMirror>,
+    external_signer: Option<&dyn Signer>,
 ) {
     ui.label("Sign Txn with Endorser DID");
     // Add more UI elements inside the nested window
     ui.heading("Endorser");
 
+    if let Some(signer) = external_signer {
+        ui.label("Endorser key held by the external signer");
+        ui.separator();
+        endorse_ui(
+            ui,
+            signer,
+            txn,
+            &mut state.signed_txn_result,
+            refresh_req_id,
+            ledger,
+            mirror,
+        );
+        return;
+    }
+
+    if state.key.is_some() {
+        let unload = ui
+            .horizontal(|ui| {
+                ui.label("Endorser key loaded");
+                ui.button("Unload key").clicked()
+            })
+            .inner;
+        if unload {
+            state.unload_key();
+        }
+    }
+    if let Some(key) = &state.key {
+        ui.separator();
+        endorse_ui(
+            ui,
+            key,
+            txn,
+            &mut state.signed_txn_result,
+            refresh_req_id,
+            ledger,
+            mirror,
+        );
+        return;
+    }
+
     ui.vertical(|ui| {
         ui.label("Endorser seed: ");
-        ui.add(
-            egui::TextEdit::singleline(endorser_seed)
-                .char_limit(32)
-                .hint_text("Enter 32 bytes seed"),
-        );
-        ui.label(format!("Length: {}", endorser_seed.len()));
+        let decoded_seed = seed_input_ui(ui, "endorser_seed", &mut state.seed);
         ui.label("Select the version for the DID.  did:Sov is 1, did:Indy is 2");
         egui::ComboBox::from_id_source("version_dropdown")
             .selected_text(format!("{:?}", did_version))
@@ -28,62 +109,128 @@ pub fn endorser_tool(
                 ui.selectable_value(&mut *did_version, DIDVersion::Sov, "SOV");
                 ui.selectable_value(&mut *did_version, DIDVersion::Indy, "Indy");
             });
+        if ui
+            .add_enabled(
+                decoded_seed.is_some(),
+                egui::Button::new("🔑 Load endorser key"),
+            )
+            .clicked()
+        {
+            state.load_key(did_version);
+        }
     });
+    if let Some(error) = &state.key_error {
+        ui.colored_label(egui::Color32::RED, error);
+    }
+}
+
+/// Multi-signs pasted transactions with the endorser key, whatever backend holds it
+fn endorse_ui(
+    ui: &mut Ui,
+    signer: &dyn Signer,
+    txn: &mut String,
+    signed_txn_result: &mut Option<String>,
+    refresh_req_id: &mut bool,
+    ledger: Option<&IndyLedger>,
+    mirror: Option<&LedgerMirror>,
+) {
+    ui.colored_label(egui::Color32::KHAKI, format!("DID: {:?}", signer.did()));
+    ui.colored_label(
+        egui::Color32::KHAKI,
+        format!("Verkey: {:?}", signer.verkey()),
+    );
     ui.separator();
-    if endorser_seed.len() == 32 {
-        let endorser_did = create_did(endorser_seed.clone(), did_version.to_usize()).unwrap();
-        ui.colored_label(
-            egui::Color32::KHAKI,
-            format!("DID: {:?}", endorser_did.did.0),
-        );
-        ui.colored_label(
-            egui::Color32::KHAKI,
-            format!("Verkey: {:?}", endorser_did.verkey),
-        );
-        ui.separator();
 
-        let response_txn = ui.add(
-            egui::TextEdit::multiline(txn)
-                .hint_text("Input Transaction")
-                .desired_width(f32::INFINITY),
-        );
-        ui.separator();
-        if response_txn.changed() {
-            let signed_txn = sign_transaction(endorser_did, txn.clone());
-            match signed_txn {
-                Ok(txn) => {
-                    let unescaped_json = serde_json::to_string(&txn).unwrap();
-                    *signed_txn_result = Some(unescaped_json.clone().to_string());
-                }
-                Err(e) => {
-                    *signed_txn_result = Some(format!("Error: {:?}", e));
-                }
+    let response_txn = ui.add(
+        egui::TextEdit::multiline(txn)
+            .hint_text("Input Transaction")
+            .desired_width(f32::INFINITY),
+    );
+    request_freshness_ui(ui, txn, refresh_req_id, ledger, mirror);
+    if response_txn.changed() {
+        // A signature of the previous text no longer matches what is shown
+        *signed_txn_result = None;
+    }
+    ui.separator();
+    if ui
+        .add_enabled(
+            !txn.trim().is_empty(),
+            egui::Button::new("✍ Sign transaction"),
+        )
+        .clicked()
+    {
+        let unsigned = serde_json::from_str::<serde_json::Value>(txn)
+            .map(|request| !is_signed(&request))
+            .unwrap_or(false);
+        if *refresh_req_id && unsigned {
+            let protocol_version = ledger.map_or(ProtocolVersion::default().to_id(), |ledger| {
+                ledger.protocol_version()
+            });
+            if let Ok(refreshed) = refresh_request(txn, protocol_version) {
+                *txn = refreshed;
             }
         }
+        let signed_txn = block_on(sign_transaction(signer, txn.clone()));
+        match signed_txn {
+            Ok(txn) => {
+                let unescaped_json = serde_json::to_string(&txn).unwrap_or_default();
+                *signed_txn_result = Some(unescaped_json);
+            }
+            Err(e) => {
+                *signed_txn_result = Some(format!("Error: {:?}", e));
+            }
+        }
+    }
 
-        ui.vertical(|ui| {
-            ui.label("Signed Transaction:");
-            if let Some(result) = &signed_txn_result {
-                // ui.colored_label(egui::Color32::GREEN, "Signed Transaction:");
-                ui.colored_label(egui::Color32::GREEN, result.clone());
+    ui.vertical(|ui| {
+        ui.label("Signed Transaction:");
+        if let Some(result) = &signed_txn_result {
+            // ui.colored_label(egui::Color32::GREEN, "Signed Transaction:");
+            ui.colored_label(egui::Color32::GREEN, result.clone());
 
-                ui.separator();
-                // Add a button to copy the unescaped_json content
-                if ui.button("Copy output").clicked() {
-                    let r = result.clone();
-                    ui.output_mut(|o| o.copied_text = r);
-                };
-            }
-        });
-        ui.separator();
-        // if ui.button("Apply Transaction").clicked() {
-        //     // Apply the signed transaction to the ledger
-        //     // apply_transaction(&signed_txn_result);
-        //     write_signed_transaction_to_ledger(signed_txn_result.clone());
-        // }
-        ui.separator();
-        ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
-            egui::warn_if_debug_build(ui);
-        });
+            ui.separator();
+            // Add a button to copy the unescaped_json content
+            if ui.button("Copy output").clicked() {
+                let r = result.clone();
+                ui.output_mut(|o| o.copied_text = r);
+            };
+        }
+    });
+    ui.separator();
+    // if ui.button("Apply Transaction").clicked() {
+    //     // Apply the signed transaction to the ledger
+    //     // apply_transaction(&signed_txn_result);
+    //     write_signed_transaction_to_ledger(signed_txn_result.clone());
+    // }
+    ui.separator();
+    ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
+        egui::warn_if_debug_build(ui);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zeroize::Zeroizing;
+
+    #[test]
+    fn test_load_endorser_key() {
+        let mut state = EndorserState::default();
+        state.seed.text = Zeroizing::new("000000000000000000000000Trustee1".to_string());
+        state.load_key(&DIDVersion::Sov);
+        assert_eq!(
+            state.key.as_ref().map(|key| key.did()),
+            Some("V4SGRU86Z58d6TV7PBUe6f")
+        );
+        assert!(state.seed.is_empty());
+
+        state.unload_key();
+        state.seed.text = Zeroizing::new("too short".to_string());
+        state.load_key(&DIDVersion::Sov);
+        assert!(state.key.is_none());
+        assert!(state
+            .key_error
+            .as_deref()
+            .is_some_and(|error| error.contains("32 bytes expected")));
     }
 }
@@ -0,0 +1,201 @@
+use crate::helpers::ledgers::{IndyLedger, RotationOutcome};
+use crate::helpers::seed::{SeedFormat, SeedInput};
+use crate::helpers::wallet::IndyWallet;
+use crate::publish_tool::{write_review_ui, WriteReview, WriteTarget};
+use crate::wallet_tool::seed_input_ui;
+use aries_askar::kms::LocalKey;
+use egui::Ui;
+use futures_executor::block_on;
+use zeroize::Zeroizing;
+
+#[derive(Default)]
+pub struct KeyRotationState {
+    pub new_seed: SeedInput,
+    pub seed_saved: bool,
+    pub new_key: Option<(LocalKey, String)>,
+    pub result: Option<Result<RotationOutcome, String>>,
+    pub review: WriteReview,
+    /// GET_NYM reads made since the rotation was written without the new verkey showing
+    pub failed_checks: u32,
+}
+
+pub fn key_rotation_tool_ui(
+    ui: &mut Ui,
+    wallet: &mut Option<IndyWallet>,
+    ledgers: &Option<IndyLedger>,
+    target: WriteTarget<'_>,
+    state: &mut KeyRotationState,
+) {
+    ui.label("Replace the verkey of the wallet DID on the ledger");
+
+    let Some(wallet_ref) = wallet.as_mut() else {
+        ui.colored_label(egui::Color32::LIGHT_RED, "Please create a wallet first");
+        return;
+    };
+    let Some(ledger) = ledgers else {
+        ui.colored_label(
+            egui::Color32::LIGHT_RED,
+            "Please connect to a ledger in the Publish Tool first",
+        );
+        return;
+    };
+
+    if let Some(pending) = write_review_ui(ui.ctx(), &mut state.review, target) {
+        let reviewed_verkey = pending.request["operation"]["verkey"].as_str();
+        match &state.new_key {
+            Some((_, new_verkey)) if Some(new_verkey.as_str()) == reviewed_verkey => {
+                match block_on(ledger.submit_signed(&pending.request)) {
+                    Ok(reply) => {
+                        if block_on(ledger.check_rotated_verkey(&wallet_ref.did, new_verkey, 3)) {
+                            use_new_key(wallet_ref, state, reply);
+                        } else {
+                            // Kept pending until GET_NYM confirms it, see the Re-check action
+                            state.failed_checks = 1;
+                            state.result = Some(Ok(RotationOutcome::Unverified(reply)));
+                        }
+                    }
+                    Err(e) => state.result = Some(Err(e.to_string())),
+                }
+            }
+            _ => {
+                state.result = Some(Err(
+                    "The new key changed during the review, nothing was sent".to_string(),
+                ))
+            }
+        }
+    }
+
+    ui.label(format!("DID: {}", wallet_ref.did));
+    ui.label(format!("Current verkey: {}", wallet_ref.verkey));
+    ui.separator();
+
+    ui.colored_label(egui::Color32::from_rgb(144, 238, 144), "New key seed");
+    // The reviewed or written request carries the new verkey, it cannot change until
+    // the rotation is cancelled or confirmed
+    let reviewing = state.review.pending.is_some();
+    let unverified = matches!(state.result, Some(Ok(RotationOutcome::Unverified(_))));
+    ui.add_enabled_ui(!reviewing && !unverified, |ui| {
+        if ui.button("🎲 Generate").clicked() {
+            match IndyWallet::generate_seed() {
+                Ok(seed) => {
+                    // Shown so that it can be stored before rotating
+                    state.new_seed.format = SeedFormat::Raw;
+                    state.new_seed.text = Zeroizing::new(seed);
+                    state.new_seed.reveal = true;
+                }
+                Err(e) => state.result = Some(Err(e.to_string())),
+            }
+        }
+        let decoded_seed = seed_input_ui(ui, "rotation_seed", &mut state.new_seed);
+        if reviewing || unverified {
+            return;
+        }
+        let new_key = match decoded_seed {
+            Some(seed) => match IndyWallet::create_key(Some(&*seed)) {
+                Ok(new_key) => Some(new_key),
+                Err(e) => {
+                    ui.colored_label(egui::Color32::RED, format!("Invalid seed: {}", e));
+                    None
+                }
+            },
+            None => None,
+        };
+        // A different seed needs to be saved again
+        let new_verkey = new_key.as_ref().map(|(_, verkey)| verkey);
+        if new_verkey != state.new_key.as_ref().map(|(_, verkey)| verkey) {
+            state.seed_saved = false;
+        }
+        state.new_key = new_key;
+    });
+
+    if let Some((_, new_verkey)) = &state.new_key {
+        ui.colored_label(egui::Color32::KHAKI, format!("New verkey: {}", new_verkey));
+        if new_verkey == &wallet_ref.verkey {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                "The new key is the same as the current one",
+            );
+        }
+        ui.colored_label(
+            egui::Color32::YELLOW,
+            "⚠️ Once rotated, only the new seed controls this DID. Store it safely before continuing.",
+        );
+        ui.checkbox(&mut state.seed_saved, "I have saved the new seed");
+
+        let can_rotate =
+            state.seed_saved && new_verkey != &wallet_ref.verkey && !reviewing && !unverified;
+        if ui
+            .add_enabled(can_rotate, egui::Button::new("🔑 Rotate key"))
+            .clicked()
+        {
+            // Signed with the current key, sent once confirmed in the write review
+            match block_on(ledger.rotate_verkey(wallet_ref, new_verkey)) {
+                Ok(request) => {
+                    if let Err(e) = state.review.open(&request, "KeyRotation") {
+                        state.result = Some(Err(format!("Failed to review the request: {:#}", e)));
+                    }
+                }
+                Err(e) => state.result = Some(Err(e.to_string())),
+            }
+        }
+    }
+
+    match &state.result {
+        Some(Ok(RotationOutcome::Verified(reply))) => {
+            ui.separator();
+            ui.colored_label(
+                egui::Color32::GREEN,
+                "Key rotated, the ledger reports the new verkey",
+            );
+            ui.monospace(reply);
+        }
+        Some(Ok(RotationOutcome::Unverified(reply))) => {
+            ui.separator();
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                "⚠️ Key written but unverified: the ledger accepted the NYM but GET_NYM does not report the new verkey yet. The wallet keeps the current key until it does.",
+            );
+            ui.label(format!("Checked {} times", state.failed_checks));
+            ui.monospace(reply);
+            let reply = reply.clone();
+            ui.horizontal(|ui| {
+                if ui.button("🔄 Re-check").clicked() {
+                    let verified = state.new_key.as_ref().is_some_and(|(_, new_verkey)| {
+                        block_on(ledger.check_rotated_verkey(&wallet_ref.did, new_verkey, 1))
+                    });
+                    if verified {
+                        use_new_key(wallet_ref, state, reply);
+                    } else {
+                        state.failed_checks += 1;
+                    }
+                }
+                if ui
+                    .button("Discard the new key")
+                    .on_hover_text("Only when the ledger still reports the current verkey, the new seed is still needed otherwise")
+                    .clicked()
+                {
+                    *state = KeyRotationState::default();
+                }
+            });
+        }
+        Some(Err(e)) => {
+            ui.separator();
+            ui.colored_label(
+                egui::Color32::RED,
+                format!("Key rotation failed, the wallet key was kept: {}", e),
+            );
+        }
+        None => {}
+    }
+}
+
+/// Swaps the wallet key once GET_NYM reports the new verkey
+fn use_new_key(wallet: &mut IndyWallet, state: &mut KeyRotationState, reply: String) {
+    if let Some((new_key, new_verkey)) = state.new_key.take() {
+        wallet.replace_key(new_key, new_verkey);
+    }
+    state.new_seed.clear();
+    state.seed_saved = false;
+    state.failed_checks = 0;
+    state.result = Some(Ok(RotationOutcome::Verified(reply)));
+}
@@ -0,0 +1,301 @@
+use crate::helpers::did::unqualified_did;
+use crate::helpers::genesis::{GenesisSource, PoolSettings};
+use crate::helpers::ledger_diff::{
+    diff_inventories, replication_requests, DiffEntry, DiffStatus, IssuerInventory,
+};
+use crate::helpers::ledgers::IndyLedger;
+use crate::helpers::mirror::LedgerMirror;
+use crate::helpers::state_proof::ProofStatus;
+use crate::helpers::wallet::IndyWallet;
+use crate::wallet_tool::{NetworkPoolSettings, RecentUrls};
+use anyhow::{anyhow, Context};
+use egui::{Color32, ComboBox, TextEdit, Ui};
+use futures_executor::block_on;
+use indy_vdr::pool::ProtocolVersion;
+use rfd::FileDialog;
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// Inventories compared for an issuer, kept to prepare the replication
+pub struct Comparison {
+    pub did: String,
+    pub source: IssuerInventory,
+    pub target: IssuerInventory,
+    pub entries: Vec<DiffEntry>,
+    /// Transactions in each mirror when the comparison was made
+    pub mirror_sizes: (u64, u64),
+    /// State proofs of the ledger reads completing each side
+    pub proofs: (Option<ProofStatus>, Option<ProofStatus>),
+    pub target_protocol_version: ProtocolVersion,
+}
+
+#[derive(Default)]
+pub struct LedgerDiffState {
+    pub issuer_did: String,
+    /// Genesis file path or URL of the target network
+    pub target_input: String,
+    /// Connection to the target network, by genesis location
+    pub target_ledger: Option<(String, IndyLedger)>,
+    pub comparison: Option<Comparison>,
+    /// (schema, unsigned SCHEMA request) for the schemas missing on the target
+    pub requests: Vec<(String, String)>,
+    /// Request to load in the Publish Tool as a custom transaction
+    pub open_in_publish: Option<String>,
+    pub error: Option<String>,
+}
+
+pub fn ledger_diff_tool_ui(
+    ui: &mut Ui,
+    wallet: &Option<IndyWallet>,
+    ledgers: &Option<IndyLedger>,
+    genesis_source: &Option<GenesisSource>,
+    recent_urls: &RecentUrls,
+    pool_settings: &NetworkPoolSettings,
+    state: &mut LedgerDiffState,
+) {
+    ui.label("Compare what an issuer wrote on two networks, from their local mirrors checked against both ledgers");
+
+    let (Some(source), Some(source_ledger)) = (genesis_source, ledgers) else {
+        ui.colored_label(
+            Color32::LIGHT_RED,
+            "Please connect to the source network in the Publish Tool first",
+        );
+        return;
+    };
+
+    egui::Grid::new("ledger_diff_inputs")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Source network:");
+            ui.colored_label(Color32::KHAKI, source.location());
+            ui.end_row();
+
+            ui.label("Target network:");
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut state.target_input)
+                        .hint_text("Genesis URL or file path")
+                        .desired_width(320.0),
+                );
+                if ui.button("📂").clicked() {
+                    if let Some(path) = FileDialog::new().pick_file() {
+                        state.target_input = path.display().to_string();
+                    }
+                }
+                if !recent_urls.is_empty() {
+                    ComboBox::from_id_source("ledger_diff_recent")
+                        .selected_text("Recent")
+                        .show_ui(ui, |ui| {
+                            for url in recent_urls.get_recent() {
+                                if ui.selectable_label(false, url).clicked() {
+                                    state.target_input = url.clone();
+                                }
+                            }
+                        });
+                }
+            });
+            ui.end_row();
+
+            ui.label("Issuer DID:");
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut state.issuer_did)
+                        .hint_text("did:indy:<namespace>:<id>, did:sov:<id> or <id>")
+                        .desired_width(320.0),
+                );
+                if let Some(wallet) = wallet {
+                    if ui.button("Use wallet DID").clicked() {
+                        state.issuer_did = wallet.did.clone();
+                    }
+                }
+            });
+            ui.end_row();
+        });
+
+    let target = GenesisSource::from_str(state.target_input.trim());
+    let did = unqualified_did(state.issuer_did.trim());
+    let ready = match (&target, &did) {
+        (Ok(target), Ok(_)) if target.location() == source.location() => {
+            ui.colored_label(
+                Color32::YELLOW,
+                "The target is the source network, pick another one",
+            );
+            false
+        }
+        (Ok(_), Ok(_)) => true,
+        _ => false,
+    };
+
+    if ui
+        .add_enabled(ready, egui::Button::new("🔍 Compare"))
+        .clicked()
+    {
+        if let (Ok(target), Ok(did)) = (&target, &did) {
+            state.comparison = None;
+            state.requests.clear();
+            state.error = None;
+            match compare(
+                did,
+                (source, source_ledger),
+                target,
+                &pool_settings.get(target),
+                &mut state.target_ledger,
+            ) {
+                Ok(comparison) => state.comparison = Some(comparison),
+                Err(e) => state.error = Some(format!("{:#}", e)),
+            }
+        }
+    }
+
+    if let Some(error) = &state.error {
+        ui.colored_label(Color32::LIGHT_RED, error);
+    }
+    let Some(comparison) = &state.comparison else {
+        return;
+    };
+
+    ui.separator();
+    let (source_size, target_size) = comparison.mirror_sizes;
+    ui.small(format!(
+        "Source mirror: {} transactions, target mirror: {} transactions",
+        source_size, target_size
+    ));
+    if source_size == 0 || target_size == 0 {
+        ui.colored_label(
+            Color32::YELLOW,
+            "⚠️ A mirror is empty, sync it in the Ledger Explorer while connected to that network",
+        );
+    }
+    ui.small("Items missing from a mirror are looked up on its ledger, items in neither mirror are not found");
+    for (network, proof) in [
+        ("Source ledger", &comparison.proofs.0),
+        ("Target ledger", &comparison.proofs.1),
+    ] {
+        if let Some(proof) = proof {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", network));
+                proof.show(ui);
+            });
+        }
+    }
+
+    if comparison.entries.is_empty() {
+        ui.label("The issuer wrote nothing on either network");
+    }
+    egui::ScrollArea::vertical()
+        .max_height(300.0)
+        .show(ui, |ui| {
+            egui::Grid::new("ledger_diff_entries")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for entry in &comparison.entries {
+                        ui.label(entry.kind);
+                        ui.monospace(&entry.item);
+                        match &entry.status {
+                            DiffStatus::OnlySource => {
+                                ui.colored_label(Color32::YELLOW, "Only on source")
+                            }
+                            DiffStatus::OnlyTarget => {
+                                ui.colored_label(Color32::LIGHT_BLUE, "Only on target")
+                            }
+                            DiffStatus::Differs(fields) => ui.colored_label(
+                                Color32::from_rgb(255, 165, 0),
+                                format!("Differs: {}", fields),
+                            ),
+                            DiffStatus::Same => ui.colored_label(Color32::GREEN, "Same"),
+                        };
+                        ui.end_row();
+                    }
+                });
+        });
+
+    ui.separator();
+    ui.small("Cred defs are not replicated, the issuer creates new keys on the target network");
+    if ui.button("Prepare schema replication").clicked() {
+        match replication_requests(
+            &comparison.did,
+            &comparison.source,
+            &comparison.target,
+            comparison.target_protocol_version,
+        ) {
+            Ok(requests) if requests.is_empty() => {
+                state.requests.clear();
+                state.error = Some("Every source schema is already on the target".to_string());
+            }
+            Ok(requests) => state.requests = requests,
+            Err(e) => state.error = Some(format!("{:#}", e)),
+        }
+    }
+
+    for (schema, request) in &state.requests {
+        egui::CollapsingHeader::new(format!("SCHEMA {}", schema))
+            .id_source(("ledger_diff_request", schema))
+            .show(ui, |ui| {
+                ui.monospace(request);
+                ui.horizontal(|ui| {
+                    if ui.button("📋 Copy").clicked() {
+                        ui.output_mut(|o| o.copied_text = request.clone());
+                    }
+                    if ui
+                        .button("Open in Publish Tool")
+                        .on_hover_text(
+                            "Loads the request as a custom transaction, connect to the target network to send it",
+                        )
+                        .clicked()
+                    {
+                        state.open_in_publish = Some(request.clone());
+                    }
+                });
+            });
+    }
+}
+
+/// Reads the issuer's inventory from both mirrors, then completes each side with
+/// reads on its ledger for what the other side holds
+fn compare(
+    did: &str,
+    (source, source_ledger): (&GenesisSource, &IndyLedger),
+    target: &GenesisSource,
+    settings: &PoolSettings,
+    connection: &mut Option<(String, IndyLedger)>,
+) -> anyhow::Result<Comparison> {
+    let source_mirror =
+        LedgerMirror::for_network(source).context("Failed to open the source mirror")?;
+    let target_mirror =
+        LedgerMirror::for_network(target).context("Failed to open the target mirror")?;
+
+    let target_ledger = match connection.take() {
+        Some((location, ledger)) if location == target.location() => ledger,
+        _ => block_on(timeout(
+            Duration::from_secs(settings.connect_timeout),
+            IndyLedger::new(target.clone(), settings),
+        ))
+        .map_err(|_| anyhow!("Connection to the target network timed out"))?
+        .map_err(|e| anyhow!("Failed to connect to the target network: {}", e))?,
+    };
+    let target_ledger = &connection
+        .insert((target.location().to_string(), target_ledger))
+        .1;
+    let target_protocol_version = ProtocolVersion::from_id(target_ledger.protocol_version())
+        .map_err(|e| anyhow!("Unknown protocol version of the target pool: {}", e))?;
+
+    let mut source_inventory = IssuerInventory::from_mirror(&source_mirror, did);
+    let mut target_inventory = IssuerInventory::from_mirror(&target_mirror, did);
+    let source_proof =
+        block_on(source_inventory.complete_from_ledger(source_ledger, did, &target_inventory))
+            .context("Failed to read the source ledger")?;
+    let target_proof =
+        block_on(target_inventory.complete_from_ledger(target_ledger, did, &source_inventory))
+            .context("Failed to read the target ledger")?;
+
+    Ok(Comparison {
+        did: did.to_string(),
+        entries: diff_inventories(did, &source_inventory, &target_inventory),
+        source: source_inventory,
+        target: target_inventory,
+        mirror_sizes: (source_mirror.last_seq_no(), target_mirror.last_seq_no()),
+        proofs: (source_proof, target_proof),
+        target_protocol_version,
+    })
+}
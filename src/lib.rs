@@ -2,10 +2,19 @@
 
 pub use app::TemplateApp;
 
+mod airgap_tool;
 mod app;
+mod auth_rules_tool;
 mod endorsing;
+mod explorer_tool;
+mod external_signer_tool;
 mod helper;
 mod helpers;
 mod indorser;
+mod key_rotation_tool;
+mod ledger_diff_tool;
+mod manage_did_tool;
 mod publish_tool;
+mod resolver_tool;
+mod schema_lookup_tool;
 mod wallet_tool;
@@ -0,0 +1,410 @@
+use crate::app::{MyRoles, TransactionOptions};
+use crate::helpers::auth_rules::{
+    check_constraint, nym_owner, role_code, role_name, AuthRuleKey, ConstraintCheck,
+};
+use crate::helpers::did::QualifiedDid;
+use crate::helpers::ledgers::{IndyLedger, ReadReply};
+use crate::helpers::state_proof::ProofStatus;
+use crate::helpers::wallet::IndyWallet;
+use crate::publish_tool::{held_write_result, write_review_ui, WriteReview, WriteTarget};
+use egui::Ui;
+use futures_executor::block_on;
+use indy_vdr::ledger::constants::{LedgerRole, UpdateRole};
+use serde_json::Value;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub enum NymAction {
+    #[default]
+    ChangeRole,
+    Demote,
+    Suspend,
+}
+
+#[derive(Default)]
+pub struct ManageDidState {
+    pub did_input: String,
+    pub nym: Option<Value>,
+    pub submitter_nym: Option<Value>,
+    pub nym_proof: Option<ProofStatus>,
+    pub action: NymAction,
+    pub new_role: MyRoles,
+    pub rule_key: Option<AuthRuleKey>,
+    pub rules: Option<Result<ReadReply<Vec<Value>>, String>>,
+    pub error: Option<String>,
+    pub result: Option<Result<String, String>>,
+    pub review: WriteReview,
+}
+
+/// Role and verkey of a NYM, before or after the selected action
+#[derive(Debug, PartialEq, Eq)]
+struct NymState {
+    role: Option<String>,
+    verkey: Option<String>,
+}
+
+impl NymState {
+    fn from_nym(nym: &Value) -> Self {
+        Self {
+            role: nym["role"].as_str().map(str::to_string),
+            verkey: nym["verkey"].as_str().map(str::to_string),
+        }
+    }
+
+    fn apply(&self, action: &NymAction, new_role: &MyRoles) -> Self {
+        match action {
+            NymAction::ChangeRole => Self {
+                role: role_code(new_role).map(str::to_string),
+                verkey: self.verkey.clone(),
+            },
+            NymAction::Demote => Self {
+                role: None,
+                verkey: self.verkey.clone(),
+            },
+            NymAction::Suspend => Self {
+                role: self.role.clone(),
+                verkey: None,
+            },
+        }
+    }
+}
+
+fn update_role(role: &MyRoles) -> UpdateRole {
+    match role {
+        MyRoles::Author => UpdateRole::Reset,
+        MyRoles::Endorser => UpdateRole::Set(LedgerRole::Endorser),
+        MyRoles::NetworkMonitor => UpdateRole::Set(LedgerRole::NetworkMonitor),
+        MyRoles::Steward => UpdateRole::Set(LedgerRole::Steward),
+        MyRoles::Trustee => UpdateRole::Set(LedgerRole::Trustee),
+    }
+}
+
+pub fn manage_did_tool_ui(
+    ui: &mut Ui,
+    wallet: &Option<IndyWallet>,
+    ledgers: &Option<IndyLedger>,
+    target: WriteTarget<'_>,
+    transaction_options: &mut TransactionOptions,
+    state: &mut ManageDidState,
+) {
+    ui.label("Change the role of an existing DID or suspend it");
+    let namespace = target.namespace;
+
+    let Some(wallet) = wallet else {
+        ui.colored_label(egui::Color32::LIGHT_RED, "Please create a wallet first");
+        return;
+    };
+    let Some(ledger) = ledgers else {
+        ui.colored_label(
+            egui::Color32::LIGHT_RED,
+            "Please connect to a ledger in the Publish Tool first",
+        );
+        return;
+    };
+
+    if let Some(pending) = write_review_ui(ui.ctx(), &mut state.review, target) {
+        match block_on(ledger.submit_signed(&pending.request)) {
+            Ok(reply) => {
+                // Show the NYM as the ledger now reports it
+                fetch_nyms(ledger, wallet, namespace, state);
+                state.result = Some(Ok(reply));
+            }
+            Err(e) => state.result = Some(Err(e.to_string())),
+        }
+    }
+
+    ui.horizontal(|ui| {
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut state.did_input)
+                .hint_text("DID to manage")
+                .desired_width(400.0),
+        );
+        let enter_pressed = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+        if ui.button("Fetch NYM").clicked() || enter_pressed {
+            fetch_nyms(ledger, wallet, namespace, state);
+        }
+    });
+
+    if let Some(error) = &state.error {
+        ui.colored_label(egui::Color32::LIGHT_RED, error);
+    }
+    match &state.result {
+        Some(Ok(reply)) => {
+            ui.colored_label(egui::Color32::GREEN, "Transaction result:");
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    ui.monospace(reply);
+                });
+        }
+        Some(Err(e)) => {
+            ui.colored_label(egui::Color32::RED, format!("Update failed: {}", e));
+        }
+        None => {}
+    }
+
+    let Some(nym) = &state.nym else {
+        return;
+    };
+    let dest = nym["dest"].as_str().unwrap_or_default().to_string();
+    let before = NymState::from_nym(nym);
+
+    ui.separator();
+    ui.colored_label(egui::Color32::KHAKI, format!("DID: {}", dest));
+    ui.label(format!("Role: {}", role_name(before.role.as_deref())));
+    ui.label(format!(
+        "Verkey: {}",
+        before.verkey.as_deref().unwrap_or("none (suspended)")
+    ));
+    ui.label(format!(
+        "Alias: {}",
+        nym["alias"]
+            .as_str()
+            .unwrap_or("not returned by the ledger")
+    ));
+    ui.label(format!(
+        "Created by: {}  |  Seq No: {}",
+        nym["identifier"].as_str().unwrap_or_default(),
+        nym["seqNo"]
+    ));
+    if let Some(proof) = &state.nym_proof {
+        proof.show(ui);
+    }
+
+    let submitter_role = state
+        .submitter_nym
+        .as_ref()
+        .and_then(|submitter| submitter["role"].as_str());
+    let is_owner = nym_owner(nym) == Some(wallet.did.as_str());
+    ui.label(format!(
+        "Submitter: {} ({}{})",
+        wallet.did,
+        role_name(submitter_role),
+        if is_owner { ", owner" } else { "" }
+    ));
+    if state.submitter_nym.is_none() {
+        ui.colored_label(
+            egui::Color32::YELLOW,
+            "The wallet DID is not on this ledger, the transaction will be rejected",
+        );
+    }
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.radio_value(&mut state.action, NymAction::ChangeRole, "Change role");
+        ui.radio_value(&mut state.action, NymAction::Demote, "Demote (remove role)");
+        ui.radio_value(
+            &mut state.action,
+            NymAction::Suspend,
+            "Suspend (verkey to null)",
+        );
+    });
+    if state.action == NymAction::ChangeRole {
+        egui::ComboBox::from_id_source("manage_did_role")
+            .selected_text(format!("{:?}", state.new_role))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut state.new_role, MyRoles::Author, "Author");
+                ui.selectable_value(&mut state.new_role, MyRoles::Endorser, "Endorser");
+                ui.selectable_value(
+                    &mut state.new_role,
+                    MyRoles::NetworkMonitor,
+                    "Network Monitor",
+                );
+                ui.selectable_value(&mut state.new_role, MyRoles::Steward, "Steward");
+                ui.selectable_value(&mut state.new_role, MyRoles::Trustee, "Trustee");
+            });
+    }
+
+    let after = before.apply(&state.action, &state.new_role);
+    egui::Grid::new("manage_did_diff")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("Field");
+            ui.strong("Before");
+            ui.strong("After");
+            ui.end_row();
+
+            diff_row(
+                ui,
+                "Role",
+                role_name(before.role.as_deref()),
+                role_name(after.role.as_deref()),
+            );
+            diff_row(
+                ui,
+                "Verkey",
+                before.verkey.as_deref().unwrap_or("null"),
+                after.verkey.as_deref().unwrap_or("null"),
+            );
+        });
+
+    if before == after {
+        ui.colored_label(egui::Color32::YELLOW, "Nothing to change");
+        return;
+    }
+
+    let rule_key = if before.verkey != after.verkey {
+        AuthRuleKey::nym_verkey_change()
+    } else {
+        AuthRuleKey::nym_role_change(before.role.as_deref(), after.role.as_deref())
+    };
+    if state.rule_key.as_ref() != Some(&rule_key) {
+        state.rules =
+            Some(block_on(ledger.get_auth_rules(Some(&rule_key))).map_err(|e| e.to_string()));
+        state.rule_key = Some(rule_key);
+    }
+
+    ui.separator();
+    ui.colored_label(egui::Color32::KHAKI, "Submitter permission:");
+    if let Some(Ok(rules)) = &state.rules {
+        rules.proof.show(ui);
+    }
+    let permitted = match &state.rules {
+        Some(Ok(rules)) => match rules.data.first() {
+            Some(rule) => {
+                let ConstraintCheck {
+                    satisfied,
+                    explanation,
+                } = check_constraint(&rule["constraint"], submitter_role, is_owner);
+                for line in explanation {
+                    ui.monospace(line);
+                }
+                satisfied
+            }
+            None => {
+                ui.label("The ledger has no auth rule for this change");
+                false
+            }
+        },
+        Some(Err(e)) => {
+            ui.colored_label(
+                egui::Color32::LIGHT_RED,
+                format!("Failed to fetch the auth rule: {}", e),
+            );
+            false
+        }
+        None => false,
+    };
+    if !permitted {
+        ui.colored_label(
+            egui::Color32::YELLOW,
+            "⚠️ The wallet DID alone does not satisfy the auth rule, the ledger will reject the change unless more signatures are added",
+        );
+    }
+    if state.action == NymAction::Suspend {
+        ui.colored_label(
+            egui::Color32::YELLOW,
+            "⚠️ A suspended DID can no longer sign transactions",
+        );
+    }
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut transaction_options.sign, "Sign Transaction");
+        ui.checkbox(&mut transaction_options.send, "Send to Ledger");
+    });
+
+    if ui.button("Apply change").clicked() {
+        let role = (before.role != after.role).then(|| match state.action {
+            NymAction::Demote => UpdateRole::Reset,
+            _ => update_role(&state.new_role),
+        });
+        let suspend = state.action == NymAction::Suspend;
+        let request = block_on(ledger.update_nym(
+            wallet,
+            &dest,
+            role,
+            suspend,
+            &transaction_options.without_send(),
+        ));
+        state.result = held_write_result(
+            request.map_err(|e| e.to_string()),
+            transaction_options.send,
+            "ManageDid",
+            &mut state.review,
+        );
+    }
+}
+
+fn diff_row(ui: &mut Ui, field: &str, before: &str, after: &str) {
+    ui.label(field);
+    ui.monospace(before);
+    if before == after {
+        ui.monospace(after);
+    } else {
+        ui.colored_label(egui::Color32::LIGHT_GREEN, after);
+    }
+    ui.end_row();
+}
+
+fn fetch_nyms(
+    ledger: &IndyLedger,
+    wallet: &IndyWallet,
+    namespace: Option<&str>,
+    state: &mut ManageDidState,
+) {
+    state.nym = None;
+    state.submitter_nym = None;
+    state.nym_proof = None;
+    state.rule_key = None;
+    state.rules = None;
+    state.error = None;
+    state.result = None;
+
+    let did = match QualifiedDid::parse_on_network(&state.did_input, namespace)
+        .and_then(|did| did.check_namespace(namespace).map(|_| did))
+    {
+        Ok(did) => did,
+        Err(e) => {
+            state.error = Some(format!("Invalid DID: {}", e));
+            return;
+        }
+    };
+
+    let mut proofs = Vec::new();
+    match block_on(ledger.get_nym(&did.id)) {
+        Ok(reply) => {
+            proofs.push(reply.proof);
+            match reply.data {
+                Some(nym) => state.nym = Some(nym),
+                None => state.error = Some(format!("{} is not on the ledger", did)),
+            }
+        }
+        Err(e) => state.error = Some(format!("Failed to fetch the NYM: {}", e)),
+    }
+    match block_on(ledger.get_nym(&wallet.did)) {
+        Ok(reply) => {
+            proofs.push(reply.proof);
+            state.submitter_nym = reply.data;
+        }
+        Err(e) => state.error = Some(format!("Failed to fetch the wallet NYM: {}", e)),
+    }
+    state.nym_proof = ProofStatus::combine(proofs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_nym_state_apply() {
+        let before = NymState::from_nym(&json!({
+            "dest": "V4SGRU86Z58d6TV7PBUe6f",
+            "role": "101",
+            "verkey": "~CoRER63DVYnWZtK8uAzNbx"
+        }));
+        assert_eq!(before.role.as_deref(), Some("101"));
+
+        let demoted = before.apply(&NymAction::Demote, &MyRoles::Trustee);
+        assert_eq!(demoted.role, None);
+        assert_eq!(demoted.verkey, before.verkey);
+
+        let promoted = before.apply(&NymAction::ChangeRole, &MyRoles::Steward);
+        assert_eq!(promoted.role.as_deref(), Some("2"));
+
+        let suspended = before.apply(&NymAction::Suspend, &MyRoles::Author);
+        assert_eq!(suspended.role, before.role);
+        assert_eq!(suspended.verkey, None);
+    }
+}
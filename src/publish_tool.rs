@@ -1,7 +1,28 @@
-use crate::app::{MyRoles, NymInfo, SchemaInfo, TransactionOptions};
+use crate::app::{
+    MyRoles, NodeInfo, NymInfo, PoolAdminInfo, RevocRegDefInfo, RevocRegEntryInfo, SchemaInfo,
+    TransactionOptions,
+};
+use crate::helpers::auth_rules::{role_code, role_name, AuthRuleKey};
+use crate::helpers::did::{qualified_object_id, QualifiedDid};
+use crate::helpers::diddoc::{check_nym_version, validate_diddoc_content, NYM_VERSIONS};
+use crate::helpers::fees::{
+    applicable_fees, attach_fees, fee_requirements, required_fee, FeeRequirement, FeeSchedule,
+    MockPaymentHandler, PaymentHandler,
+};
 use crate::helpers::genesis::GenesisSource;
 use crate::helpers::ledgers::IndyLedger;
-use crate::helpers::wallet::IndyWallet;
+use crate::helpers::mirror::LedgerMirror;
+use crate::helpers::pool_admin::{
+    build_node_data, build_pool_upgrade, generate_schedule, genesis_nodes, prefill_node_info,
+    restart_datetime, POOL_ACTIONS,
+};
+use crate::helpers::request_json::{
+    format_json, freshness_warnings, highlight_json, is_signed, refresh_request, validate_request,
+};
+use crate::helpers::revocation::{build_revoc_reg_def, build_revoc_reg_entry, ISSUANCE_TYPES};
+use crate::helpers::signer::Signer;
+use crate::helpers::state_proof::ProofStatus;
+use crate::helpers::write_review::WriteSummary;
 use derive_more::Display;
 use egui::{ComboBox, Ui};
 use futures_executor::block_on;
@@ -11,6 +32,9 @@ use indy_data_types::anoncreds::schema::{
 use indy_data_types::did::DidValue;
 use indy_data_types::{SchemaId, Validatable};
 use indy_vdr::ledger::constants::{LedgerRole, UpdateRole};
+use indy_vdr::pool::{Pool, ProtocolVersion};
+use serde_json::Value;
+use std::collections::BTreeSet;
 
 #[derive(PartialEq, Eq, Debug, Display)]
 enum PublishEntities {
@@ -18,23 +42,156 @@ enum PublishEntities {
     Nym,
     Attrib,
     Schema,
+    RevocRegDef,
+    RevocRegEntry,
+    Node,
+    PoolUpgrade,
+    PoolConfig,
+    PoolRestart,
     Custom,
 }
 
+/// A prepared write waiting for confirmation before it is submitted
+pub struct PendingWrite {
+    pub request: Value,
+    pub summary: WriteSummary,
+    /// Publish option the request was prepared from
+    pub publish_option: String,
+}
+
+#[derive(Default)]
+pub struct WriteReview {
+    pub pending: Option<PendingWrite>,
+    /// Network name typed to confirm a write to a production network
+    pub typed_confirmation: String,
+    /// Sending a request that carries no signature was explicitly accepted
+    pub unsigned_acknowledged: bool,
+}
+
+impl WriteReview {
+    pub fn open(&mut self, request_text: &str, publish_option: &str) -> anyhow::Result<()> {
+        let request: Value = serde_json::from_str(request_text)?;
+        let summary = WriteSummary::from_request(&request)?;
+        self.pending = Some(PendingWrite {
+            request,
+            summary,
+            publish_option: publish_option.to_string(),
+        });
+        self.typed_confirmation.clear();
+        self.unsigned_acknowledged = false;
+        Ok(())
+    }
+}
+
+/// Network a write goes to, as shown when it is reviewed
+#[derive(Clone, Copy, Default)]
+pub struct WriteTarget<'a> {
+    pub genesis_source: Option<&'a GenesisSource>,
+    pub namespace: Option<&'a str>,
+    pub production: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaymentChoice {
+    #[default]
+    None,
+    Mock,
+}
+
+/// Fees detected on the connected ledger and the handler paying them
+#[derive(Default)]
+pub struct FeeState {
+    pub schedule: Option<Result<FeeSchedule, String>>,
+    /// State proof of the GET_FEES reply
+    pub proof: Option<ProofStatus>,
+    /// Auth rule the requirements were looked up for
+    pub rule_key: Option<AuthRuleKey>,
+    /// Why the auth rule of the transaction could not be read
+    pub rule_error: Option<String>,
+    pub requirements: Vec<FeeRequirement>,
+    /// DID the fees were detected for, with its role on the ledger
+    pub signer: Option<(String, Option<String>)>,
+    /// Fee alias to pay when the signer satisfies branches with different fees
+    pub chosen_alias: Option<String>,
+    pub payment: PaymentChoice,
+    pub mock: MockPaymentHandler,
+}
+
+impl FeeState {
+    /// Drops what was detected on the previous network, keeping the handler
+    pub fn reset_detection(&mut self) {
+        self.schedule = None;
+        self.proof = None;
+        self.rule_key = None;
+        self.rule_error = None;
+        self.requirements.clear();
+        self.signer = None;
+        self.chosen_alias = None;
+    }
+
+    /// Role on the ledger of the signer the fees were detected for
+    fn signer_role(&self, signer_did: &str) -> Option<Option<&str>> {
+        match &self.signer {
+            Some((did, role)) if did == signer_did => Some(role.as_deref()),
+            _ => None,
+        }
+    }
+
+    /// Exact fee of the transaction of `key` for the signer, as detected
+    pub fn required_fee(
+        &self,
+        key: Option<&AuthRuleKey>,
+        signer_did: &str,
+    ) -> Option<anyhow::Result<u64>> {
+        if key.is_none() || self.rule_key.as_ref() != key {
+            return None;
+        }
+        let role = self.signer_role(signer_did)?;
+        Some(required_fee(
+            &self.requirements,
+            role,
+            self.chosen_alias.as_deref(),
+        ))
+    }
+
+    /// Handler and amount paying the transaction of `key`, when it has a fee
+    pub fn payment_for(
+        &self,
+        key: Option<&AuthRuleKey>,
+        signer_did: &str,
+    ) -> Option<(&dyn PaymentHandler, u64)> {
+        let amount = self.required_fee(key, signer_did)?.ok()?;
+        let handler: &dyn PaymentHandler = match self.payment {
+            PaymentChoice::None => return None,
+            PaymentChoice::Mock => &self.mock,
+        };
+        (amount > 0).then_some((handler, amount))
+    }
+}
+
 // FIXME: This function has too many arguments. Consider grouping them into a struct.
 #[allow(clippy::too_many_arguments)]
 pub fn publish_tool_ui(
     ui: &mut Ui,
-    wallet: &mut Option<IndyWallet>,
+    signer: &dyn Signer,
     publish_option: &mut String,
     nym_role: &mut MyRoles,
     nym_info: &mut NymInfo,
     genesis_source: &mut Option<GenesisSource>,
+    namespace: Option<&str>,
     ledgers: &mut Option<IndyLedger>,
     txn_result: &mut String,
     schema_info: &mut SchemaInfo,
+    revoc_reg_def_info: &mut RevocRegDefInfo,
+    revoc_reg_entry_info: &mut RevocRegEntryInfo,
+    pool_admin_info: &mut PoolAdminInfo,
+    node_info: &mut NodeInfo,
     txn: &mut String,
     transaction_options: &mut TransactionOptions,
+    mirror: Option<&LedgerMirror>,
+    production: bool,
+    review: &mut WriteReview,
+    fees: &mut FeeState,
 ) -> anyhow::Result<()> {
     ui.label("Publish something on a ledger");
 
@@ -43,6 +200,12 @@ pub fn publish_tool_ui(
         //PublishEntities::CredDef,
         PublishEntities::Nym,
         PublishEntities::Schema,
+        PublishEntities::RevocRegDef,
+        PublishEntities::RevocRegEntry,
+        PublishEntities::Node,
+        PublishEntities::PoolUpgrade,
+        PublishEntities::PoolConfig,
+        PublishEntities::PoolRestart,
         PublishEntities::Custom,
     ];
 
@@ -65,6 +228,10 @@ pub fn publish_tool_ui(
         );
     }
 
+    let fee_key = prepared_auth_rule_key(publish_option, nym_role, txn);
+    fees_ui(ui, ledgers.as_ref(), fee_key.as_ref(), signer.did(), fees);
+    let payment = fees.payment_for(fee_key.as_ref(), signer.did());
+
     ComboBox::from_id_source("publish_option")
         .selected_text(publish_option.as_str())
         .show_ui(ui, |ui| {
@@ -141,13 +308,19 @@ pub fn publish_tool_ui(
             for attribute in &schema_info.attributes {
                 ui.label(attribute);
             }
-            let wallet_ref = wallet.as_ref().unwrap();
+            let schema_id = SchemaId::new(
+                &DidValue(signer.did().to_string()),
+                &schema_info.schema_name.clone(),
+                &schema_info.schema_version.clone(),
+            );
+            ui.label(format!("Schema ID: {}", schema_id.0));
+            if let Some(qualified_id) =
+                namespace.and_then(|ns| qualified_object_id(&schema_id.0, ns))
+            {
+                ui.label(format!("Qualified schema ID: {}", qualified_id));
+            }
             let schema_to_publish: IndySchema = IndySchema::SchemaV1(IndySchemaV1 {
-                id: SchemaId::new(
-                    &DidValue(wallet_ref.did.clone()),
-                    &schema_info.schema_name.clone(),
-                    &schema_info.schema_version.clone(),
-                ),
+                id: schema_id,
                 name: schema_info.schema_name.clone(),
                 version: schema_info.schema_version.clone(),
                 attr_names: AttributeNames::from(schema_info.attributes.clone()),
@@ -163,37 +336,596 @@ pub fn publish_tool_ui(
             if ui.button("Register Schema").clicked() && genesis_source.is_some() {
                 ui.label("Registering Schema...");
 
-                let wallet_ref = wallet.as_ref().unwrap();
                 if let Some(ledger) = ledgers {
                     match block_on(IndyLedger::publish_schema(
                         ledger,
-                        wallet_ref,
-                        &wallet_ref.did,
+                        signer,
+                        signer.did(),
                         &schema_to_publish,
-                        transaction_options,
+                        &transaction_options.without_send(),
                     )) {
                         Ok(result) => {
-                            // Store only the raw transaction JSON
-                            *txn_result = result;
-
-                            // Display additional context in the UI only
-                            ui.label(if transaction_options.send {
-                                "Schema transaction submitted successfully:"
-                            } else if transaction_options.sign {
-                                "Signed schema transaction (not submitted):"
-                            } else {
-                                "Unsigned schema transaction:"
-                            });
-
-                            // Display the formatted transaction
-                            ui.monospace(&*txn_result);
+                            prepared_write(
+                                result,
+                                transaction_options.send,
+                                payment,
+                                publish_option,
+                                txn_result,
+                                review,
+                            );
                         }
                         Err(e) => {
-                            *txn_result = e.to_string();
+                            *txn_result = format!("Error: {}", e);
+                        }
+                    }
+                }
+            }
+        }
+    //endregion
+    } else if *publish_option == PublishEntities::RevocRegDef.to_string() {
+        //region REVOC_REG_DEF REGISTRATION
+        ui.heading("Revocation registry definition");
+        ui.colored_label(
+            egui::Color32::from_rgb(144, 238, 144),
+            "Enter the values generated by your issuer agent for the tails file",
+        );
+
+        ui.label("Credential definition ID:");
+        ui.add(
+            egui::TextEdit::singleline(&mut revoc_reg_def_info.cred_def_id)
+                .hint_text("<did>:3:CL:<schema_seq_no>:<tag>")
+                .desired_width(f32::INFINITY),
+        );
+        ui.label("Tag:");
+        ui.add(egui::TextEdit::singleline(&mut revoc_reg_def_info.tag).hint_text("Enter tag"));
+        ui.label("Issuance type:");
+        ComboBox::from_id_source("revoc_issuance_type")
+            .selected_text(revoc_reg_def_info.issuance_type.as_str())
+            .show_ui(ui, |ui| {
+                for issuance_type in ISSUANCE_TYPES {
+                    ui.selectable_value(
+                        &mut revoc_reg_def_info.issuance_type,
+                        issuance_type.to_string(),
+                        issuance_type,
+                    );
+                }
+            });
+        ui.label("Max credential number:");
+        ui.add(
+            egui::TextEdit::singleline(&mut revoc_reg_def_info.max_cred_num).hint_text("e.g. 1000"),
+        );
+        ui.label("Tails hash:");
+        ui.add(
+            egui::TextEdit::singleline(&mut revoc_reg_def_info.tails_hash)
+                .hint_text("Base58 SHA256 of the tails file")
+                .desired_width(f32::INFINITY),
+        );
+        ui.label("Tails location:");
+        ui.add(
+            egui::TextEdit::singleline(&mut revoc_reg_def_info.tails_location)
+                .hint_text("https://tails.example.com/<hash>")
+                .desired_width(f32::INFINITY),
+        );
+        ui.label("Accumulator public key (z):");
+        ui.add(
+            egui::TextEdit::multiline(&mut revoc_reg_def_info.accum_key)
+                .hint_text("1 0A1B... 1 0C2D...")
+                .desired_rows(2)
+                .desired_width(f32::INFINITY),
+        );
+
+        match build_revoc_reg_def(signer.did(), revoc_reg_def_info) {
+            Ok((revoc_reg_def_id, revoc_reg_def)) => {
+                ui.label("The revocation registry definition seems valid.");
+                ui.label(format!("Revocation registry ID: {}", revoc_reg_def_id.0));
+                if let Some(qualified_id) =
+                    namespace.and_then(|ns| qualified_object_id(&revoc_reg_def_id.0, ns))
+                {
+                    ui.label(format!(
+                        "Qualified revocation registry ID: {}",
+                        qualified_id
+                    ));
+                }
+                if ui.button("Register Revocation Registry").clicked() {
+                    if let Some(ledger) = ledgers {
+                        match block_on(ledger.publish_revoc_reg_def(
+                            signer,
+                            signer.did(),
+                            &revoc_reg_def,
+                            &transaction_options.without_send(),
+                        )) {
+                            Ok(result) => {
+                                prepared_write(
+                                    result,
+                                    transaction_options.send,
+                                    payment,
+                                    publish_option,
+                                    txn_result,
+                                    review,
+                                );
+                            }
+                            Err(e) => {
+                                *txn_result = format!("Error: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                ui.colored_label(
+                    egui::Color32::LIGHT_RED,
+                    format!("Invalid revocation registry definition: {:#}", e),
+                );
+            }
+        }
+    //endregion
+    } else if *publish_option == PublishEntities::RevocRegEntry.to_string() {
+        //region REVOC_REG_ENTRY REGISTRATION
+        ui.heading("Revocation registry entry");
+        ui.colored_label(
+            egui::Color32::from_rgb(144, 238, 144),
+            "Enter the accumulator update produced by your issuer agent",
+        );
+
+        ui.label("Revocation registry ID:");
+        ui.add(
+            egui::TextEdit::singleline(&mut revoc_reg_entry_info.revoc_reg_def_id)
+                .hint_text("<did>:4:<cred_def_id>:CL_ACCUM:<tag>")
+                .desired_width(f32::INFINITY),
+        );
+        ui.label("Previous accumulator (empty for the first entry):");
+        ui.add(
+            egui::TextEdit::singleline(&mut revoc_reg_entry_info.prev_accum)
+                .desired_width(f32::INFINITY),
+        );
+        ui.label("New accumulator:");
+        ui.add(
+            egui::TextEdit::singleline(&mut revoc_reg_entry_info.accum)
+                .desired_width(f32::INFINITY),
+        );
+        ui.label("Max credential number of the registry:");
+        ui.add(
+            egui::TextEdit::singleline(&mut revoc_reg_entry_info.max_cred_num)
+                .hint_text("e.g. 1000"),
+        );
+        ui.label("Issued indices:");
+        ui.add(
+            egui::TextEdit::singleline(&mut revoc_reg_entry_info.issued)
+                .hint_text("e.g. 1, 2, 5-7"),
+        );
+        ui.label("Revoked indices:");
+        ui.add(
+            egui::TextEdit::singleline(&mut revoc_reg_entry_info.revoked).hint_text("e.g. 3, 4"),
+        );
+
+        match build_revoc_reg_entry(revoc_reg_entry_info) {
+            Ok((revoc_reg_def_id, revoc_reg_entry)) => {
+                ui.label("The revocation registry entry seems valid.");
+                if ui.button("Register Revocation Entry").clicked() {
+                    if let Some(ledger) = ledgers {
+                        match block_on(ledger.publish_revoc_reg_entry(
+                            signer,
+                            signer.did(),
+                            &revoc_reg_def_id,
+                            &revoc_reg_entry,
+                            &transaction_options.without_send(),
+                        )) {
+                            Ok(result) => {
+                                prepared_write(
+                                    result,
+                                    transaction_options.send,
+                                    payment,
+                                    publish_option,
+                                    txn_result,
+                                    review,
+                                );
+                            }
+                            Err(e) => {
+                                *txn_result = format!("Error: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                ui.colored_label(
+                    egui::Color32::LIGHT_RED,
+                    format!("Invalid revocation registry entry: {:#}", e),
+                );
+            }
+        }
+    //endregion
+    } else if *publish_option == PublishEntities::Node.to_string() {
+        //region NODE
+        ui.heading("Node");
+        ui.colored_label(
+            egui::Color32::from_rgb(144, 238, 144),
+            "Add a validator node or update one of your nodes (stewards only)",
+        );
+
+        if node_info.genesis_nodes.is_none() {
+            if let Some(ledger) = ledgers {
+                node_info.genesis_nodes = Some(
+                    genesis_nodes(&ledger.pool.get_transactions()).map_err(|e| format!("{:#}", e)),
+                );
+            }
+        }
+        // Taken out while the form may be reset, put back below
+        let cached_nodes = node_info.genesis_nodes.take();
+        let nodes = match &cached_nodes {
+            Some(Ok(nodes)) => nodes.as_slice(),
+            Some(Err(e)) => {
+                ui.colored_label(
+                    egui::Color32::LIGHT_RED,
+                    format!("Failed to read the genesis nodes: {}", e),
+                );
+                &[]
+            }
+            None => &[],
+        };
+        let selected_text = node_info
+            .existing
+            .as_ref()
+            .map_or("New node".to_string(), |node| {
+                format!("Update {}", node.alias)
+            });
+        ComboBox::from_id_source("node_existing")
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                if ui
+                    .selectable_label(node_info.existing.is_none(), "New node")
+                    .clicked()
+                {
+                    *node_info = NodeInfo::default();
+                }
+                for node in nodes {
+                    let selected = node_info.existing.as_ref() == Some(node);
+                    if ui
+                        .selectable_label(selected, format!("Update {}", node.alias))
+                        .clicked()
+                    {
+                        prefill_node_info(node_info, node);
+                    }
+                }
+            });
+        node_info.genesis_nodes = cached_nodes;
+
+        egui::Grid::new("node_form").num_columns(2).show(ui, |ui| {
+            ui.label("Node DID (verkey):");
+            ui.add_enabled(
+                node_info.existing.is_none(),
+                egui::TextEdit::singleline(&mut node_info.dest).desired_width(400.0),
+            );
+            ui.end_row();
+            ui.label("Alias:");
+            ui.add_enabled(
+                node_info.existing.is_none(),
+                egui::TextEdit::singleline(&mut node_info.alias),
+            );
+            ui.end_row();
+            ui.label("Node IP / port:");
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut node_info.node_ip).hint_text("10.0.0.2"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut node_info.node_port)
+                        .hint_text("9701")
+                        .desired_width(60.0),
+                );
+            });
+            ui.end_row();
+            ui.label("Client IP / port:");
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut node_info.client_ip).hint_text("10.0.0.2"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut node_info.client_port)
+                        .hint_text("9702")
+                        .desired_width(60.0),
+                );
+            });
+            ui.end_row();
+            ui.label("BLS key:");
+            ui.add(
+                egui::TextEdit::multiline(&mut node_info.blskey)
+                    .desired_rows(2)
+                    .desired_width(400.0),
+            );
+            ui.end_row();
+            ui.label("BLS key proof of possession:");
+            ui.add(
+                egui::TextEdit::multiline(&mut node_info.blskey_pop)
+                    .desired_rows(2)
+                    .desired_width(400.0),
+            );
+            ui.end_row();
+        });
+        ui.checkbox(
+            &mut node_info.validator,
+            "Validator (uncheck to demote the node)",
+        );
+
+        if let Some(node) = &node_info.existing {
+            if node.steward != signer.did() {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!(
+                        "⚠️ {} belongs to steward {}, the ledger will reject updates signed by another DID",
+                        node.alias, node.steward
+                    ),
+                );
+            }
+        }
+
+        match build_node_data(node_info) {
+            Ok((dest, data)) => {
+                if ui.button("Register Node").clicked() {
+                    if let Some(ledger) = ledgers {
+                        match block_on(ledger.publish_node(
+                            signer,
+                            &dest,
+                            data,
+                            &transaction_options.without_send(),
+                        )) {
+                            Ok(result) => {
+                                prepared_write(
+                                    result,
+                                    transaction_options.send,
+                                    payment,
+                                    publish_option,
+                                    txn_result,
+                                    review,
+                                );
+                            }
+                            Err(e) => {
+                                *txn_result = format!("Error: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                ui.colored_label(egui::Color32::LIGHT_RED, format!("Invalid node: {:#}", e));
+            }
+        }
+    //endregion
+    } else if *publish_option == PublishEntities::PoolUpgrade.to_string() {
+        //region POOL_UPGRADE
+        ui.heading("Pool upgrade");
+        ui.colored_label(
+            egui::Color32::from_rgb(144, 238, 144),
+            "Schedule the upgrade of the validator nodes (trustees only)",
+        );
+
+        egui::Grid::new("pool_upgrade_form")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Name:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut pool_admin_info.upgrade_name)
+                        .hint_text("e.g. upgrade-1126"),
+                );
+                ui.end_row();
+                ui.label("Version:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut pool_admin_info.upgrade_version)
+                        .hint_text("e.g. 1.12.6"),
+                );
+                ui.end_row();
+                ui.label("Action:");
+                ComboBox::from_id_source("pool_upgrade_action")
+                    .selected_text(pool_admin_info.upgrade_action.as_str())
+                    .show_ui(ui, |ui| {
+                        for action in POOL_ACTIONS {
+                            ui.selectable_value(
+                                &mut pool_admin_info.upgrade_action,
+                                action.to_string(),
+                                action,
+                            );
+                        }
+                    });
+                ui.end_row();
+                ui.label("SHA256:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut pool_admin_info.upgrade_sha256)
+                        .hint_text("Hash of the package version")
+                        .desired_width(450.0),
+                );
+                ui.end_row();
+                ui.label("Package:");
+                ui.text_edit_singleline(&mut pool_admin_info.upgrade_package);
+                ui.end_row();
+                ui.label("Timeout (minutes):");
+                ui.text_edit_singleline(&mut pool_admin_info.upgrade_timeout);
+                ui.end_row();
+                ui.label("Justification:");
+                ui.text_edit_singleline(&mut pool_admin_info.upgrade_justification);
+                ui.end_row();
+            });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut pool_admin_info.upgrade_force, "Force");
+            ui.checkbox(&mut pool_admin_info.upgrade_reinstall, "Reinstall");
+        });
+
+        ui.separator();
+        ui.label("Schedule:");
+        ui.horizontal(|ui| {
+            ui.label("Start:");
+            ui.add(
+                egui::TextEdit::singleline(&mut pool_admin_info.schedule_start)
+                    .hint_text("2025-01-31T22:00:00+00:00"),
+            );
+            ui.label("Minutes between nodes:");
+            ui.add(
+                egui::TextEdit::singleline(&mut pool_admin_info.schedule_interval)
+                    .desired_width(40.0),
+            );
+            if ui.button("Generate from genesis").clicked() {
+                if let Some(ledger) = ledgers {
+                    match genesis_nodes(&ledger.pool.get_transactions()).and_then(|nodes| {
+                        generate_schedule(
+                            &nodes,
+                            &pool_admin_info.schedule_start,
+                            &pool_admin_info.schedule_interval,
+                        )
+                    }) {
+                        Ok(schedule) => pool_admin_info.schedule = schedule,
+                        Err(e) => *txn_result = format!("Error: {:#}", e),
+                    }
+                }
+            }
+        });
+        let mut removed = None;
+        egui::Grid::new("pool_upgrade_schedule")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, entry) in pool_admin_info.schedule.iter_mut().enumerate() {
+                    ui.label(&entry.alias);
+                    ui.monospace(&entry.dest);
+                    ui.text_edit_singleline(&mut entry.time);
+                    if ui.small_button("🗑").clicked() {
+                        removed = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = removed {
+            pool_admin_info.schedule.remove(index);
+        }
+
+        match build_pool_upgrade(pool_admin_info) {
+            Ok(upgrade) => {
+                if ui.button("Register Pool Upgrade").clicked() {
+                    if let Some(ledger) = ledgers {
+                        match block_on(ledger.publish_pool_upgrade(
+                            signer,
+                            &upgrade,
+                            &transaction_options.without_send(),
+                        )) {
+                            Ok(result) => {
+                                prepared_write(
+                                    result,
+                                    transaction_options.send,
+                                    payment,
+                                    publish_option,
+                                    txn_result,
+                                    review,
+                                );
+                            }
+                            Err(e) => {
+                                *txn_result = format!("Error: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                ui.colored_label(
+                    egui::Color32::LIGHT_RED,
+                    format!("Invalid pool upgrade: {:#}", e),
+                );
+            }
+        }
+    //endregion
+    } else if *publish_option == PublishEntities::PoolConfig.to_string() {
+        //region POOL_CONFIG
+        ui.heading("Pool config");
+        ui.colored_label(
+            egui::Color32::from_rgb(144, 238, 144),
+            "Enable or freeze writes on the pool (trustees only)",
+        );
+        ui.checkbox(&mut pool_admin_info.config_writes, "Writes allowed");
+        ui.checkbox(&mut pool_admin_info.config_force, "Force");
+        if !pool_admin_info.config_writes {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                "⚠️ Only pool administration transactions will be accepted once writes are disabled",
+            );
+        }
+
+        if ui.button("Register Pool Config").clicked() {
+            if let Some(ledger) = ledgers {
+                match block_on(ledger.publish_pool_config(
+                    signer,
+                    pool_admin_info.config_writes,
+                    pool_admin_info.config_force,
+                    &transaction_options.without_send(),
+                )) {
+                    Ok(result) => {
+                        prepared_write(
+                            result,
+                            transaction_options.send,
+                            payment,
+                            publish_option,
+                            txn_result,
+                            review,
+                        );
+                    }
+                    Err(e) => {
+                        *txn_result = format!("Error: {}", e);
+                    }
+                }
+            }
+        }
+    //endregion
+    } else if *publish_option == PublishEntities::PoolRestart.to_string() {
+        //region POOL_RESTART
+        ui.heading("Pool restart");
+        ui.colored_label(
+            egui::Color32::from_rgb(144, 238, 144),
+            "Restart all the nodes of the pool (trustees only)",
+        );
+        ui.horizontal(|ui| {
+            ui.label("Action:");
+            ComboBox::from_id_source("pool_restart_action")
+                .selected_text(pool_admin_info.restart_action.as_str())
+                .show_ui(ui, |ui| {
+                    for action in POOL_ACTIONS {
+                        ui.selectable_value(
+                            &mut pool_admin_info.restart_action,
+                            action.to_string(),
+                            action,
+                        );
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Restart time:");
+            ui.add(
+                egui::TextEdit::singleline(&mut pool_admin_info.restart_datetime)
+                    .hint_text("Empty for now, or 2025-01-31T22:00:00+00:00"),
+            );
+        });
+
+        match restart_datetime(&pool_admin_info.restart_datetime) {
+            Ok(datetime) => {
+                if ui.button("Send Pool Restart").clicked() {
+                    if let Some(ledger) = ledgers {
+                        match block_on(ledger.publish_pool_restart(
+                            signer,
+                            &pool_admin_info.restart_action,
+                            &datetime,
+                            &transaction_options.without_send(),
+                        )) {
+                            Ok(result) => {
+                                prepared_write(
+                                    result,
+                                    transaction_options.send,
+                                    payment,
+                                    publish_option,
+                                    txn_result,
+                                    review,
+                                );
+                            }
+                            Err(e) => {
+                                *txn_result = format!("Error: {}", e);
+                            }
                         }
                     }
                 }
             }
+            Err(e) => {
+                ui.colored_label(egui::Color32::LIGHT_RED, format!("{:#}", e));
+            }
         }
     //endregion
     } else if *publish_option == PublishEntities::Custom.to_string() {
@@ -206,10 +938,48 @@ pub fn publish_tool_ui(
             "Paste your transaction below",
         );
 
+        ui.horizontal(|ui| {
+            for (label, pretty) in [("Pretty-print", true), ("Minify", false)] {
+                if ui.button(label).clicked() {
+                    match format_json(txn, pretty) {
+                        Ok(formatted) => *txn = formatted,
+                        Err(e) => *txn_result = format!("Error: {:#}", e),
+                    }
+                }
+            }
+        });
+        let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+            let mut job = highlight_json(text);
+            job.wrap.max_width = wrap_width;
+            ui.fonts(|fonts| fonts.layout_job(job))
+        };
         ui.add(
             egui::TextEdit::multiline(txn)
                 .hint_text("Input Transaction")
-                .desired_width(f32::INFINITY),
+                .code_editor()
+                .desired_width(f32::INFINITY)
+                .layouter(&mut layouter),
+        );
+
+        let issues = if txn.trim().is_empty() {
+            Vec::new()
+        } else {
+            validate_request(txn)
+        };
+        for issue in &issues {
+            if issue.warning {
+                ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", issue.message));
+            } else {
+                ui.colored_label(egui::Color32::LIGHT_RED, format!("❌ {}", issue.message));
+            }
+        }
+        let is_valid = !txn.trim().is_empty() && issues.iter().all(|issue| issue.warning);
+        request_freshness_ui(
+            ui,
+            txn,
+            &mut transaction_options.refresh_req_id,
+            ledgers.as_ref(),
+            mirror,
         );
         ui.separator();
 
@@ -226,15 +996,25 @@ pub fn publish_tool_ui(
             );
         }
 
-        if ui.button("Prepare Custom Transaction").clicked() {
+        if ui
+            .add_enabled(is_valid, egui::Button::new("Prepare Custom Transaction"))
+            .clicked()
+        {
             if let Some(ledger) = ledgers {
                 match block_on(ledger.prepare_transaction(
-                    wallet.as_ref().unwrap(),
+                    signer,
                     txn,
-                    transaction_options,
+                    &transaction_options.without_send(),
                 )) {
                     Ok(result) => {
-                        *txn_result = result;
+                        prepared_write(
+                            result,
+                            transaction_options.send,
+                            payment,
+                            publish_option,
+                            txn_result,
+                            review,
+                        );
                     }
                     Err(e) => {
                         *txn_result = format!("Error: {}", e);
@@ -256,15 +1036,15 @@ pub fn publish_tool_ui(
             ui.colored_label(egui::Color32::from_rgb(144, 238, 144), "NYM DID: ");
             ui.add(
                 egui::TextEdit::singleline(&mut nym_info.did)
-                    .char_limit(32)
-                    .hint_text("NYM DID"),
+                    .hint_text("NYM DID, bare or did:sov:/did:indy:<namespace>:"),
             );
             ui.label("NYM Verkey: ");
             ui.add(egui::TextEdit::singleline(&mut nym_info.verkey).hint_text("NYM Verkey"));
         });
-        let is_valid_did = &DidValue((*nym_info.did.to_string()).parse()?).validate();
-        match is_valid_did {
-            Ok(_) => ui.label("The entered NYM DID seems valid."),
+        let nym_did = QualifiedDid::parse_on_network(&nym_info.did, namespace)
+            .and_then(|did| did.check_namespace(namespace).map(|_| did));
+        match &nym_did {
+            Ok(did) => ui.label(format!("The entered NYM DID seems valid: {}", did)),
             Err(e) => ui.label(format!("Invalid NYM DID: {} ", e)),
         };
         let is_valid_verkey = &DidValue((*nym_info.verkey.to_string()).parse()?).validate();
@@ -297,14 +1077,68 @@ pub fn publish_tool_ui(
                 ui.selectable_value(&mut *nym_role, MyRoles::Trustee, "Trustee");
             });
 
+        ui.separator();
+        ui.label("did:indy options (only accepted by DID:INDY networks)");
+        ui.label("NYM version:");
+        let version_text = NYM_VERSIONS
+            .iter()
+            .find(|(version, _)| Some(*version) == nym_info.version)
+            .map_or("Not set", |(_, label)| label);
+        egui::ComboBox::from_id_source("nym_version")
+            .selected_text(version_text)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut nym_info.version, None, "Not set");
+                for (version, label) in NYM_VERSIONS {
+                    ui.selectable_value(&mut nym_info.version, Some(version), label);
+                }
+            });
+        let version_check = match nym_info.version {
+            Some(version) if !nym_info.verkey.is_empty() => match &nym_did {
+                Ok(did) => check_nym_version(&did.id, &nym_info.verkey, version),
+                Err(_) => Ok(()),
+            },
+            _ => Ok(()),
+        };
+        if let Err(e) = &version_check {
+            ui.colored_label(egui::Color32::LIGHT_RED, e.to_string());
+        }
+
+        ui.label("DIDDoc content (JSON, optional):");
+        ui.add(
+            egui::TextEdit::multiline(&mut nym_info.diddoc_content)
+                .code_editor()
+                .hint_text(r#"{"service": [{"id": "...#didcomm", "type": "DIDCommMessaging", "serviceEndpoint": "https://..."}]}"#)
+                .desired_rows(4)
+                .desired_width(f32::INFINITY),
+        );
+        let diddoc_check = validate_diddoc_content(&nym_info.diddoc_content);
+        match &diddoc_check {
+            Ok(Some(_)) => {
+                ui.label("The DIDDoc content seems valid.");
+            }
+            Ok(None) => {}
+            Err(e) => {
+                ui.colored_label(
+                    egui::Color32::LIGHT_RED,
+                    format!("Invalid DIDDoc content: {:#}", e),
+                );
+            }
+        }
+
         // Check each field and add the name of the missing fields to a vector
         let mut missing_fields = Vec::new();
-        if nym_info.did.is_empty() || !is_valid_did.is_ok() {
+        if nym_info.did.is_empty() || nym_did.is_err() {
             missing_fields.push("NYM DID");
         }
         if nym_info.verkey.is_empty() || !is_valid_verkey.is_ok() {
             missing_fields.push("NYM Verkey");
         }
+        if version_check.is_err() {
+            missing_fields.push("NYM version");
+        }
+        if diddoc_check.is_err() {
+            missing_fields.push("DIDDoc content");
+        }
         if genesis_source.is_none() {
             missing_fields.push("genesis file");
         }
@@ -323,7 +1157,6 @@ pub fn publish_tool_ui(
             );
         } else if ui.button("Prepare NYM Transaction").clicked() {
             if let Some(ledger) = ledgers {
-                let wallet_ref = wallet.as_ref().unwrap();
                 let role = match nym_role {
                     MyRoles::Author => UpdateRole::Reset,
                     MyRoles::Endorser => UpdateRole::Set(LedgerRole::Endorser),
@@ -333,32 +1166,21 @@ pub fn publish_tool_ui(
                 };
 
                 match block_on(ledger.publish_nym(
-                    wallet_ref,
-                    &wallet_ref.did,
+                    signer,
+                    signer.did(),
                     nym_info,
                     role,
-                    transaction_options,
+                    &transaction_options.without_send(),
                 )) {
                     Ok(result) => {
-                        // Store only the raw transaction JSON in txn_result
-                        *txn_result = result;
-
-                        // Display additional context in the UI only
-                        ui.label(if transaction_options.send {
-                            "Transaction submitted successfully:"
-                        } else if transaction_options.sign {
-                            "Signed transaction (not submitted):"
-                        } else {
-                            "Unsigned transaction:"
-                        });
-
-                        // Display the formatted transaction
-                        ui.monospace(&*txn_result);
-
-                        if transaction_options.send {
-                            nym_info.did.clear();
-                            nym_info.verkey.clear();
-                        }
+                        prepared_write(
+                            result,
+                            transaction_options.send,
+                            payment,
+                            publish_option,
+                            txn_result,
+                            review,
+                        );
                     }
                     Err(e) => {
                         *txn_result = format!("Error: {}", e);
@@ -367,6 +1189,29 @@ pub fn publish_tool_ui(
             }
         }
     }
+
+    let target = WriteTarget {
+        genesis_source: genesis_source.as_ref(),
+        namespace,
+        production,
+    };
+    if let Some(pending) = write_review_ui(ui.ctx(), review, target) {
+        if let Some(ledger) = ledgers.as_ref() {
+            match block_on(ledger.submit_signed(&pending.request)) {
+                Ok(reply) => {
+                    *txn_result = reply;
+                    if pending.publish_option == PublishEntities::Nym.to_string() {
+                        nym_info.did.clear();
+                        nym_info.verkey.clear();
+                    }
+                }
+                Err(e) => {
+                    *txn_result = format!("Error: {}", e);
+                }
+            }
+        }
+    }
+
     // Add copy button for the transaction result
     ui.separator();
     // Clone once at the start before any usage
@@ -393,6 +1238,24 @@ pub fn publish_tool_ui(
                         "Prepared schema transaction (unsigned):"
                     }
                 }
+                "RevocRegDef" | "RevocRegEntry" => {
+                    if transaction_options.send {
+                        "Revocation transaction submitted successfully:"
+                    } else if transaction_options.sign {
+                        "Signed revocation transaction (not submitted):"
+                    } else {
+                        "Prepared revocation transaction (unsigned):"
+                    }
+                }
+                "Node" | "PoolUpgrade" | "PoolConfig" | "PoolRestart" => {
+                    if transaction_options.send {
+                        "Pool transaction submitted successfully:"
+                    } else if transaction_options.sign {
+                        "Signed pool transaction (not submitted):"
+                    } else {
+                        "Prepared pool transaction (unsigned):"
+                    }
+                }
                 "Custom" => {
                     if transaction_options.send {
                         "Transaction submitted successfully:"
@@ -418,12 +1281,457 @@ pub fn publish_tool_ui(
     Ok(())
 }
 
+/// Pays a prepared request, then holds it for review when it is to be sent.
+/// Returns the request when it is only to be shown.
+pub fn hold_for_review(
+    request: String,
+    send: bool,
+    payment: Option<(&dyn PaymentHandler, u64)>,
+    publish_option: &str,
+    review: &mut WriteReview,
+) -> anyhow::Result<Option<String>> {
+    let request = match payment {
+        Some((handler, amount)) => pay_request(&request, handler, amount)?,
+        None => request,
+    };
+    if !send {
+        return Ok(Some(request));
+    }
+    review
+        .open(&request, publish_option)
+        .map_err(|e| e.context("Failed to review the request"))?;
+    Ok(None)
+}
+
+/// `hold_for_review` for the tools keeping their outcome as `Option<Result<_, String>>`,
+/// `None` while the write is being reviewed
+pub fn held_write_result(
+    result: Result<String, String>,
+    send: bool,
+    publish_option: &str,
+    review: &mut WriteReview,
+) -> Option<Result<String, String>> {
+    result
+        .and_then(|request| {
+            hold_for_review(request, send, None, publish_option, review)
+                .map_err(|e| format!("{:#}", e))
+        })
+        .transpose()
+}
+
+/// Shows a prepared request, or holds it for review when it is to be sent
+fn prepared_write(
+    result: String,
+    send: bool,
+    payment: Option<(&dyn PaymentHandler, u64)>,
+    publish_option: &str,
+    txn_result: &mut String,
+    review: &mut WriteReview,
+) {
+    match hold_for_review(result, send, payment, publish_option, review) {
+        Ok(Some(request)) => *txn_result = request,
+        Ok(None) => txn_result.clear(),
+        Err(e) => *txn_result = format!("Error: {:#}", e),
+    }
+}
+
+fn pay_request(
+    request_text: &str,
+    handler: &dyn PaymentHandler,
+    amount: u64,
+) -> anyhow::Result<String> {
+    let mut request: Value = serde_json::from_str(request_text)?;
+    block_on(attach_fees(&mut request, handler, amount))?;
+    Ok(serde_json::to_string_pretty(&request)?)
+}
+
+/// Lists what the pending write does. Returns it once confirmed, production
+/// networks also need their name typed and unsigned requests an acknowledgement.
+pub fn write_review_ui(
+    ctx: &egui::Context,
+    review: &mut WriteReview,
+    target: WriteTarget<'_>,
+) -> Option<PendingWrite> {
+    let WriteTarget {
+        genesis_source,
+        namespace,
+        production,
+    } = target;
+    let pending = review.pending.as_ref()?;
+    let summary = &pending.summary;
+    let location = genesis_source.map_or("no network", GenesisSource::location);
+    // Typed before writing to a production network
+    let phrase = namespace.unwrap_or(location);
+    let mut confirmed = false;
+    let mut cancelled = false;
+
+    egui::Window::new("Review write")
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            egui::Grid::new("write_review_summary")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Network");
+                    ui.horizontal(|ui| {
+                        match namespace {
+                            Some(namespace) => {
+                                ui.colored_label(
+                                    egui::Color32::KHAKI,
+                                    format!("{} ({})", namespace, location),
+                                );
+                            }
+                            None => {
+                                ui.colored_label(egui::Color32::KHAKI, location);
+                            }
+                        }
+                        if production {
+                            ui.colored_label(egui::Color32::RED, "PRODUCTION");
+                        }
+                    });
+                    ui.end_row();
+                    ui.label("Signed by");
+                    if summary.signers.is_empty() {
+                        ui.colored_label(egui::Color32::LIGHT_RED, "Not signed");
+                    } else {
+                        ui.colored_label(egui::Color32::KHAKI, summary.signers.join(", "));
+                    }
+                    ui.end_row();
+                    ui.label("Type");
+                    ui.colored_label(egui::Color32::KHAKI, &summary.txn_type);
+                    ui.end_row();
+                    ui.label("TAA acceptance");
+                    ui.label(&summary.taa);
+                    ui.end_row();
+                    ui.label("Endorser");
+                    ui.label(summary.endorser.as_deref().unwrap_or("None"));
+                    ui.end_row();
+                    ui.label("Fees");
+                    ui.label(&summary.fees);
+                    ui.end_row();
+                });
+
+            ui.separator();
+            ui.label("Operation:");
+            egui::ScrollArea::vertical()
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("write_review_operation")
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (field, value) in &summary.operation {
+                                ui.label(field);
+                                ui.monospace(value);
+                                ui.end_row();
+                            }
+                        });
+                });
+            egui::CollapsingHeader::new("Full request").show(ui, |ui| {
+                ui.monospace(serde_json::to_string_pretty(&pending.request).unwrap_or_default());
+            });
+
+            ui.separator();
+            let unsigned = summary.signers.is_empty();
+            if unsigned {
+                ui.checkbox(
+                    &mut review.unsigned_acknowledged,
+                    "Send without any signature, the ledger rejects it unless the write needs none",
+                );
+            }
+            if production {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!("⚠️ Production network, type {} to confirm", phrase),
+                );
+                ui.text_edit_singleline(&mut review.typed_confirmation);
+            }
+            let allowed = (!unsigned || review.unsigned_acknowledged)
+                && (!production || review.typed_confirmation.trim() == phrase);
+            ui.horizontal(|ui| {
+                confirmed = ui
+                    .add_enabled(allowed, egui::Button::new("📤 Confirm and send"))
+                    .clicked();
+                cancelled = ui.button("Cancel").clicked();
+            });
+        });
+
+    if confirmed || cancelled {
+        review.typed_confirmation.clear();
+        let pending = review.pending.take();
+        if confirmed {
+            return pending;
+        }
+    }
+    None
+}
+
+/// Fee detection and payment handler for the transaction being prepared
+fn fees_ui(
+    ui: &mut Ui,
+    ledger: Option<&IndyLedger>,
+    key: Option<&AuthRuleKey>,
+    signer_did: &str,
+    fees: &mut FeeState,
+) {
+    egui::CollapsingHeader::new("Fees").show(ui, |ui| {
+        if ui
+            .add_enabled(ledger.is_some(), egui::Button::new("🔍 Detect fees"))
+            .clicked()
+        {
+            if let Some(ledger) = ledger {
+                fees.reset_detection();
+                let schedule = match block_on(ledger.get_fees(Some(signer_did))) {
+                    Ok(reply) => {
+                        fees.proof = Some(reply.proof);
+                        Ok(reply.data)
+                    }
+                    Err(e) => Err(e.to_string()),
+                };
+                if let (Ok(schedule), Some(key)) = (&schedule, key) {
+                    match block_on(ledger.get_auth_rules(Some(key))) {
+                        Ok(rules) => {
+                            let constraint = rules.data.first().map(|rule| &rule["constraint"]);
+                            fees.requirements =
+                                fee_requirements(&key.auth_type, constraint, schedule);
+                            fees.rule_key = Some(key.clone());
+                        }
+                        Err(e) => fees.rule_error = Some(format!("GET_AUTH_RULE failed: {}", e)),
+                    }
+                    // The branch paid depends on the role of the signer
+                    match block_on(ledger.get_nym(signer_did)) {
+                        Ok(nym) => {
+                            let role = nym
+                                .data
+                                .and_then(|nym| nym["role"].as_str().map(str::to_string));
+                            fees.signer = Some((signer_did.to_string(), role));
+                        }
+                        Err(e) => {
+                            fees.rule_error = Some(format!("GET_NYM of the signer failed: {}", e))
+                        }
+                    }
+                }
+                fees.schedule = Some(schedule);
+            }
+        }
+
+        if let Some(proof) = &fees.proof {
+            proof.show(ui);
+        }
+        match &fees.schedule {
+            None => {
+                ui.label("Fees not checked on this network");
+            }
+            Some(Err(e)) => {
+                ui.label(format!("No payment plugin detected ({})", e));
+            }
+            Some(Ok(_)) if fees.rule_error.is_some() => {
+                ui.colored_label(
+                    egui::Color32::RED,
+                    fees.rule_error.as_deref().unwrap_or_default(),
+                );
+            }
+            Some(Ok(_))
+                if fees.rule_key.is_none()
+                    || fees.rule_key.as_ref() != key
+                    || fees.signer_role(signer_did).is_none() =>
+            {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    "Fees were detected for another transaction or signer, detect them again",
+                );
+            }
+            Some(Ok(_)) if fees.requirements.is_empty() => {
+                ui.colored_label(egui::Color32::LIGHT_GREEN, "No fee for this transaction");
+            }
+            Some(Ok(_)) => {
+                let role = fees.signer_role(signer_did).flatten().map(str::to_string);
+                let role = role.as_deref();
+                let applicable = applicable_fees(&fees.requirements, role);
+                // Several met branches with different fees, the one to pay is chosen
+                let ambiguous = applicable
+                    .iter()
+                    .map(|requirement| requirement.amount.unwrap_or_default())
+                    .collect::<BTreeSet<_>>()
+                    .len()
+                    > 1;
+                let applicable: Vec<String> = applicable
+                    .into_iter()
+                    .map(|requirement| requirement.alias.clone())
+                    .collect();
+                for requirement in &fees.requirements {
+                    let text = match requirement.amount {
+                        Some(amount) => format!("{}: {}", requirement.alias, amount),
+                        None => format!("{}: not in the fee schedule", requirement.alias),
+                    };
+                    if !applicable.contains(&requirement.alias) {
+                        ui.label(format!(
+                            "{} (not met by a {} signer)",
+                            text,
+                            role_name(role)
+                        ));
+                    } else if ambiguous {
+                        ui.radio_value(
+                            &mut fees.chosen_alias,
+                            Some(requirement.alias.clone()),
+                            text,
+                        );
+                    } else {
+                        ui.label(format!("✔ {}", text));
+                    }
+                }
+                match fees.required_fee(key, signer_did) {
+                    Some(Ok(amount)) => {
+                        ui.colored_label(
+                            egui::Color32::KHAKI,
+                            format!("Cost of this transaction: {}", amount),
+                        );
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(egui::Color32::YELLOW, format!("{:#}", e));
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Payment handler:");
+            ComboBox::from_id_source("payment_handler")
+                .selected_text(match fees.payment {
+                    PaymentChoice::None => "None",
+                    PaymentChoice::Mock => "Mock (testing)",
+                })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut fees.payment, PaymentChoice::None, "None");
+                    ui.selectable_value(&mut fees.payment, PaymentChoice::Mock, "Mock (testing)");
+                });
+            if fees.payment == PaymentChoice::Mock {
+                ui.label("Balance:");
+                ui.add(egui::DragValue::new(&mut fees.mock.balance));
+            }
+        });
+        if fees.payment_for(key, signer_did).is_some() {
+            ui.label("Fee inputs are attached to the prepared request");
+        }
+    });
+}
+
+/// reqId handling of a pasted request: regenerate it while unsigned, warn about replays
+/// once signed (stale reqId, or already written according to the mirror or the ledger)
+pub fn request_freshness_ui(
+    ui: &mut Ui,
+    txn: &mut String,
+    refresh_req_id: &mut bool,
+    ledger: Option<&IndyLedger>,
+    mirror: Option<&LedgerMirror>,
+) {
+    let Ok(request) = serde_json::from_str::<serde_json::Value>(txn) else {
+        return;
+    };
+    let protocol_version = ledger.map_or(ProtocolVersion::default().to_id(), |ledger| {
+        ledger.protocol_version()
+    });
+
+    if !is_signed(&request) {
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                refresh_req_id,
+                "Regenerate reqId and protocolVersion before signing",
+            );
+            if ui.button("↻ New reqId").clicked() {
+                if let Ok(refreshed) = refresh_request(txn, protocol_version) {
+                    *txn = refreshed;
+                }
+            }
+        });
+        return;
+    }
+
+    for warning in freshness_warnings(txn, std::time::SystemTime::now()) {
+        ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", warning.message));
+    }
+    let (Some(identifier), Some(req_id)) =
+        (request["identifier"].as_str(), request["reqId"].as_u64())
+    else {
+        return;
+    };
+    if let Some(written) = mirror.and_then(|mirror| mirror.find_request(identifier, req_id)) {
+        ui.colored_label(
+            egui::Color32::YELLOW,
+            format!(
+                "⚠ This request was already written as transaction #{} (local mirror)",
+                written["txnMetadata"]["seqNo"]
+            ),
+        );
+    }
+
+    let (Some(ledger), Some("1")) = (ledger, request["operation"]["type"].as_str()) else {
+        return;
+    };
+    let check_id = ui.id().with(("written_nym", req_id));
+    ui.horizontal(|ui| {
+        if ui.button("Check the ledger for this NYM").clicked() {
+            let outcome = match block_on(ledger.find_written_nym(&request)) {
+                Ok(Some(seq_no)) => format!(
+                    "⚠ Already on the ledger as transaction #{}, sending it again will be rejected",
+                    seq_no
+                ),
+                Ok(None) => "The ledger has no NYM written from this request".to_string(),
+                Err(e) => format!("Failed to check the ledger: {}", e),
+            };
+            ui.data_mut(|data| data.insert_temp(check_id, outcome));
+        }
+        if let Some(outcome) = ui.data(|data| data.get_temp::<String>(check_id)) {
+            ui.label(outcome);
+        }
+    });
+}
+
+/// Opens a prepared request in the Custom transaction form
+pub fn load_custom_transaction(publish_option: &mut String, txn: &mut String, request: &str) {
+    *publish_option = PublishEntities::Custom.to_string();
+    *txn = request.to_string();
+}
+
+/// Auth rule that applies to the transaction being prepared in the Publish Tool
+pub fn prepared_auth_rule_key(
+    publish_option: &str,
+    nym_role: &MyRoles,
+    txn: &str,
+) -> Option<AuthRuleKey> {
+    if publish_option == PublishEntities::Nym.to_string() {
+        Some(AuthRuleKey::add(
+            "1",
+            "role",
+            role_code(nym_role).unwrap_or_default(),
+        ))
+    } else if publish_option == PublishEntities::Schema.to_string() {
+        Some(AuthRuleKey::add("101", "*", "*"))
+    } else if publish_option == PublishEntities::RevocRegDef.to_string() {
+        Some(AuthRuleKey::add("113", "*", "*"))
+    } else if publish_option == PublishEntities::RevocRegEntry.to_string() {
+        Some(AuthRuleKey::add("114", "*", "*"))
+    } else if publish_option == PublishEntities::PoolUpgrade.to_string() {
+        Some(AuthRuleKey::add("109", "action", "start"))
+    } else if publish_option == PublishEntities::PoolConfig.to_string() {
+        Some(AuthRuleKey::add("111", "action", "*"))
+    } else if publish_option == PublishEntities::PoolRestart.to_string() {
+        Some(AuthRuleKey::add("118", "action", "*"))
+    } else if publish_option == PublishEntities::Custom.to_string() {
+        let txn: serde_json::Value = serde_json::from_str(txn).ok()?;
+        let txn_type = txn["operation"]["type"].as_str()?;
+        Some(AuthRuleKey::add(txn_type, "*", "*"))
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::app::MyRoles;
     use indy_vdr::ledger::{constants, RequestBuilder};
-    use indy_vdr::pool::ProtocolVersion;
 
     #[test]
     fn test_author_role_mapping() -> anyhow::Result<()> {
@@ -472,6 +1780,64 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_write_review_open() {
+        let mut review = WriteReview {
+            typed_confirmation: "sovrin".to_string(),
+            ..Default::default()
+        };
+        let request = r#"{"identifier":"V4SGRU86Z58d6TV7PBUe6f","operation":{"type":"1","dest":"7RR5ZhPkxRnNFsV6uhNDfq"},"reqId":1}"#;
+        review.open(request, "Nym").unwrap();
+        let pending = review.pending.as_ref().unwrap();
+        assert_eq!(pending.summary.txn_type, "NYM (1)");
+        assert_eq!(pending.publish_option, "Nym");
+        assert!(review.typed_confirmation.is_empty());
+
+        let mut txn_result = String::new();
+        prepared_write(
+            "not json".to_string(),
+            true,
+            None,
+            "Custom",
+            &mut txn_result,
+            &mut review,
+        );
+        assert!(txn_result.starts_with("Error:"));
+        prepared_write(
+            request.to_string(),
+            false,
+            None,
+            "Custom",
+            &mut txn_result,
+            &mut review,
+        );
+        assert_eq!(txn_result, request);
+
+        let mock = MockPaymentHandler::default();
+        prepared_write(
+            request.to_string(),
+            false,
+            Some((&mock, 10)),
+            "Custom",
+            &mut txn_result,
+            &mut review,
+        );
+        assert!(txn_result.contains("\"fees\""));
+
+        review.pending = None;
+        review.unsigned_acknowledged = true;
+        assert_eq!(
+            held_write_result(Ok(request.to_string()), true, "AuthRule", &mut review),
+            None
+        );
+        assert_eq!(review.pending.as_ref().unwrap().publish_option, "AuthRule");
+        assert!(!review.unsigned_acknowledged);
+        assert_eq!(
+            held_write_result(Err("timeout".to_string()), true, "AuthRule", &mut review),
+            Some(Err("timeout".to_string()))
+        );
+    }
+
     #[test]
     fn test_transaction_options_default() {
         let options = TransactionOptions::default();
@@ -484,6 +1850,12 @@ mod tests {
         assert_eq!(PublishEntities::Nym.to_string(), "Nym");
         assert_eq!(PublishEntities::Schema.to_string(), "Schema");
         assert_eq!(PublishEntities::Custom.to_string(), "Custom");
+        assert_eq!(PublishEntities::RevocRegDef.to_string(), "RevocRegDef");
+        assert_eq!(PublishEntities::RevocRegEntry.to_string(), "RevocRegEntry");
+        assert_eq!(PublishEntities::Node.to_string(), "Node");
+        assert_eq!(PublishEntities::PoolUpgrade.to_string(), "PoolUpgrade");
+        assert_eq!(PublishEntities::PoolConfig.to_string(), "PoolConfig");
+        assert_eq!(PublishEntities::PoolRestart.to_string(), "PoolRestart");
         assert_eq!(PublishEntities::CredDef.to_string(), "CredDef");
         assert_eq!(PublishEntities::Attrib.to_string(), "Attrib");
     }
@@ -537,4 +1909,29 @@ mod tests {
             UpdateRole::Set(LedgerRole::Trustee)
         ));
     }
+
+    #[test]
+    fn test_prepared_auth_rule_key() {
+        assert_eq!(
+            prepared_auth_rule_key("Nym", &MyRoles::Endorser, ""),
+            Some(AuthRuleKey::add("1", "role", "101"))
+        );
+        assert_eq!(
+            prepared_auth_rule_key("Schema", &MyRoles::Author, ""),
+            Some(AuthRuleKey::add("101", "*", "*"))
+        );
+        assert_eq!(
+            prepared_auth_rule_key(
+                "Custom",
+                &MyRoles::Author,
+                r#"{"operation": {"type": "102"}}"#
+            ),
+            Some(AuthRuleKey::add("102", "*", "*"))
+        );
+        assert_eq!(
+            prepared_auth_rule_key("Custom", &MyRoles::Author, "not json"),
+            None
+        );
+        assert_eq!(prepared_auth_rule_key("", &MyRoles::Author, ""), None);
+    }
 }
@@ -0,0 +1,181 @@
+use crate::helpers::did::QualifiedDid;
+use crate::helpers::diddoc::{resolve_did, resolve_did_offline, ResolvedDid};
+use crate::helpers::ledgers::IndyLedger;
+use crate::helpers::mirror::LedgerMirror;
+use egui::Ui;
+use futures_executor::block_on;
+use rfd::FileDialog;
+
+#[derive(Default)]
+pub struct ResolverState {
+    pub did_input: String,
+    pub resolved: Option<ResolvedDid>,
+    pub error: Option<String>,
+    pub offline: bool,
+}
+
+pub fn resolver_tool_ui(
+    ui: &mut Ui,
+    ledgers: &Option<IndyLedger>,
+    mirror: &Option<LedgerMirror>,
+    namespace: Option<&str>,
+    state: &mut ResolverState,
+) {
+    ui.label("Resolve a did:sov or did:indy DID into a DID Document");
+
+    if ledgers.is_none() && mirror.is_none() {
+        ui.colored_label(
+            egui::Color32::LIGHT_RED,
+            "Please connect to a ledger in the Publish Tool first",
+        );
+        return;
+    }
+    if let Some(mirror) = mirror {
+        ui.checkbox(
+            &mut state.offline,
+            format!(
+                "Resolve offline from the local mirror ({} transactions)",
+                mirror.len()
+            ),
+        );
+    }
+
+    match namespace {
+        Some(namespace) => ui.label(format!(
+            "Bare identifiers are resolved as did:indy:{}:<id>",
+            namespace
+        )),
+        None => ui.label(
+            "No did:indy namespace is configured for this network, bare identifiers are resolved as did:sov",
+        ),
+    };
+
+    ui.horizontal(|ui| {
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut state.did_input)
+                .hint_text("did:indy:<namespace>:<id> or did:sov:<id>")
+                .desired_width(400.0),
+        );
+        let enter_pressed = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+        if ui.button("Resolve").clicked() || enter_pressed {
+            state.resolved = None;
+            state.error = None;
+            let did = QualifiedDid::parse_on_network(&state.did_input, namespace)
+                .and_then(|did| did.check_namespace(namespace).map(|_| did));
+            match did {
+                Ok(did) => {
+                    let resolved = match (mirror, ledgers) {
+                        (Some(mirror), _) if state.offline => resolve_did_offline(mirror, &did),
+                        (_, Some(ledger)) => block_on(resolve_did(ledger, &did)),
+                        _ => Err(anyhow::anyhow!(
+                            "Not connected, enable offline resolution from the mirror"
+                        )),
+                    };
+                    match resolved {
+                        Ok(resolved) => state.resolved = Some(resolved),
+                        Err(e) => state.error = Some(format!("{:#}", e)),
+                    }
+                }
+                Err(e) => state.error = Some(format!("Invalid DID: {}", e)),
+            }
+        }
+    });
+
+    if let Some(error) = &state.error {
+        ui.colored_label(egui::Color32::LIGHT_RED, error);
+    }
+
+    let Some(resolved) = &state.resolved else {
+        return;
+    };
+
+    ui.separator();
+    ui.colored_label(egui::Color32::KHAKI, format!("DID: {}", resolved.did));
+    ui.label(format!(
+        "Role: {}  |  Seq No: {}  |  Txn time: {}",
+        resolved.nym["role"]
+            .as_str()
+            .map_or("Author (none)".to_string(), |role| role.to_string()),
+        resolved.nym["seqNo"],
+        resolved.nym["txnTime"]
+    ));
+    match &resolved.proof {
+        Some(proof) => proof.show(ui),
+        None => {
+            ui.label("Resolved offline from the local mirror, no state proof");
+        }
+    }
+
+    ui.colored_label(egui::Color32::KHAKI, "Verification methods:");
+    for method in resolved.document["verificationMethod"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        ui.monospace(format!(
+            "{}  ({})",
+            method["id"].as_str().unwrap_or_default(),
+            method["type"].as_str().unwrap_or_default()
+        ));
+    }
+
+    ui.colored_label(egui::Color32::KHAKI, "Services:");
+    match resolved.document["service"].as_array() {
+        Some(services) if !services.is_empty() => {
+            for service in services {
+                ui.monospace(format!(
+                    "{}  ({}) → {}",
+                    service["id"].as_str().unwrap_or_default(),
+                    service["type"].as_str().unwrap_or_default(),
+                    service["serviceEndpoint"]
+                ));
+            }
+        }
+        _ => {
+            ui.label("No services");
+        }
+    }
+
+    match (&resolved.diddoc_content, &resolved.endpoint) {
+        (Some(content), _) => {
+            ui.collapsing("diddocContent merged from the NYM", |ui| {
+                ui.monospace(serde_json::to_string_pretty(content).unwrap_or_default());
+            });
+        }
+        (None, Some(endpoint)) => {
+            ui.collapsing("Services built from the endpoint ATTRIB", |ui| {
+                ui.monospace(serde_json::to_string_pretty(endpoint).unwrap_or_default());
+            });
+        }
+        (None, None) => {}
+    }
+
+    ui.separator();
+    let document = serde_json::to_string_pretty(&resolved.document).unwrap_or_default();
+    egui::ScrollArea::vertical()
+        .max_height(300.0)
+        .show(ui, |ui| {
+            ui.monospace(&document);
+        });
+
+    ui.horizontal(|ui| {
+        if ui.button("📋 Copy DID Document").clicked() {
+            ui.output_mut(|o| o.copied_text = document.clone());
+        }
+        if ui.button("💾 Export JSON").clicked() {
+            if let Some(path) = FileDialog::new()
+                .set_file_name(format!("{}.json", resolved.did.id))
+                .add_filter("JSON", &["json"])
+                .save_file()
+            {
+                if let Err(e) = std::fs::write(&path, &document) {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        format!("Failed to export DID Document: {}", e),
+                    );
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,236 @@
+use crate::helpers::did::qualified_object_id;
+use crate::helpers::ledgers::IndyLedger;
+use crate::helpers::mirror::LedgerMirror;
+use crate::helpers::schema_lookup::{
+    lookup_schema_id, scan_cred_defs, CredDefSummary, FoundSchema,
+};
+use crate::helpers::state_proof::ProofStatus;
+use crate::helpers::wallet::IndyWallet;
+use egui::{Color32, Ui};
+use futures_executor::block_on;
+
+/// Transactions read per frame while scanning the ledger for cred defs, the
+/// scan goes on over the next frames so the window stays responsive
+const SCAN_PAGE: i32 = 10;
+
+#[derive(Default)]
+pub struct SchemaLookupState {
+    pub issuer_did: String,
+    pub name: String,
+    pub version: String,
+    pub schema: Option<FoundSchema>,
+    /// State proof of the GET_SCHEMA reply, `None` when the schema came from the mirror
+    pub proof: Option<ProofStatus>,
+    pub cred_defs: Vec<CredDefSummary>,
+    /// Next DOMAIN seq_no to scan for cred defs, `None` once the whole ledger was read
+    pub next_scan: Option<u64>,
+    pub scanning: bool,
+    pub error: Option<String>,
+}
+
+impl SchemaLookupState {
+    fn add_cred_defs(&mut self, found: impl IntoIterator<Item = CredDefSummary>) {
+        for cred_def in found {
+            if !self.cred_defs.iter().any(|known| known.id == cred_def.id) {
+                self.cred_defs.push(cred_def);
+            }
+        }
+    }
+}
+
+pub fn schema_lookup_tool_ui(
+    ui: &mut Ui,
+    wallet: &Option<IndyWallet>,
+    ledgers: &Option<IndyLedger>,
+    mirror: &Option<LedgerMirror>,
+    namespace: Option<&str>,
+    state: &mut SchemaLookupState,
+) {
+    ui.label("Find a schema by issuer DID, name and version, and the cred defs built on it");
+
+    if ledgers.is_none() && mirror.is_none() {
+        ui.colored_label(
+            Color32::LIGHT_RED,
+            "Please connect to a ledger in the Publish Tool first",
+        );
+        return;
+    }
+
+    egui::Grid::new("schema_lookup_inputs")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Issuer DID:");
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut state.issuer_did)
+                        .hint_text("did:indy:<namespace>:<id>, did:sov:<id> or <id>")
+                        .desired_width(320.0),
+                );
+                if let Some(wallet) = wallet {
+                    if ui.button("Use wallet DID").clicked() {
+                        state.issuer_did = wallet.did.clone();
+                    }
+                }
+            });
+            ui.end_row();
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut state.name);
+            ui.end_row();
+            ui.label("Version:");
+            ui.text_edit_singleline(&mut state.version);
+            ui.end_row();
+        });
+
+    let schema_id = lookup_schema_id(&state.issuer_did, &state.name, &state.version);
+    match &schema_id {
+        Ok(schema_id) => {
+            ui.label(format!("Schema ID: {}", schema_id.0));
+        }
+        Err(e) if !state.issuer_did.trim().is_empty() => {
+            ui.colored_label(Color32::LIGHT_RED, format!("{:#}", e));
+        }
+        Err(_) => {}
+    }
+
+    if ui
+        .add_enabled(schema_id.is_ok(), egui::Button::new("🔍 Look up"))
+        .clicked()
+    {
+        if let Ok(schema_id) = &schema_id {
+            state.schema = None;
+            state.proof = None;
+            state.cred_defs.clear();
+            state.next_scan = None;
+            state.scanning = false;
+            state.error = None;
+
+            let found = match ledgers {
+                Some(ledger) => block_on(ledger.get_schema(schema_id))
+                    .map_err(|e| anyhow::anyhow!("GET_SCHEMA failed: {}", e))
+                    .and_then(|reply| {
+                        state.proof = Some(reply.proof);
+                        FoundSchema::from_reply(schema_id, &reply.data)
+                    }),
+                None => Ok(mirror.as_ref().and_then(|mirror| {
+                    let issuer = schema_id.0.split(':').next().unwrap_or_default();
+                    mirror
+                        .schema(issuer, state.name.trim(), state.version.trim())
+                        .and_then(|txn| FoundSchema::from_txn(schema_id, txn))
+                })),
+            };
+            match found {
+                Ok(Some(schema)) => {
+                    if let Some(mirror) = mirror {
+                        let cred_defs = mirror
+                            .cred_defs_for_schema(schema.seq_no)
+                            .into_iter()
+                            .filter_map(|txn| CredDefSummary::from_txn(txn, schema.seq_no))
+                            .collect::<Vec<_>>();
+                        state.add_cred_defs(cred_defs);
+                        // Cred defs are written after their schema, resume after the mirror
+                        state.next_scan = Some(mirror.last_seq_no().max(schema.seq_no) + 1);
+                    } else {
+                        state.next_scan = Some(schema.seq_no + 1);
+                    }
+                    state.schema = Some(schema);
+                }
+                Ok(None) => state.error = Some("No schema with this ID on the ledger".to_string()),
+                Err(e) => state.error = Some(format!("{:#}", e)),
+            }
+        }
+    }
+
+    if let Some(error) = &state.error {
+        ui.colored_label(Color32::LIGHT_RED, error);
+    }
+
+    let Some(schema) = state.schema.clone() else {
+        return;
+    };
+    ui.separator();
+    match &state.proof {
+        Some(proof) => proof.show(ui),
+        None => {
+            ui.small("From the local mirror");
+        }
+    }
+    ui.horizontal(|ui| {
+        ui.colored_label(Color32::KHAKI, format!("Schema: {}", schema.id));
+        if ui.small_button("📋").clicked() {
+            ui.output_mut(|o| o.copied_text = schema.id.clone());
+        }
+    });
+    if let Some(qualified_id) = namespace.and_then(|ns| qualified_object_id(&schema.id, ns)) {
+        ui.label(format!("Qualified schema ID: {}", qualified_id));
+    }
+    ui.label(format!(
+        "Seq No: {}  |  Txn time: {}",
+        schema.seq_no,
+        schema
+            .txn_time
+            .map_or("unknown".to_string(), |time| time.to_string())
+    ));
+    ui.label(format!("Attributes: {}", schema.attr_names.join(", ")));
+
+    ui.separator();
+    ui.colored_label(Color32::KHAKI, "Credential definitions:");
+    if let Some(mirror) = mirror {
+        ui.small(format!(
+            "From the local mirror, synced up to seq_no {}",
+            mirror.last_seq_no()
+        ));
+    }
+    if state.cred_defs.is_empty() {
+        ui.label("None found yet");
+    }
+    for cred_def in &state.cred_defs {
+        ui.horizontal(|ui| {
+            ui.monospace(&cred_def.id);
+            if ui.small_button("📋").clicked() {
+                ui.output_mut(|o| o.copied_text = cred_def.id.clone());
+            }
+        });
+        if let Some(qualified_id) = namespace.and_then(|ns| qualified_object_id(&cred_def.id, ns)) {
+            ui.small(qualified_id);
+        }
+    }
+
+    match (ledgers, state.next_scan) {
+        (Some(ledger), Some(next)) if state.scanning => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(format!("Scanning the ledger, at seq_no {}", next));
+                if ui.button("Stop").clicked() {
+                    state.scanning = false;
+                }
+            });
+            if state.scanning {
+                match block_on(scan_cred_defs(ledger, schema.seq_no, next, SCAN_PAGE)) {
+                    Ok((found, next)) => {
+                        state.add_cred_defs(found);
+                        state.next_scan = next;
+                        state.scanning = next.is_some();
+                    }
+                    Err(e) => {
+                        state.error = Some(format!("{:#}", e));
+                        state.scanning = false;
+                    }
+                }
+                ui.ctx().request_repaint();
+            }
+        }
+        (Some(_), Some(next)) => {
+            if ui
+                .button(format!("Scan the ledger from seq_no {}", next))
+                .clicked()
+            {
+                state.error = None;
+                state.scanning = true;
+            }
+        }
+        (Some(_), None) => {
+            ui.label("The whole ledger was scanned");
+        }
+        (None, _) => {}
+    }
+}
@@ -1,11 +1,14 @@
 use crate::app::DIDVersion;
-use crate::helpers::genesis::GenesisSource;
-use crate::helpers::wallet::IndyWallet;
+use crate::helpers::did::{validate_namespace, QualifiedDid};
+use crate::helpers::genesis::{GenesisSource, PoolSettings};
+use crate::helpers::seed::{SeedFormat, SeedInput, SEED_LENGTH};
+use crate::helpers::wallet::{IndyWallet, LockedWallet};
 use egui::{Button, TextEdit, Ui};
 use futures_executor::block_on;
 use rfd::FileDialog;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
+use zeroize::Zeroizing;
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct RecentUrls {
@@ -48,27 +51,161 @@ impl RecentUrls {
     }
 }
 
-pub fn create_wallet_ui(
+/// did:indy namespace configured for each genesis source, keyed by file path or URL
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct NetworkNamespaces {
+    namespaces: HashMap<String, String>,
+}
+
+impl NetworkNamespaces {
+    pub fn get(&self, source: &GenesisSource) -> Option<&str> {
+        self.namespaces
+            .get(source.location())
+            .map(String::as_str)
+            .filter(|namespace| validate_namespace(namespace).is_ok())
+    }
+
+    pub fn set(&mut self, source: &GenesisSource, namespace: String) {
+        if namespace.trim().is_empty() {
+            self.namespaces.remove(source.location());
+        } else {
+            self.namespaces
+                .insert(source.location().to_string(), namespace.trim().to_string());
+        }
+    }
+
+    fn get_raw(&self, source: &GenesisSource) -> &str {
+        self.namespaces
+            .get(source.location())
+            .map_or("", String::as_str)
+    }
+}
+
+/// Pool settings configured for each genesis source, defaults when not customized
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct NetworkPoolSettings {
+    settings: HashMap<String, PoolSettings>,
+    /// Invalid values being edited for a network, never saved
+    #[serde(skip)]
+    draft: Option<(String, PoolSettings)>,
+}
+
+impl NetworkPoolSettings {
+    /// Saved settings of the network, defaults when they are not valid anymore
+    pub fn get(&self, source: &GenesisSource) -> PoolSettings {
+        self.settings
+            .get(source.location())
+            .filter(|settings| settings.validate().is_ok())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Saves the settings of the network, invalid values are rejected
+    pub fn set(&mut self, source: &GenesisSource, settings: PoolSettings) -> anyhow::Result<()> {
+        settings.validate()?;
+        if settings == PoolSettings::default() {
+            self.settings.remove(source.location());
+        } else {
+            self.settings
+                .insert(source.location().to_string(), settings);
+        }
+        Ok(())
+    }
+
+    /// Settings shown in the form: the invalid draft being fixed, or the saved ones
+    fn editing(&self, source: &GenesisSource) -> PoolSettings {
+        match &self.draft {
+            Some((location, draft)) if location == source.location() => draft.clone(),
+            _ => self.get(source),
+        }
+    }
+
+    /// Saves edited settings when valid, otherwise keeps them as a draft until fixed
+    fn edit(&mut self, source: &GenesisSource, settings: PoolSettings) {
+        self.draft = match self.set(source, settings.clone()) {
+            Ok(()) => None,
+            Err(_) => Some((source.location().to_string(), settings)),
+        };
+    }
+}
+
+/// Genesis sources tagged as production networks, writes to them need a typed confirmation
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ProductionNetworks {
+    locations: HashSet<String>,
+}
+
+impl ProductionNetworks {
+    pub fn contains(&self, source: &GenesisSource) -> bool {
+        self.locations.contains(source.location())
+    }
+
+    pub fn set(&mut self, source: &GenesisSource, production: bool) {
+        if production {
+            self.locations.insert(source.location().to_string());
+        } else {
+            self.locations.remove(source.location());
+        }
+    }
+}
+
+/// Masked seed entry with a format selector. Returns the decoded seed once the
+/// input is valid, and explains what is wrong with it otherwise.
+pub fn seed_input_ui(
     ui: &mut Ui,
-    seed: &mut String,
+    id_source: &str,
+    input: &mut SeedInput,
+) -> Option<Zeroizing<[u8; SEED_LENGTH]>> {
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source(format!("{}_format", id_source))
+            .selected_text(input.format.label())
+            .show_ui(ui, |ui| {
+                for format in SeedFormat::ALL {
+                    ui.selectable_value(&mut input.format, format, format.label());
+                }
+            });
+        ui.checkbox(&mut input.reveal, "Show");
+    });
+
+    let edit = if input.format == SeedFormat::Mnemonic {
+        TextEdit::multiline(&mut *input.text).desired_rows(2)
+    } else {
+        TextEdit::singleline(&mut *input.text)
+    };
+    ui.add(
+        edit.password(!input.reveal)
+            .hint_text(input.format.hint())
+            .desired_width(f32::INFINITY),
+    );
+
+    if input.is_empty() {
+        return None;
+    }
+    match input.decode() {
+        Ok(seed) => {
+            ui.colored_label(egui::Color32::GREEN, "✔ Valid 32 bytes seed");
+            Some(seed)
+        }
+        Err(e) => {
+            ui.colored_label(egui::Color32::LIGHT_RED, format!("{:#}", e));
+            None
+        }
+    }
+}
+
+fn new_wallet_ui(
+    ui: &mut Ui,
+    seed: &mut SeedInput,
     wallet: &mut Option<IndyWallet>,
-    genesis_source: &mut Option<GenesisSource>,
     did_version: &mut DIDVersion,
-    genesis_url_input: &mut String,
-    recent_urls: &mut RecentUrls,
-) -> anyhow::Result<()> {
+) {
     // Wallet Creation Section
     ui.colored_label(
         egui::Color32::from_rgb(144, 238, 144),
         "Create a new wallet",
     );
 
-    ui.add(
-        TextEdit::singleline(seed)
-            .char_limit(32)
-            .hint_text("Enter 32 bytes seed"),
-    );
-    ui.label(format!("Length: {}", seed.len()));
+    let decoded_seed = seed_input_ui(ui, "wallet_seed", seed);
 
     ui.colored_label(
         egui::Color32::from_rgb(144, 238, 144),
@@ -82,15 +219,22 @@ pub fn create_wallet_ui(
             ui.selectable_value(&mut *did_version, DIDVersion::Indy, "Indy");
         });
 
-    if ui.add(Button::new("Create Wallet")).clicked() && seed.len() == 32 {
-        let seed = seed.clone();
+    let create_clicked = ui
+        .add_enabled(decoded_seed.is_some(), Button::new("Create Wallet"))
+        .clicked();
+    if let (true, Some(decoded_seed)) = (create_clicked, decoded_seed) {
         let did_version_value = match did_version {
             DIDVersion::Sov => 1,
             DIDVersion::Indy => 2,
         };
-        match block_on(IndyWallet::new(Some(&seed), did_version_value)) {
+        match block_on(IndyWallet::new(
+            Some(decoded_seed.as_ref()),
+            did_version_value,
+        )) {
             Ok(new_wallet) => {
                 *wallet = Some(new_wallet);
+                // The key holds the secret from now on
+                seed.clear();
             }
             Err(e) => {
                 ui.colored_label(
@@ -100,12 +244,80 @@ pub fn create_wallet_ui(
             }
         }
     }
+}
+
+/// Asks the seed of a wallet locked by the session lock again
+fn unlock_wallet_ui(
+    ui: &mut Ui,
+    seed: &mut SeedInput,
+    wallet: &mut Option<IndyWallet>,
+    locked_wallet: &mut Option<LockedWallet>,
+) {
+    let Some(locked) = locked_wallet else {
+        return;
+    };
+    ui.colored_label(
+        egui::Color32::YELLOW,
+        format!("🔒 Wallet locked: {}", locked.did),
+    );
+    ui.label("Enter the seed of this DID again to sign");
+    let decoded_seed = seed_input_ui(ui, "unlock_seed", seed);
+    let mut forget = false;
+    ui.horizontal(|ui| {
+        let unlock_clicked = ui
+            .add_enabled(decoded_seed.is_some(), Button::new("🔓 Unlock"))
+            .clicked();
+        if let (true, Some(decoded_seed)) = (unlock_clicked, &decoded_seed) {
+            match locked.unlock(decoded_seed.as_ref()) {
+                Ok(unlocked) => *wallet = Some(unlocked),
+                Err(e) => {
+                    ui.colored_label(egui::Color32::RED, format!("{:#}", e));
+                }
+            }
+        }
+        forget = ui.button("Forget wallet").clicked();
+    });
+    if wallet.is_some() || forget {
+        seed.clear();
+        *locked_wallet = None;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_wallet_ui(
+    ui: &mut Ui,
+    seed: &mut SeedInput,
+    wallet: &mut Option<IndyWallet>,
+    locked_wallet: &mut Option<LockedWallet>,
+    genesis_source: &mut Option<GenesisSource>,
+    did_version: &mut DIDVersion,
+    genesis_url_input: &mut String,
+    recent_urls: &mut RecentUrls,
+    network_namespaces: &mut NetworkNamespaces,
+    network_pool_settings: &mut NetworkPoolSettings,
+    production_networks: &mut ProductionNetworks,
+) -> anyhow::Result<()> {
+    if locked_wallet.is_some() {
+        unlock_wallet_ui(ui, seed, wallet, locked_wallet);
+    } else {
+        new_wallet_ui(ui, seed, wallet, did_version);
+    }
 
     if let Some(wallet) = wallet {
         ui.label(format!(
             "Wallet created with DID: {} and Verkey: {}",
             wallet.did, wallet.verkey
         ));
+        let namespace = genesis_source
+            .as_ref()
+            .and_then(|source| network_namespaces.get(source));
+        let qualified_did = QualifiedDid::on_network(&wallet.did, namespace);
+        ui.horizontal(|ui| {
+            ui.label(format!("Qualified DID: {}", qualified_did));
+            if ui.small_button("📋").clicked() {
+                ui.output_mut(|o| o.copied_text = qualified_did.to_string());
+            }
+        });
     }
 
     ui.add_space(10.0);
@@ -210,6 +422,38 @@ pub fn create_wallet_ui(
                 }
             }
         });
+
+        ui.horizontal(|ui| {
+            ui.label("did:indy namespace:");
+            let mut namespace = network_namespaces.get_raw(source).to_string();
+            if ui
+                .add(TextEdit::singleline(&mut namespace).hint_text("e.g. sovrin:staging"))
+                .changed()
+            {
+                network_namespaces.set(source, namespace.clone());
+            }
+            if namespace.trim().is_empty() {
+                ui.small("Not set, DIDs are shown as did:sov");
+            } else if let Err(e) = validate_namespace(namespace.trim()) {
+                ui.colored_label(egui::Color32::LIGHT_RED, e.to_string());
+            }
+        });
+
+        let mut production = production_networks.contains(source);
+        if ui
+            .checkbox(&mut production, "Production network")
+            .on_hover_text("Writes must be confirmed by typing the network name")
+            .changed()
+        {
+            production_networks.set(source, production);
+        }
+
+        egui::CollapsingHeader::new("Pool settings").show(ui, |ui| {
+            let mut settings = network_pool_settings.editing(source);
+            if pool_settings_ui(ui, &mut settings) {
+                network_pool_settings.edit(source, settings);
+            }
+        });
     }
 
     // Helper text at the bottom with enhanced visibility
@@ -236,3 +480,121 @@ pub fn create_wallet_ui(
 
     Ok(())
 }
+
+/// Form for the pool settings of the active network, returns whether a value changed
+fn pool_settings_ui(ui: &mut Ui, settings: &mut PoolSettings) -> bool {
+    let mut changed = false;
+    egui::Grid::new("pool_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Protocol version:");
+            ui.horizontal(|ui| {
+                changed |= ui
+                    .radio_value(&mut settings.protocol_version, 2, "Node 1.4")
+                    .changed();
+                changed |= ui
+                    .radio_value(&mut settings.protocol_version, 1, "Node 1.3")
+                    .changed();
+            });
+            ui.end_row();
+
+            ui.label("Ack timeout (s):");
+            changed |= ui
+                .add(egui::DragValue::new(&mut settings.ack_timeout))
+                .changed();
+            ui.end_row();
+            ui.label("Reply timeout (s):");
+            changed |= ui
+                .add(egui::DragValue::new(&mut settings.reply_timeout))
+                .changed();
+            ui.end_row();
+            ui.label("Freshness threshold (s):");
+            changed |= ui
+                .add(egui::DragValue::new(&mut settings.freshness_threshold))
+                .changed();
+            ui.end_row();
+            ui.label("Connection request limit:");
+            changed |= ui
+                .add(egui::DragValue::new(&mut settings.conn_request_limit))
+                .changed();
+            ui.end_row();
+            ui.label("Connection active timeout (s):");
+            changed |= ui
+                .add(egui::DragValue::new(&mut settings.conn_active_timeout))
+                .changed();
+            ui.end_row();
+            ui.label("Read request nodes:");
+            changed |= ui
+                .add(egui::DragValue::new(&mut settings.request_read_nodes))
+                .changed();
+            ui.end_row();
+
+            ui.label("Genesis HTTP client timeout (s):");
+            changed |= ui
+                .add(egui::DragValue::new(&mut settings.genesis_client_timeout))
+                .changed();
+            ui.end_row();
+            ui.label("Genesis request timeout (s):");
+            changed |= ui
+                .add(egui::DragValue::new(&mut settings.genesis_request_timeout))
+                .changed();
+            ui.end_row();
+            ui.label("Genesis read timeout (s):");
+            changed |= ui
+                .add(egui::DragValue::new(&mut settings.genesis_read_timeout))
+                .changed();
+            ui.end_row();
+            ui.label("Connection timeout (s):");
+            changed |= ui
+                .add(egui::DragValue::new(&mut settings.connect_timeout))
+                .changed();
+            ui.end_row();
+        });
+
+    match settings.validate() {
+        Ok(()) => {
+            ui.small("Applied on the next connection to this network");
+        }
+        Err(e) => {
+            ui.colored_label(egui::Color32::LIGHT_RED, e.to_string());
+        }
+    }
+    if ui.button("Reset to defaults").clicked() {
+        *settings = PoolSettings::default();
+        changed = true;
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_pool_settings_reject_invalid() {
+        let source = GenesisSource::from_str("https://example.org/genesis").unwrap();
+        let mut networks = NetworkPoolSettings::default();
+
+        let mut settings = PoolSettings {
+            connect_timeout: 30,
+            ..PoolSettings::default()
+        };
+        networks.set(&source, settings.clone()).unwrap();
+        assert_eq!(networks.get(&source).connect_timeout, 30);
+
+        settings.ack_timeout = 0;
+        assert!(networks.set(&source, settings.clone()).is_err());
+        assert_eq!(
+            networks.get(&source).ack_timeout,
+            PoolSettings::default().ack_timeout
+        );
+
+        // The invalid edit stays in the form without replacing the saved settings
+        networks.edit(&source, settings);
+        assert_eq!(networks.editing(&source).ack_timeout, 0);
+        assert_eq!(networks.get(&source).connect_timeout, 30);
+        networks.edit(&source, PoolSettings::default());
+        assert!(networks.draft.is_none());
+        assert_eq!(networks.get(&source), PoolSettings::default());
+    }
+}